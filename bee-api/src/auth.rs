@@ -0,0 +1,436 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+//! Authentication of API requests through IP allow-lists and JWT bearer tokens.
+//!
+//! Every route belongs to a `RouteGroup`. A request is only served if its remote address is allowed for the group of
+//! the requested route and, unless the route is public, if it carries a token granting access to that group. Tokens
+//! are signed with a key derived from the node's Ed25519 identity so that they can't be forged without it.
+
+use crate::{config::ApiConfig, filters, rejection::CustomRejection};
+
+use bee_network::Keypair;
+
+use blake2::{
+    digest::{Update, VariableOutput},
+    VarBlake2b,
+};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use warp::{path::FullPath, reject, Filter, Rejection};
+
+use std::{
+    fmt,
+    net::SocketAddr,
+    str::FromStr,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Path prefixes of the routes belonging to the admin group, all other routes belonging to the data group.
+const ADMIN_ROUTES: [&str; 1] = ["/api/v1/peers"];
+const JWT_ISSUER: &str = "bee";
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Signing the token failed: {0}.")]
+    TokenSigningFailure(#[from] jsonwebtoken::errors::Error),
+}
+
+/// Groups of routes that are permitted separately.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RouteGroup {
+    /// Routes reading or submitting tangle data.
+    Data,
+    /// Routes managing the node, e.g. its peers.
+    Admin,
+}
+
+impl RouteGroup {
+    pub(crate) fn of(path: &str) -> Self {
+        if ADMIN_ROUTES.iter().any(|prefix| path.starts_with(prefix)) {
+            RouteGroup::Admin
+        } else {
+            RouteGroup::Data
+        }
+    }
+}
+
+impl FromStr for RouteGroup {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "data" => Ok(RouteGroup::Data),
+            "admin" => Ok(RouteGroup::Admin),
+            _ => Err(format!("invalid route group {}, expected data or admin", value)),
+        }
+    }
+}
+
+impl fmt::Display for RouteGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RouteGroup::Data => write!(f, "data"),
+            RouteGroup::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+struct Claims {
+    iss: String,
+    /// Hex encoded public key of the node the token was issued for.
+    sub: String,
+    iat: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exp: Option<u64>,
+    permissions: Vec<RouteGroup>,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+fn subject(keypair: &Keypair) -> String {
+    hex::encode(keypair.public().encode())
+}
+
+fn secret(keypair: &Keypair, salt: &str) -> [u8; 32] {
+    let mut secret = [0u8; 32];
+    let mut hasher = VarBlake2b::new(32).unwrap();
+
+    hasher.update(&keypair.encode()[..]);
+    hasher.update(salt.as_bytes());
+    hasher.finalize_variable(|digest| secret.copy_from_slice(digest));
+
+    secret
+}
+
+/// Issues a token granting access to the routes of the given groups, valid for `ttl` seconds or forever.
+pub fn issue_token(
+    config: &ApiConfig,
+    keypair: &Keypair,
+    permissions: Vec<RouteGroup>,
+    ttl: Option<u64>,
+) -> Result<String, Error> {
+    let iat = now();
+    let claims = Claims {
+        iss: JWT_ISSUER.to_string(),
+        sub: subject(keypair),
+        iat,
+        exp: ttl.map(|ttl| iat + ttl),
+        permissions,
+    };
+
+    Ok(jsonwebtoken::encode(
+        &Header::new(Algorithm::HS256),
+        &claims,
+        &EncodingKey::from_secret(&secret(keypair, &config.jwt_salt)),
+    )?)
+}
+
+#[derive(Clone)]
+pub(crate) struct Auth {
+    config: ApiConfig,
+    secret: Arc<[u8; 32]>,
+    subject: String,
+}
+
+impl Auth {
+    pub(crate) fn new(config: ApiConfig, keypair: &Keypair) -> Self {
+        Self {
+            secret: Arc::new(secret(keypair, &config.jwt_salt)),
+            subject: subject(keypair),
+            config,
+        }
+    }
+
+    fn is_public(&self, path: &str) -> bool {
+        self.config
+            .public_routes
+            .iter()
+            .any(|route| match route.strip_suffix('*') {
+                Some(prefix) => path.starts_with(prefix),
+                None => path == route,
+            })
+    }
+
+    fn claims(&self, authorization: Option<&str>) -> Result<Claims, Rejection> {
+        let token = authorization
+            .and_then(|authorization| authorization.strip_prefix("Bearer "))
+            .ok_or_else(|| reject::custom(CustomRejection::Unauthorized("Missing bearer token.".to_string())))?;
+
+        let mut validation = Validation::new(Algorithm::HS256);
+        // Tokens may not expire, the expiration is checked below when there is one.
+        validation.validate_exp = false;
+        validation.iss = Some(JWT_ISSUER.to_string());
+        validation.sub = Some(self.subject.clone());
+
+        let claims = jsonwebtoken::decode::<Claims>(token, &DecodingKey::from_secret(&*self.secret), &validation)
+            .map_err(|_| reject::custom(CustomRejection::Unauthorized("Invalid bearer token.".to_string())))?
+            .claims;
+
+        match claims.exp {
+            Some(exp) if exp < now() => Err(reject::custom(CustomRejection::Unauthorized(
+                "Expired bearer token.".to_string(),
+            ))),
+            _ => Ok(claims),
+        }
+    }
+
+    fn check(&self, path: &str, remote: Option<SocketAddr>, authorization: Option<&str>) -> Result<(), Rejection> {
+        let group = RouteGroup::of(path);
+
+        match remote {
+            Some(remote)
+                if self
+                    .config
+                    .allowed_ips(group)
+                    .iter()
+                    .any(|network| network.contains(&remote.ip())) => {}
+            _ => {
+                return Err(reject::custom(CustomRejection::Forbidden(
+                    "Address not allowed.".to_string(),
+                )))
+            }
+        }
+
+        if self.is_public(path) {
+            return Ok(());
+        }
+
+        if self.claims(authorization)?.permissions.contains(&group) {
+            Ok(())
+        } else {
+            Err(reject::custom(CustomRejection::Forbidden(format!(
+                "Token lacks the {} permission.",
+                group
+            ))))
+        }
+    }
+}
+
+/// Rejects the requests that are not allowed to reach the route they target.
+pub(crate) fn guard(auth: Auth) -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    warp::path::full()
+        .and(filters::remote())
+        .and(warp::header::optional::<String>("authorization"))
+        .and_then(
+            move |path: FullPath, remote: Option<SocketAddr>, authorization: Option<String>| {
+                let result = auth.check(path.as_str(), remote, authorization.as_deref());
+                async move { result }
+            },
+        )
+        .untuple_one()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use crate::config::{ApiConfigBuilder, IpNetwork};
+
+    use std::net::IpAddr;
+
+    fn ip(ip: &str) -> IpAddr {
+        ip.parse().unwrap()
+    }
+
+    fn remote(ip: &str) -> Option<SocketAddr> {
+        Some(SocketAddr::new(ip.parse().unwrap(), 50000))
+    }
+
+    fn config(salt: &str) -> ApiConfig {
+        ApiConfigBuilder::new()
+            .jwt_salt(salt.to_string())
+            .public_routes(vec!["/api/v1/info".to_string(), "/api/v1/messages/*".to_string()])
+            .allowed_ips(RouteGroup::Data, vec!["127.0.0.1/32".parse().unwrap()])
+            .allowed_ips(RouteGroup::Admin, vec!["127.0.0.1/32".parse().unwrap()])
+            .finish()
+            .unwrap()
+    }
+
+    fn bearer(token: &str) -> String {
+        format!("Bearer {}", token)
+    }
+
+    fn sign(keypair: &Keypair, salt: &str, claims: &Claims) -> String {
+        jsonwebtoken::encode(
+            &Header::new(Algorithm::HS256),
+            claims,
+            &EncodingKey::from_secret(&secret(keypair, salt)),
+        )
+        .unwrap()
+    }
+
+    fn is_unauthorized(result: Result<Claims, Rejection>) -> bool {
+        matches!(
+            result.err().as_ref().and_then(Rejection::find::<CustomRejection>),
+            Some(CustomRejection::Unauthorized(_))
+        )
+    }
+
+    #[test]
+    fn ipv4_network() {
+        let network = "192.168.1.0/24".parse::<IpNetwork>().unwrap();
+
+        assert!(network.contains(&ip("192.168.1.77")));
+        assert!(network.contains(&ip("::ffff:192.168.1.77")));
+        assert!(!network.contains(&ip("192.168.2.1")));
+        assert!(!network.contains(&ip("2001:db8::1")));
+
+        let network = "10.0.0.1/32".parse::<IpNetwork>().unwrap();
+
+        assert!(network.contains(&ip("10.0.0.1")));
+        assert!(!network.contains(&ip("10.0.0.2")));
+        assert_eq!("10.0.0.1".parse::<IpNetwork>().unwrap().to_string(), "10.0.0.1/32");
+
+        let network = "0.0.0.0/0".parse::<IpNetwork>().unwrap();
+
+        assert!(network.contains(&ip("1.2.3.4")));
+        assert!(network.contains(&ip("255.255.255.255")));
+    }
+
+    #[test]
+    fn ipv6_network() {
+        let network = "2001:db8::/32".parse::<IpNetwork>().unwrap();
+
+        assert!(network.contains(&ip("2001:db8:1::1")));
+        assert!(!network.contains(&ip("2001:db9::1")));
+        assert!(!network.contains(&ip("192.168.1.1")));
+
+        let network = "::1/128".parse::<IpNetwork>().unwrap();
+
+        assert!(network.contains(&ip("::1")));
+        assert!(!network.contains(&ip("::2")));
+
+        let network = "::/0".parse::<IpNetwork>().unwrap();
+
+        assert!(network.contains(&ip("2001:db8::1")));
+        assert!(network.contains(&ip("fe80::1")));
+    }
+
+    #[test]
+    fn malformed_network() {
+        assert!("".parse::<IpNetwork>().is_err());
+        assert!("localhost".parse::<IpNetwork>().is_err());
+        assert!("192.168.1.0/".parse::<IpNetwork>().is_err());
+        assert!("192.168.1.0/x".parse::<IpNetwork>().is_err());
+        assert!("192.168.1.0/33".parse::<IpNetwork>().is_err());
+        assert!("2001:db8::/129".parse::<IpNetwork>().is_err());
+        assert!("192.168.1.0/24/8".parse::<IpNetwork>().is_err());
+    }
+
+    #[test]
+    fn issued_token_is_verified() {
+        let keypair = Keypair::generate();
+        let config = config("salt");
+        let auth = Auth::new(config.clone(), &keypair);
+        let token = issue_token(&config, &keypair, vec![RouteGroup::Data], Some(3600)).unwrap();
+
+        let claims = auth.claims(Some(&bearer(&token))).unwrap();
+        assert_eq!(claims.permissions, vec![RouteGroup::Data]);
+        assert_eq!(claims.sub, subject(&keypair));
+
+        assert!(auth
+            .check("/api/v1/outputs/0", remote("127.0.0.1"), Some(&bearer(&token)))
+            .is_ok());
+        assert!(auth
+            .check("/api/v1/peers", remote("127.0.0.1"), Some(&bearer(&token)))
+            .is_err());
+        assert!(auth
+            .check("/api/v1/outputs/0", remote("127.0.0.1"), Some(&token))
+            .is_err());
+        assert!(auth.check("/api/v1/outputs/0", remote("127.0.0.1"), None).is_err());
+    }
+
+    #[test]
+    fn expired_token() {
+        let keypair = Keypair::generate();
+        let auth = Auth::new(config("salt"), &keypair);
+        let token = sign(
+            &keypair,
+            "salt",
+            &Claims {
+                iss: JWT_ISSUER.to_string(),
+                sub: subject(&keypair),
+                iat: now() - 20,
+                exp: Some(now() - 10),
+                permissions: vec![RouteGroup::Data],
+            },
+        );
+
+        assert!(is_unauthorized(auth.claims(Some(&bearer(&token)))));
+    }
+
+    #[test]
+    fn wrongly_signed_token() {
+        let keypair = Keypair::generate();
+        let auth = Auth::new(config("salt"), &keypair);
+        let token = issue_token(&config("other salt"), &keypair, vec![RouteGroup::Data], None).unwrap();
+
+        assert!(is_unauthorized(auth.claims(Some(&bearer(&token)))));
+        assert!(is_unauthorized(auth.claims(Some("Bearer not.a.token"))));
+    }
+
+    #[test]
+    fn token_of_another_node() {
+        let keypair = Keypair::generate();
+        let auth = Auth::new(config("salt"), &keypair);
+        let token = sign(
+            &keypair,
+            "salt",
+            &Claims {
+                iss: JWT_ISSUER.to_string(),
+                sub: subject(&Keypair::generate()),
+                iat: now(),
+                exp: None,
+                permissions: vec![RouteGroup::Data, RouteGroup::Admin],
+            },
+        );
+
+        assert!(is_unauthorized(auth.claims(Some(&bearer(&token)))));
+
+        let token = sign(
+            &keypair,
+            "salt",
+            &Claims {
+                iss: "hornet".to_string(),
+                sub: subject(&keypair),
+                iat: now(),
+                exp: None,
+                permissions: vec![RouteGroup::Data, RouteGroup::Admin],
+            },
+        );
+
+        assert!(is_unauthorized(auth.claims(Some(&bearer(&token)))));
+    }
+
+    #[test]
+    fn public_routes_bypass_tokens() {
+        let auth = Auth::new(config("salt"), &Keypair::generate());
+
+        assert!(auth.check("/api/v1/info", remote("127.0.0.1"), None).is_ok());
+        assert!(auth.check("/api/v1/messages/abc", remote("127.0.0.1"), None).is_ok());
+        assert!(auth.check("/api/v1/info/extra", remote("127.0.0.1"), None).is_err());
+        assert!(auth.check("/api/v1/milestones/1", remote("127.0.0.1"), None).is_err());
+        // The allowed addresses still apply to public routes.
+        assert!(auth.check("/api/v1/info", remote("10.0.0.1"), None).is_err());
+        assert!(auth.check("/api/v1/info", None, None).is_err());
+    }
+}
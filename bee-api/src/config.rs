@@ -0,0 +1,472 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::auth::RouteGroup;
+
+use serde::{Deserialize, Deserializer};
+use thiserror::Error;
+
+use std::{
+    fmt,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+const DEFAULT_BIND_ADDRESS: &str = "0.0.0.0:14265";
+const DEFAULT_HEALTH_MILESTONE_DISTANCE: u32 = 2;
+const DEFAULT_PUBLIC_ROUTES: [&str; 10] = [
+    "/health",
+    "/metrics",
+    "/api/v1/info",
+    "/api/v1/tips",
+    "/api/v1/messages*",
+    "/api/v1/outputs*",
+    "/api/v1/addresses*",
+    "/api/v1/milestones*",
+    "/api/v1/search*",
+    "/api/v1/events",
+];
+const DEFAULT_JWT_SALT: &str = "bee";
+const DEFAULT_ALLOWED_IPS_DATA: [&str; 2] = ["0.0.0.0/0", "::/0"];
+const DEFAULT_ALLOWED_IPS_ADMIN: [&str; 2] = ["127.0.0.1/32", "::1/128"];
+const DEFAULT_RATE_LIMIT_DATA: RateLimit = RateLimit { rate: 50.0, burst: 100 };
+const DEFAULT_RATE_LIMIT_ADMIN: RateLimit = RateLimit { rate: 10.0, burst: 20 };
+const DEFAULT_RATE_LIMIT_SUBMIT: RateLimit = RateLimit { rate: 5.0, burst: 10 };
+const DEFAULT_POW_CONCURRENCY: usize = 1;
+const DEFAULT_CORS_ALLOWED_METHODS: [&str; 4] = ["GET", "POST", "DELETE", "OPTIONS"];
+const DEFAULT_CORS_ALLOWED_HEADERS: [&str; 2] = ["content-type", "authorization"];
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Invalid {0} rate limit: the rate must be a positive number and the burst at least 1.")]
+    InvalidRateLimit(&'static str),
+    #[error("Incomplete TLS configuration: both a certificate and a key path are required.")]
+    IncompleteTls,
+}
+
+/// An IP network in CIDR notation, e.g. `192.168.0.0/16`. A plain address is a network of a single address.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IpNetwork {
+    address: IpAddr,
+    prefix: u8,
+}
+
+impl IpNetwork {
+    /// Returns whether `ip` belongs to the network, IPv4-mapped IPv6 addresses matching IPv4 networks.
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        match (self.address, ip) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - self.prefix as u32).unwrap_or(0);
+                u32::from(network) & mask == u32::from(*ip) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - self.prefix as u32).unwrap_or(0);
+                u128::from(network) & mask == u128::from(*ip) & mask
+            }
+            (IpAddr::V4(_), IpAddr::V6(ip)) => match ip.segments() {
+                [0, 0, 0, 0, 0, 0xffff, high, low] => self.contains(&IpAddr::V4(Ipv4Addr::new(
+                    (high >> 8) as u8,
+                    high as u8,
+                    (low >> 8) as u8,
+                    low as u8,
+                ))),
+                _ => false,
+            },
+            (IpAddr::V6(_), IpAddr::V4(ip)) => self.contains(&IpAddr::V6(ip.to_ipv6_mapped())),
+        }
+    }
+}
+
+impl FromStr for IpNetwork {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut parts = value.splitn(2, '/');
+        let address = parts
+            .next()
+            .unwrap_or_default()
+            .parse::<IpAddr>()
+            .map_err(|_| format!("invalid IP network {}", value))?;
+        let max_prefix = if address.is_ipv4() { 32 } else { 128 };
+        let prefix = match parts.next() {
+            Some(prefix) => prefix
+                .parse::<u8>()
+                .ok()
+                .filter(|prefix| *prefix <= max_prefix)
+                .ok_or_else(|| format!("invalid IP network prefix {}", value))?,
+            None => max_prefix,
+        };
+
+        Ok(Self { address, prefix })
+    }
+}
+
+impl fmt::Display for IpNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.address, self.prefix)
+    }
+}
+
+impl<'de> Deserialize<'de> for IpNetwork {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// A token bucket quota of `rate` requests per second on average, allowing bursts of up to `burst` requests.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+pub struct RateLimit {
+    pub(crate) rate: f64,
+    pub(crate) burst: u32,
+}
+
+impl RateLimit {
+    pub fn new(rate: f64, burst: u32) -> Self {
+        Self { rate, burst }
+    }
+
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    pub fn burst(&self) -> u32 {
+        self.burst
+    }
+
+    /// A rate that isn't positive would never refill the bucket, and a burst of zero would reject every request.
+    fn validate(self, name: &'static str) -> Result<Self, Error> {
+        if self.rate.is_finite() && self.rate > 0.0 && self.burst >= 1 {
+            Ok(self)
+        } else {
+            Err(Error::InvalidRateLimit(name))
+        }
+    }
+}
+
+#[derive(Default, Deserialize)]
+struct ApiRateLimitsConfigBuilder {
+    data: Option<RateLimit>,
+    admin: Option<RateLimit>,
+    submit: Option<RateLimit>,
+}
+
+#[derive(Default, Deserialize)]
+struct ApiTlsConfigBuilder {
+    cert_path: Option<PathBuf>,
+    key_path: Option<PathBuf>,
+}
+
+#[derive(Default, Deserialize)]
+struct ApiCorsConfigBuilder {
+    allowed_origins: Option<Vec<String>>,
+    allowed_methods: Option<Vec<String>>,
+    allowed_headers: Option<Vec<String>>,
+}
+
+#[derive(Default, Deserialize)]
+struct ApiAllowedIpsConfigBuilder {
+    data: Option<Vec<IpNetwork>>,
+    admin: Option<Vec<IpNetwork>>,
+}
+
+#[derive(Default, Deserialize)]
+pub struct ApiConfigBuilder {
+    bind_address: Option<SocketAddr>,
+    health_milestone_distance: Option<u32>,
+    public_routes: Option<Vec<String>>,
+    jwt_salt: Option<String>,
+    #[serde(default)]
+    allowed_ips: ApiAllowedIpsConfigBuilder,
+    #[serde(default)]
+    rate_limits: ApiRateLimitsConfigBuilder,
+    pow_concurrency: Option<usize>,
+    #[serde(default)]
+    tls: ApiTlsConfigBuilder,
+    #[serde(default)]
+    cors: ApiCorsConfigBuilder,
+}
+
+impl ApiConfigBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn bind_address(mut self, bind_address: SocketAddr) -> Self {
+        self.bind_address.replace(bind_address);
+        self
+    }
+
+    pub fn health_milestone_distance(mut self, health_milestone_distance: u32) -> Self {
+        self.health_milestone_distance.replace(health_milestone_distance);
+        self
+    }
+
+    pub fn public_routes(mut self, public_routes: Vec<String>) -> Self {
+        self.public_routes.replace(public_routes);
+        self
+    }
+
+    pub fn jwt_salt(mut self, jwt_salt: String) -> Self {
+        self.jwt_salt.replace(jwt_salt);
+        self
+    }
+
+    pub fn allowed_ips(mut self, group: RouteGroup, allowed_ips: Vec<IpNetwork>) -> Self {
+        match group {
+            RouteGroup::Data => self.allowed_ips.data.replace(allowed_ips),
+            RouteGroup::Admin => self.allowed_ips.admin.replace(allowed_ips),
+        };
+        self
+    }
+
+    pub fn rate_limit(mut self, group: RouteGroup, rate_limit: RateLimit) -> Self {
+        match group {
+            RouteGroup::Data => self.rate_limits.data.replace(rate_limit),
+            RouteGroup::Admin => self.rate_limits.admin.replace(rate_limit),
+        };
+        self
+    }
+
+    pub fn submit_rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limits.submit.replace(rate_limit);
+        self
+    }
+
+    pub fn pow_concurrency(mut self, pow_concurrency: usize) -> Self {
+        self.pow_concurrency.replace(pow_concurrency);
+        self
+    }
+
+    pub fn tls(mut self, cert_path: PathBuf, key_path: PathBuf) -> Self {
+        self.tls.cert_path.replace(cert_path);
+        self.tls.key_path.replace(key_path);
+        self
+    }
+
+    pub fn cors_allowed_origins(mut self, allowed_origins: Vec<String>) -> Self {
+        self.cors.allowed_origins.replace(allowed_origins);
+        self
+    }
+
+    pub fn cors_allowed_methods(mut self, allowed_methods: Vec<String>) -> Self {
+        self.cors.allowed_methods.replace(allowed_methods);
+        self
+    }
+
+    pub fn cors_allowed_headers(mut self, allowed_headers: Vec<String>) -> Self {
+        self.cors.allowed_headers.replace(allowed_headers);
+        self
+    }
+
+    pub fn finish(self) -> Result<ApiConfig, Error> {
+        let default_networks = |networks: &[&str]| networks.iter().map(|network| network.parse().unwrap()).collect();
+        let default_strings = |strings: &[&str]| strings.iter().map(ToString::to_string).collect();
+
+        Ok(ApiConfig {
+            bind_address: self
+                .bind_address
+                .unwrap_or_else(|| DEFAULT_BIND_ADDRESS.parse().unwrap()),
+            health_milestone_distance: self
+                .health_milestone_distance
+                .unwrap_or(DEFAULT_HEALTH_MILESTONE_DISTANCE),
+            public_routes: self
+                .public_routes
+                .unwrap_or_else(|| default_strings(&DEFAULT_PUBLIC_ROUTES)),
+            jwt_salt: self.jwt_salt.unwrap_or_else(|| DEFAULT_JWT_SALT.to_string()),
+            allowed_ips_data: self
+                .allowed_ips
+                .data
+                .unwrap_or_else(|| default_networks(&DEFAULT_ALLOWED_IPS_DATA)),
+            allowed_ips_admin: self
+                .allowed_ips
+                .admin
+                .unwrap_or_else(|| default_networks(&DEFAULT_ALLOWED_IPS_ADMIN)),
+            rate_limit_data: self
+                .rate_limits
+                .data
+                .unwrap_or(DEFAULT_RATE_LIMIT_DATA)
+                .validate("data")?,
+            rate_limit_admin: self
+                .rate_limits
+                .admin
+                .unwrap_or(DEFAULT_RATE_LIMIT_ADMIN)
+                .validate("admin")?,
+            rate_limit_submit: self
+                .rate_limits
+                .submit
+                .unwrap_or(DEFAULT_RATE_LIMIT_SUBMIT)
+                .validate("submit")?,
+            pow_concurrency: self.pow_concurrency.unwrap_or(DEFAULT_POW_CONCURRENCY),
+            // Serving plain HTTP when only half of the TLS configuration is given would expose what was meant to be
+            // encrypted.
+            tls: match (self.tls.cert_path, self.tls.key_path) {
+                (Some(cert_path), Some(key_path)) => Some(ApiTlsConfig { cert_path, key_path }),
+                (None, None) => None,
+                _ => return Err(Error::IncompleteTls),
+            },
+            cors_allowed_origins: self.cors.allowed_origins.unwrap_or_default(),
+            cors_allowed_methods: self
+                .cors
+                .allowed_methods
+                .unwrap_or_else(|| default_strings(&DEFAULT_CORS_ALLOWED_METHODS)),
+            cors_allowed_headers: self
+                .cors
+                .allowed_headers
+                .unwrap_or_else(|| default_strings(&DEFAULT_CORS_ALLOWED_HEADERS)),
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct ApiConfig {
+    pub(crate) bind_address: SocketAddr,
+    pub(crate) health_milestone_distance: u32,
+    pub(crate) public_routes: Vec<String>,
+    pub(crate) jwt_salt: String,
+    pub(crate) allowed_ips_data: Vec<IpNetwork>,
+    pub(crate) allowed_ips_admin: Vec<IpNetwork>,
+    pub(crate) rate_limit_data: RateLimit,
+    pub(crate) rate_limit_admin: RateLimit,
+    pub(crate) rate_limit_submit: RateLimit,
+    pub(crate) pow_concurrency: usize,
+    pub(crate) tls: Option<ApiTlsConfig>,
+    pub(crate) cors_allowed_origins: Vec<String>,
+    pub(crate) cors_allowed_methods: Vec<String>,
+    pub(crate) cors_allowed_headers: Vec<String>,
+}
+
+/// Paths of the PEM encoded certificate chain and private key the API serves TLS with.
+#[derive(Clone)]
+pub struct ApiTlsConfig {
+    pub(crate) cert_path: PathBuf,
+    pub(crate) key_path: PathBuf,
+}
+
+impl ApiTlsConfig {
+    pub fn cert_path(&self) -> &Path {
+        &self.cert_path
+    }
+
+    pub fn key_path(&self) -> &Path {
+        &self.key_path
+    }
+}
+
+impl ApiConfig {
+    pub fn build() -> ApiConfigBuilder {
+        ApiConfigBuilder::new()
+    }
+
+    pub fn bind_address(&self) -> &SocketAddr {
+        &self.bind_address
+    }
+
+    pub fn health_milestone_distance(&self) -> u32 {
+        self.health_milestone_distance
+    }
+
+    pub fn public_routes(&self) -> &[String] {
+        &self.public_routes
+    }
+
+    pub fn jwt_salt(&self) -> &str {
+        &self.jwt_salt
+    }
+
+    pub fn allowed_ips(&self, group: RouteGroup) -> &[IpNetwork] {
+        match group {
+            RouteGroup::Data => &self.allowed_ips_data,
+            RouteGroup::Admin => &self.allowed_ips_admin,
+        }
+    }
+
+    pub fn rate_limit(&self, group: RouteGroup) -> &RateLimit {
+        match group {
+            RouteGroup::Data => &self.rate_limit_data,
+            RouteGroup::Admin => &self.rate_limit_admin,
+        }
+    }
+
+    pub fn submit_rate_limit(&self) -> &RateLimit {
+        &self.rate_limit_submit
+    }
+
+    pub fn pow_concurrency(&self) -> usize {
+        self.pow_concurrency
+    }
+
+    pub fn tls(&self) -> Option<&ApiTlsConfig> {
+        self.tls.as_ref()
+    }
+
+    /// Origins allowed to make cross-origin requests, `*` allowing any; CORS is disabled when empty.
+    pub fn cors_allowed_origins(&self) -> &[String] {
+        &self.cors_allowed_origins
+    }
+
+    pub fn cors_allowed_methods(&self) -> &[String] {
+        &self.cors_allowed_methods
+    }
+
+    pub fn cors_allowed_headers(&self) -> &[String] {
+        &self.cors_allowed_headers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn invalid_rate_limits() {
+        for rate_limit in &[
+            RateLimit::new(-1.0, 10),
+            RateLimit::new(0.0, 10),
+            RateLimit::new(f64::NAN, 10),
+            RateLimit::new(1.0, 0),
+        ] {
+            assert!(matches!(
+                ApiConfigBuilder::new()
+                    .rate_limit(RouteGroup::Data, *rate_limit)
+                    .finish(),
+                Err(Error::InvalidRateLimit("data"))
+            ));
+        }
+
+        assert!(ApiConfigBuilder::new()
+            .submit_rate_limit(RateLimit::new(0.5, 1))
+            .finish()
+            .is_ok());
+    }
+
+    #[test]
+    fn incomplete_tls() {
+        let mut builder = ApiConfigBuilder::new();
+        builder.tls.cert_path.replace(PathBuf::from("cert.pem"));
+        assert!(matches!(builder.finish(), Err(Error::IncompleteTls)));
+
+        let mut builder = ApiConfigBuilder::new();
+        builder.tls.key_path.replace(PathBuf::from("key.pem"));
+        assert!(matches!(builder.finish(), Err(Error::IncompleteTls)));
+
+        assert!(ApiConfigBuilder::new()
+            .tls(PathBuf::from("cert.pem"), PathBuf::from("key.pem"))
+            .finish()
+            .unwrap()
+            .tls()
+            .is_some());
+        assert!(ApiConfigBuilder::new().finish().unwrap().tls().is_none());
+    }
+}
@@ -0,0 +1,121 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+//! Cross-origin resource sharing, letting browser applications served from other origins call the API.
+
+use crate::config::ApiConfig;
+
+use log::warn;
+use warp::{
+    cors::Cors,
+    http::{header::HeaderName, Method, Uri},
+};
+
+/// Whether `origin` is of the `scheme://host[:port]` form browsers send, anything else would never match.
+fn is_valid_origin(origin: &str) -> bool {
+    match origin.parse::<Uri>() {
+        Ok(uri) => {
+            uri.scheme().is_some()
+                && uri.authority().is_some()
+                && uri
+                    .path_and_query()
+                    .map_or(true, |path| path.as_str().is_empty() || path.as_str() == "/")
+        }
+        Err(_) => false,
+    }
+}
+
+/// Builds the CORS policy of the configuration, skipping and reporting invalid entries, or `None` if no origin is
+/// allowed.
+pub(crate) fn cors(config: &ApiConfig) -> Option<Cors> {
+    let origins = config.cors_allowed_origins();
+
+    if origins.is_empty() {
+        return None;
+    }
+
+    let mut cors = warp::cors();
+
+    if origins.iter().any(|origin| origin == "*") {
+        cors = cors.allow_any_origin();
+    } else {
+        for origin in origins {
+            if is_valid_origin(origin) {
+                cors = cors.allow_origin(origin.trim_end_matches('/'));
+            } else {
+                warn!("Ignoring invalid CORS origin {}.", origin);
+            }
+        }
+    }
+
+    for method in config.cors_allowed_methods() {
+        match Method::from_bytes(method.as_bytes()) {
+            Ok(method) => cors = cors.allow_method(method),
+            Err(_) => warn!("Ignoring invalid CORS method {}.", method),
+        }
+    }
+
+    for header in config.cors_allowed_headers() {
+        match HeaderName::from_bytes(header.as_bytes()) {
+            Ok(header) => cors = cors.allow_header(header),
+            Err(_) => warn!("Ignoring invalid CORS header {}.", header),
+        }
+    }
+
+    Some(cors.build())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use crate::config::ApiConfigBuilder;
+
+    use warp::{http::StatusCode, Filter};
+
+    #[test]
+    fn valid_origins() {
+        assert!(is_valid_origin("https://example.com"));
+        assert!(is_valid_origin("http://localhost:8080/"));
+        assert!(!is_valid_origin("example.com"));
+        assert!(!is_valid_origin("https://example.com/path"));
+    }
+
+    #[tokio::test]
+    async fn preflight() {
+        let config = ApiConfigBuilder::new()
+            .cors_allowed_origins(vec!["https://example.com/".to_string(), "example.com".to_string()])
+            .finish()
+            .unwrap();
+        let routes = warp::any().map(warp::reply).with(cors(&config).unwrap());
+        let preflight = |origin: &str| {
+            warp::test::request()
+                .method("OPTIONS")
+                .path("/api/v1/info")
+                .header("origin", origin)
+                .header("access-control-request-method", "POST")
+        };
+
+        let response = preflight("https://example.com").reply(&routes).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["access-control-allow-origin"], "https://example.com");
+
+        let response = preflight("https://attacker.com").reply(&routes).await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert!(!response.headers().contains_key("access-control-allow-origin"));
+    }
+
+    #[test]
+    fn disabled_without_origins() {
+        assert!(cors(&ApiConfigBuilder::new().finish().unwrap()).is_none());
+    }
+}
@@ -0,0 +1,258 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use dashmap::DashMap;
+use log::{trace, warn};
+use serde::Serialize;
+
+use std::{
+    collections::HashSet,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+};
+
+/// Number of events that can be queued for a single subscriber before new ones are dropped.
+const SUBSCRIBER_QUEUE_SIZE: usize = 1000;
+
+#[derive(Serialize)]
+struct Publication<'a, T: Serialize> {
+    topic: &'a str,
+    payload: &'a T,
+}
+
+struct Subscriber {
+    filters: HashSet<String>,
+    tx: flume::Sender<String>,
+}
+
+/// Dispatches published events to the subscribers whose topic filters match the event topic.
+///
+/// Topics are `/` separated levels. Filters follow MQTT semantics: `+` matches exactly one level and a trailing `#`
+/// matches any number of remaining levels.
+pub(crate) struct Broker {
+    subscribers: DashMap<u64, Subscriber>,
+    next_id: AtomicU64,
+    // Nothing is ever sent, dropping the sender disconnects the receivers held by the sessions.
+    shutdown_tx: Mutex<Option<flume::Sender<()>>>,
+    shutdown_rx: flume::Receiver<()>,
+}
+
+impl Default for Broker {
+    fn default() -> Self {
+        let (shutdown_tx, shutdown_rx) = flume::bounded(0);
+
+        Self {
+            subscribers: DashMap::new(),
+            next_id: AtomicU64::new(0),
+            shutdown_tx: Mutex::new(Some(shutdown_tx)),
+            shutdown_rx,
+        }
+    }
+}
+
+impl Broker {
+    /// Registers a new subscriber without any filter and returns its id and the receiving end of its event queue.
+    pub(crate) fn connect(&self) -> (u64, flume::Receiver<String>) {
+        let (tx, rx) = flume::bounded(SUBSCRIBER_QUEUE_SIZE);
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+
+        self.subscribers.insert(
+            id,
+            Subscriber {
+                filters: HashSet::new(),
+                tx,
+            },
+        );
+
+        (id, rx)
+    }
+
+    /// Returns a receiver that disconnects once the broker is shut down, for sessions to end with the API.
+    pub(crate) fn shutdown_signal(&self) -> flume::Receiver<()> {
+        self.shutdown_rx.clone()
+    }
+
+    pub(crate) fn shutdown(&self) {
+        self.shutdown_tx.lock().unwrap().take();
+    }
+
+    pub(crate) fn disconnect(&self, id: u64) {
+        self.subscribers.remove(&id);
+    }
+
+    pub(crate) fn subscribe(&self, id: u64, filters: Vec<String>) {
+        if let Some(mut subscriber) = self.subscribers.get_mut(&id) {
+            subscriber.filters.extend(filters);
+        }
+    }
+
+    pub(crate) fn unsubscribe(&self, id: u64, filters: Vec<String>) {
+        if let Some(mut subscriber) = self.subscribers.get_mut(&id) {
+            for filter in filters {
+                subscriber.filters.remove(&filter);
+            }
+        }
+    }
+
+    /// Returns whether at least one subscriber is interested in a topic, allowing to skip building its payload.
+    pub(crate) fn has_subscribers(&self, topic: &str) -> bool {
+        self.subscribers
+            .iter()
+            .any(|subscriber| subscriber.filters.iter().any(|filter| matches(filter, topic)))
+    }
+
+    /// Publishes a payload to all subscribers of a topic. Slow subscribers miss events rather than blocking the node.
+    pub(crate) fn publish<T: Serialize>(&self, topic: &str, payload: &T) {
+        let mut publication = None;
+
+        for subscriber in self.subscribers.iter() {
+            if !subscriber.filters.iter().any(|filter| matches(filter, topic)) {
+                continue;
+            }
+
+            if publication.is_none() {
+                match serde_json::to_string(&Publication { topic, payload }) {
+                    Ok(serialized) => publication = Some(serialized),
+                    Err(e) => {
+                        warn!("Serializing event of topic {} failed: {}.", topic, e);
+                        return;
+                    }
+                }
+            }
+
+            if let Some(publication) = &publication {
+                if subscriber.tx.try_send(publication.clone()).is_err() {
+                    trace!("Dropped event of topic {} for subscriber {}.", topic, subscriber.key());
+                }
+            }
+        }
+    }
+}
+
+/// Checks that a topic filter only uses wildcards as whole levels and `#` as the last level.
+pub(crate) fn is_valid_filter(filter: &str) -> bool {
+    let levels = filter.split('/').collect::<Vec<&str>>();
+
+    !filter.is_empty()
+        && levels.iter().enumerate().all(|(i, level)| match *level {
+            "#" => i == levels.len() - 1,
+            "+" => true,
+            level => !level.contains('#') && !level.contains('+'),
+        })
+}
+
+fn matches(filter: &str, topic: &str) -> bool {
+    let mut filter_levels = filter.split('/');
+    let mut topic_levels = topic.split('/');
+
+    loop {
+        match (filter_levels.next(), topic_levels.next()) {
+            (Some("#"), _) => return true,
+            (Some("+"), Some(_)) => continue,
+            (Some(filter_level), Some(topic_level)) if filter_level == topic_level => continue,
+            (None, None) => return true,
+            _ => return false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use crate::events::listeners::{topic_address_outputs, topic_indexation};
+
+    #[test]
+    fn valid_filters() {
+        assert!(is_valid_filter("milestones/latest"));
+        assert!(is_valid_filter("messages/indexation/+"));
+        assert!(is_valid_filter("addresses/+/outputs"));
+        assert!(is_valid_filter("#"));
+        assert!(is_valid_filter("messages/#"));
+
+        assert!(!is_valid_filter(""));
+        assert!(!is_valid_filter("messages/#/referenced"));
+        assert!(!is_valid_filter("messages/index+"));
+        assert!(!is_valid_filter("messages/#index"));
+    }
+
+    #[test]
+    fn exact_match() {
+        assert!(matches("milestones/latest", "milestones/latest"));
+        assert!(!matches("milestones/latest", "milestones/solid"));
+        assert!(!matches("milestones", "milestones/latest"));
+        assert!(!matches("milestones/latest/extra", "milestones/latest"));
+    }
+
+    #[test]
+    fn single_level_wildcard() {
+        assert!(matches("milestones/+", "milestones/latest"));
+        assert!(matches("+/latest", "milestones/latest"));
+        assert!(!matches("milestones/+", "milestones"));
+        assert!(!matches("milestones/+", "milestones/latest/extra"));
+    }
+
+    #[test]
+    fn multi_level_wildcard() {
+        assert!(matches("#", "milestones/latest"));
+        assert!(matches("messages/#", "messages/indexation/696f7461"));
+        assert!(matches("messages/#", "messages"));
+        assert!(!matches("messages/#", "milestones/latest"));
+    }
+
+    #[test]
+    fn indexation_topic() {
+        let topic = topic_indexation("iota/bee");
+
+        assert_eq!(topic, "messages/indexation/696f74612f626565");
+        assert!(matches("messages/indexation/+", &topic));
+        assert!(matches("messages/indexation/696f74612f626565", &topic));
+        assert!(!matches("messages/indexation/696f7461", &topic));
+    }
+
+    #[test]
+    fn address_outputs_topic() {
+        let topic = topic_address_outputs("iot1qqp6xzp4");
+
+        assert_eq!(topic, "addresses/iot1qqp6xzp4/outputs");
+        assert!(matches("addresses/+/outputs", &topic));
+        assert!(matches("addresses/iot1qqp6xzp4/#", &topic));
+        assert!(!matches("addresses/iot1other/outputs", &topic));
+    }
+
+    #[test]
+    fn publish_to_matching_subscribers() {
+        let broker = Broker::default();
+        let (id, rx) = broker.connect();
+
+        broker.subscribe(id, vec!["milestones/+".to_string()]);
+        assert!(broker.has_subscribers("milestones/latest"));
+        assert!(!broker.has_subscribers("messages"));
+
+        broker.publish("milestones/latest", &1);
+        broker.publish("messages", &2);
+        assert_eq!(rx.try_recv().unwrap(), r#"{"topic":"milestones/latest","payload":1}"#);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn shutdown_disconnects_sessions() {
+        let broker = Broker::default();
+        let signal = broker.shutdown_signal();
+
+        assert!(matches!(signal.try_recv(), Err(flume::TryRecvError::Empty)));
+        broker.shutdown();
+        assert!(matches!(signal.try_recv(), Err(flume::TryRecvError::Disconnected)));
+    }
+}
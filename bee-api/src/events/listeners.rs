@@ -0,0 +1,203 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::{
+    events::broker::Broker,
+    types::{MessageDto, OutputDto},
+    worker::ApiWorker,
+};
+
+use bee_common_ext::{event::Bus, node::Node};
+use bee_ledger::event::{MessageReferenced, MilestoneConfirmed, OutputCreated};
+use bee_message::{
+    payload::{transaction::Output, Payload},
+    Message, MessageId,
+};
+use bee_protocol::{
+    event::{LatestMilestoneChanged, LatestSolidMilestoneChanged, MessageProcessed},
+    Milestone,
+};
+
+use log::warn;
+use serde::Serialize;
+
+use std::{convert::TryFrom, sync::Arc};
+
+pub(crate) const TOPIC_MILESTONES_LATEST: &str = "milestones/latest";
+pub(crate) const TOPIC_MILESTONES_SOLID: &str = "milestones/solid";
+pub(crate) const TOPIC_MILESTONES_CONFIRMED: &str = "milestones/confirmed";
+pub(crate) const TOPIC_MESSAGES: &str = "messages";
+pub(crate) const TOPIC_MESSAGES_REFERENCED: &str = "messages/referenced";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MilestoneEvent {
+    index: u32,
+    message_id: String,
+}
+
+impl From<&Milestone> for MilestoneEvent {
+    fn from(milestone: &Milestone) -> Self {
+        MilestoneEvent {
+            index: *milestone.index(),
+            message_id: milestone.message_id().to_string(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MilestoneConfirmedEvent {
+    index: u32,
+    timestamp: u64,
+    messages_referenced: usize,
+    messages_excluded_no_transaction: usize,
+    messages_excluded_conflicting: usize,
+    messages_included: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MessageEvent {
+    message_id: String,
+    message: MessageDto,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MessageReferencedEvent {
+    message_id: String,
+    referenced_by_milestone_index: u32,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OutputEvent {
+    message_id: String,
+    transaction_id: String,
+    output_index: u16,
+    output: OutputDto,
+}
+
+/// Topic of the messages carrying an indexation payload with a given index, hex encoded to keep topic levels intact.
+pub(crate) fn topic_indexation(index: &str) -> String {
+    format!("messages/indexation/{}", hex::encode(index))
+}
+
+/// Topic of the outputs created on a given bech32 encoded address.
+pub(crate) fn topic_address_outputs(address: &str) -> String {
+    format!("addresses/{}/outputs", address)
+}
+
+fn publish_message(broker: &Broker, message_id: &MessageId, message: &Message) {
+    let indexation_topic = match message.payload() {
+        Some(Payload::Indexation(indexation)) => Some(topic_indexation(indexation.index())),
+        _ => None,
+    };
+
+    if !broker.has_subscribers(TOPIC_MESSAGES)
+        && !indexation_topic
+            .as_ref()
+            .map_or(false, |topic| broker.has_subscribers(topic))
+    {
+        return;
+    }
+
+    let event = match MessageDto::try_from(message) {
+        Ok(dto) => MessageEvent {
+            message_id: message_id.to_string(),
+            message: dto,
+        },
+        Err(e) => {
+            warn!("Converting message {} failed: {}.", message_id, e);
+            return;
+        }
+    };
+
+    broker.publish(TOPIC_MESSAGES, &event);
+
+    if let Some(topic) = indexation_topic {
+        broker.publish(&topic, &event);
+    }
+}
+
+fn publish_output(broker: &Broker, created: &OutputCreated) {
+    let address = match created.output.inner() {
+        Output::SignatureLockedSingle(output) => output.address().to_bech32(),
+        _ => return,
+    };
+    let topic = topic_address_outputs(&address);
+
+    if !broker.has_subscribers(&topic) {
+        return;
+    }
+
+    match OutputDto::try_from(created.output.inner()) {
+        Ok(dto) => broker.publish(
+            &topic,
+            &OutputEvent {
+                message_id: created.output.message_id().to_string(),
+                transaction_id: created.output_id.transaction_id().to_string(),
+                output_index: created.output_id.index(),
+                output: dto,
+            },
+        ),
+        Err(e) => warn!("Converting output {} failed: {}.", created.output_id, e),
+    }
+}
+
+/// Forwards the node events of interest from the bus to the broker topics.
+pub(crate) fn register<N: Node>(node: &N, broker: Arc<Broker>) {
+    let bus = node.resource::<Bus>();
+
+    let b = broker.clone();
+    bus.add_listener::<ApiWorker, _, _>(move |latest_milestone: &LatestMilestoneChanged| {
+        b.publish(TOPIC_MILESTONES_LATEST, &MilestoneEvent::from(&latest_milestone.0))
+    });
+
+    let b = broker.clone();
+    bus.add_listener::<ApiWorker, _, _>(move |latest_solid_milestone: &LatestSolidMilestoneChanged| {
+        b.publish(TOPIC_MILESTONES_SOLID, &MilestoneEvent::from(&latest_solid_milestone.0))
+    });
+
+    let b = broker.clone();
+    bus.add_listener::<ApiWorker, _, _>(move |confirmed: &MilestoneConfirmed| {
+        b.publish(
+            TOPIC_MILESTONES_CONFIRMED,
+            &MilestoneConfirmedEvent {
+                index: *confirmed.index,
+                timestamp: confirmed.timestamp,
+                messages_referenced: confirmed.messages_referenced,
+                messages_excluded_no_transaction: confirmed.messages_excluded_no_transaction,
+                messages_excluded_conflicting: confirmed.messages_excluded_conflicting,
+                messages_included: confirmed.messages_included,
+            },
+        )
+    });
+
+    let b = broker.clone();
+    bus.add_listener::<ApiWorker, _, _>(move |processed: &MessageProcessed| {
+        publish_message(&b, &processed.0, &processed.1)
+    });
+
+    let b = broker.clone();
+    bus.add_listener::<ApiWorker, _, _>(move |referenced: &MessageReferenced| {
+        b.publish(
+            TOPIC_MESSAGES_REFERENCED,
+            &MessageReferencedEvent {
+                message_id: referenced.message_id.to_string(),
+                referenced_by_milestone_index: *referenced.index,
+            },
+        )
+    });
+
+    bus.add_listener::<ApiWorker, _, _>(move |created: &OutputCreated| publish_output(&broker, created));
+}
@@ -0,0 +1,530 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::{
+    auth::{self, Auth},
+    config::ApiConfig,
+    events::Broker,
+    handlers::{self, peers::KnownPeers},
+    rate_limit::{self, RateLimiter},
+    rejection::CustomRejection,
+    storage::Backend,
+    tls::RemoteAddress,
+};
+
+use bee_common_ext::node::ResHandle;
+use bee_message::{
+    payload::transaction::{Address, Ed25519Address, OutputId},
+    MessageId,
+};
+use bee_network::{Network, PeerId};
+use bee_peering::ManualPeers;
+use bee_protocol::{
+    config::ProtocolConfig, tangle::MsTangle, MessageSubmitterWorkerEvent, MilestoneIndex, WorkerQueue,
+};
+
+use dashmap::DashMap;
+use tokio::sync::Semaphore;
+use warp::{reject, Filter, Rejection, Reply};
+
+use std::{convert::Infallible, net::SocketAddr, sync::Arc};
+
+/// Maximum accepted size of a submitted message body.
+const MAX_BODY_SIZE: u64 = 32 * 1024;
+
+pub(crate) fn all<B: Backend>(
+    config: ApiConfig,
+    network_id: (String, u64),
+    version: String,
+    protocol_config: ProtocolConfig,
+    tangle: ResHandle<MsTangle<B>>,
+    storage: ResHandle<B>,
+    message_submitter: flume::Sender<MessageSubmitterWorkerEvent>,
+    network: Network,
+    manual_peers: ManualPeers,
+    broker: Arc<Broker>,
+    worker_queues: Arc<Vec<WorkerQueue>>,
+    auth: Auth,
+    rate_limiter: RateLimiter,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    let known_peers: KnownPeers = Arc::new(DashMap::new());
+    let pow_permits = Arc::new(Semaphore::new(config.pow_concurrency));
+
+    rate_limit::guard(rate_limiter).and(auth::guard(auth)).and(
+        info(config.clone(), network_id.clone(), version, tangle.clone())
+            .or(health(config, tangle.clone()))
+            .or(metrics(tangle.clone(), storage.clone(), worker_queues))
+            .or(submit_message(
+                network_id,
+                protocol_config,
+                tangle.clone(),
+                message_submitter,
+                pow_permits,
+            ))
+            .or(tips(tangle.clone()))
+            .or(messages_find(storage.clone()))
+            .or(message(tangle.clone(), storage.clone()))
+            .or(message_raw(tangle.clone(), storage.clone()))
+            .or(message_metadata(tangle.clone(), storage.clone()))
+            .or(message_children(tangle.clone(), storage.clone()))
+            .or(milestone(tangle.clone(), storage.clone()))
+            .or(milestone_utxo_changes(storage.clone()))
+            .or(output(storage.clone()))
+            .or(outputs_ed25519(storage.clone()))
+            .or(balance_bech32(storage.clone()))
+            .or(search(tangle, storage))
+            .or(peers(known_peers.clone(), manual_peers.clone()))
+            .or(peer(known_peers.clone(), manual_peers.clone()))
+            .or(add_peer(network.clone(), known_peers.clone(), manual_peers))
+            .or(remove_peer(network.clone(), known_peers))
+            .or(ban_peer(network.clone()))
+            .or(unban_peer(network.clone()))
+            .or(ban_address(network.clone()))
+            .or(unban_address(network))
+            .or(events(broker)),
+    )
+}
+
+fn info<B: Backend>(
+    config: ApiConfig,
+    network_id: (String, u64),
+    version: String,
+    tangle: ResHandle<MsTangle<B>>,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(warp::path!("api" / "v1" / "info"))
+        .and(with_config(config))
+        .and(with_network_id(network_id))
+        .and(with_version(version))
+        .and(with_tangle(tangle))
+        .and_then(handlers::info::info)
+}
+
+fn health<B: Backend>(
+    config: ApiConfig,
+    tangle: ResHandle<MsTangle<B>>,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(warp::path!("health"))
+        .and(with_config(config))
+        .and(with_tangle(tangle))
+        .and_then(handlers::health::health)
+}
+
+fn metrics<B: Backend>(
+    tangle: ResHandle<MsTangle<B>>,
+    storage: ResHandle<B>,
+    worker_queues: Arc<Vec<WorkerQueue>>,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(warp::path!("metrics"))
+        .and(with_tangle(tangle))
+        .and(with_storage(storage))
+        .and(with_worker_queues(worker_queues))
+        .and_then(handlers::metrics::metrics)
+}
+
+fn message<B: Backend>(
+    tangle: ResHandle<MsTangle<B>>,
+    storage: ResHandle<B>,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(api_v1("messages"))
+        .and(message_id())
+        .and(warp::path::end())
+        .and(with_tangle(tangle))
+        .and(with_storage(storage))
+        .and_then(handlers::message::message)
+}
+
+fn submit_message<B: Backend>(
+    network_id: (String, u64),
+    protocol_config: ProtocolConfig,
+    tangle: ResHandle<MsTangle<B>>,
+    message_submitter: flume::Sender<MessageSubmitterWorkerEvent>,
+    pow_permits: Arc<Semaphore>,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::post()
+        .and(warp::path!("api" / "v1" / "messages"))
+        .and(warp::header::optional::<String>("content-type"))
+        .and(warp::body::content_length_limit(MAX_BODY_SIZE))
+        .and(warp::body::bytes())
+        .and(with_network_id(network_id))
+        .and(with_protocol_config(protocol_config))
+        .and(with_tangle(tangle))
+        .and(with_message_submitter(message_submitter))
+        .and(with_pow_permits(pow_permits))
+        .and_then(handlers::submit_message::submit_message)
+}
+
+fn tips<B: Backend>(tangle: ResHandle<MsTangle<B>>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(warp::path!("api" / "v1" / "tips"))
+        .and(with_tangle(tangle))
+        .and_then(handlers::tips::tips)
+}
+
+fn messages_find<B: Backend>(storage: ResHandle<B>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(warp::path!("api" / "v1" / "messages"))
+        .and(warp::query::<handlers::messages_find::MessagesQuery>())
+        .and(with_storage(storage))
+        .and_then(handlers::messages_find::messages_find)
+}
+
+fn message_raw<B: Backend>(
+    tangle: ResHandle<MsTangle<B>>,
+    storage: ResHandle<B>,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(api_v1("messages"))
+        .and(message_id())
+        .and(warp::path("raw"))
+        .and(warp::path::end())
+        .and(with_tangle(tangle))
+        .and(with_storage(storage))
+        .and_then(handlers::message_raw::message_raw)
+}
+
+fn message_metadata<B: Backend>(
+    tangle: ResHandle<MsTangle<B>>,
+    storage: ResHandle<B>,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(api_v1("messages"))
+        .and(message_id())
+        .and(warp::path("metadata"))
+        .and(warp::path::end())
+        .and(with_tangle(tangle))
+        .and(with_storage(storage))
+        .and_then(handlers::message_metadata::message_metadata)
+}
+
+fn message_children<B: Backend>(
+    tangle: ResHandle<MsTangle<B>>,
+    storage: ResHandle<B>,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(api_v1("messages"))
+        .and(message_id())
+        .and(warp::path("children"))
+        .and(warp::path::end())
+        .and(with_tangle(tangle))
+        .and(with_storage(storage))
+        .and_then(handlers::message_children::message_children)
+}
+
+fn milestone<B: Backend>(
+    tangle: ResHandle<MsTangle<B>>,
+    storage: ResHandle<B>,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(api_v1("milestones"))
+        .and(milestone_index())
+        .and(warp::path::end())
+        .and(with_tangle(tangle))
+        .and(with_storage(storage))
+        .and_then(handlers::milestone::milestone)
+}
+
+fn milestone_utxo_changes<B: Backend>(
+    storage: ResHandle<B>,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(api_v1("milestones"))
+        .and(milestone_index())
+        .and(warp::path("utxo-changes"))
+        .and(warp::path::end())
+        .and(with_storage(storage))
+        .and_then(handlers::milestone_utxo_changes::milestone_utxo_changes)
+}
+
+fn output<B: Backend>(storage: ResHandle<B>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(api_v1("outputs"))
+        .and(output_id())
+        .and(warp::path::end())
+        .and(with_storage(storage))
+        .and_then(handlers::output::output)
+}
+
+fn outputs_ed25519<B: Backend>(storage: ResHandle<B>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(api_v1("addresses"))
+        .and(warp::path("ed25519"))
+        .and(ed25519_address())
+        .and(warp::path("outputs"))
+        .and(warp::path::end())
+        .and(warp::query::<handlers::outputs_ed25519::OutputsQuery>())
+        .and(with_storage(storage))
+        .and_then(handlers::outputs_ed25519::outputs_ed25519)
+}
+
+fn balance_bech32<B: Backend>(storage: ResHandle<B>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(api_v1("addresses"))
+        .and(bech32_address())
+        .and(warp::path::end())
+        .and(with_storage(storage))
+        .and_then(handlers::balance_bech32::balance_bech32)
+}
+
+fn search<B: Backend>(
+    tangle: ResHandle<MsTangle<B>>,
+    storage: ResHandle<B>,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(api_v1("search"))
+        .and(warp::path::param::<String>())
+        .and(warp::path::end())
+        .and(with_tangle(tangle))
+        .and(with_storage(storage))
+        .and_then(handlers::search::search)
+}
+
+fn peers(
+    known_peers: KnownPeers,
+    manual_peers: ManualPeers,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(warp::path!("api" / "v1" / "peers"))
+        .and(with_known_peers(known_peers))
+        .and(with_manual_peers(manual_peers))
+        .and_then(handlers::peers::peers)
+}
+
+fn peer(
+    known_peers: KnownPeers,
+    manual_peers: ManualPeers,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(api_v1("peers"))
+        .and(peer_id())
+        .and(warp::path::end())
+        .and(with_known_peers(known_peers))
+        .and(with_manual_peers(manual_peers))
+        .and_then(handlers::peers::peer)
+}
+
+fn add_peer(
+    network: Network,
+    known_peers: KnownPeers,
+    manual_peers: ManualPeers,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::post()
+        .and(warp::path!("api" / "v1" / "peers"))
+        .and(warp::body::content_length_limit(MAX_BODY_SIZE))
+        .and(warp::body::json())
+        .and(with_network(network))
+        .and(with_known_peers(known_peers))
+        .and(with_manual_peers(manual_peers))
+        .and_then(handlers::peers::add_peer)
+}
+
+fn remove_peer(
+    network: Network,
+    known_peers: KnownPeers,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::delete()
+        .and(api_v1("peers"))
+        .and(peer_id())
+        .and(warp::path::end())
+        .and(with_network(network))
+        .and(with_known_peers(known_peers))
+        .and_then(handlers::peers::remove_peer)
+}
+
+fn ban_peer(network: Network) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::post()
+        .and(api_v1("peers"))
+        .and(peer_id())
+        .and(warp::path("ban"))
+        .and(warp::path::end())
+        .and(with_network(network))
+        .and_then(handlers::peers::ban_peer)
+}
+
+fn unban_peer(network: Network) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::delete()
+        .and(api_v1("peers"))
+        .and(peer_id())
+        .and(warp::path("ban"))
+        .and(warp::path::end())
+        .and(with_network(network))
+        .and_then(handlers::peers::unban_peer)
+}
+
+fn ban_address(network: Network) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::post()
+        .and(warp::path!("api" / "v1" / "peers" / "ban-address"))
+        .and(warp::body::content_length_limit(MAX_BODY_SIZE))
+        .and(warp::body::json())
+        .and(with_network(network))
+        .and_then(handlers::peers::ban_address)
+}
+
+fn unban_address(network: Network) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::post()
+        .and(warp::path!("api" / "v1" / "peers" / "unban-address"))
+        .and(warp::body::content_length_limit(MAX_BODY_SIZE))
+        .and(warp::body::json())
+        .and(with_network(network))
+        .and_then(handlers::peers::unban_address)
+}
+
+fn events(broker: Arc<Broker>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(warp::path!("api" / "v1" / "events"))
+        .and(warp::ws())
+        .and(with_broker(broker))
+        .and_then(handlers::events::events)
+}
+
+/// Extracts the remote address of the client, provided by warp for plain connections and by the TLS server otherwise.
+pub(crate) fn remote() -> impl Filter<Extract = (Option<SocketAddr>,), Error = Infallible> + Clone {
+    warp::addr::remote().and(warp::ext::optional::<RemoteAddress>()).map(
+        |remote: Option<SocketAddr>, tls_remote: Option<RemoteAddress>| {
+            remote.or_else(|| tls_remote.map(|tls_remote| tls_remote.0))
+        },
+    )
+}
+
+/// Matches the `/api/v1/<segment>` path prefix.
+fn api_v1(segment: &'static str) -> impl Filter<Extract = (), Error = Rejection> + Copy {
+    warp::path("api").and(warp::path("v1")).and(warp::path(segment))
+}
+
+/// Extracts a hex encoded `MessageId` path parameter and rejects malformed ones with a bad request.
+fn message_id() -> impl Filter<Extract = (MessageId,), Error = Rejection> + Copy {
+    warp::path::param().and_then(|value: String| async move {
+        value
+            .parse::<MessageId>()
+            .map_err(|_| reject::custom(CustomRejection::BadRequest(format!("Invalid message id {}.", value))))
+    })
+}
+
+/// Extracts a `MilestoneIndex` path parameter and rejects malformed ones with a bad request.
+fn milestone_index() -> impl Filter<Extract = (MilestoneIndex,), Error = Rejection> + Copy {
+    warp::path::param().and_then(|value: String| async move {
+        value.parse::<u32>().map(MilestoneIndex).map_err(|_| {
+            reject::custom(CustomRejection::BadRequest(format!(
+                "Invalid milestone index {}.",
+                value
+            )))
+        })
+    })
+}
+
+/// Extracts a hex encoded `OutputId` path parameter and rejects malformed ones with a bad request.
+fn output_id() -> impl Filter<Extract = (OutputId,), Error = Rejection> + Copy {
+    warp::path::param().and_then(|value: String| async move {
+        value
+            .parse::<OutputId>()
+            .map_err(|_| reject::custom(CustomRejection::BadRequest(format!("Invalid output id {}.", value))))
+    })
+}
+
+/// Extracts a hex encoded `Ed25519Address` path parameter and rejects malformed ones with a bad request.
+fn ed25519_address() -> impl Filter<Extract = (Ed25519Address,), Error = Rejection> + Copy {
+    warp::path::param().and_then(|value: String| async move {
+        value.parse::<Ed25519Address>().map_err(|_| {
+            reject::custom(CustomRejection::BadRequest(format!(
+                "Invalid Ed25519 address {}.",
+                value
+            )))
+        })
+    })
+}
+
+/// Extracts a bech32 encoded `Address` path parameter and rejects malformed ones with a bad request.
+fn bech32_address() -> impl Filter<Extract = (Address,), Error = Rejection> + Copy {
+    warp::path::param().and_then(|value: String| async move {
+        Address::try_from_bech32(&value).map_err(|_| {
+            reject::custom(CustomRejection::BadRequest(format!(
+                "Invalid bech32 address {}.",
+                value
+            )))
+        })
+    })
+}
+
+/// Extracts a base58 encoded `PeerId` path parameter and rejects malformed ones with a bad request.
+fn peer_id() -> impl Filter<Extract = (PeerId,), Error = Rejection> + Copy {
+    warp::path::param().and_then(|value: String| async move {
+        value
+            .parse::<PeerId>()
+            .map_err(|_| reject::custom(CustomRejection::BadRequest(format!("Invalid peer id {}.", value))))
+    })
+}
+
+fn with_config(config: ApiConfig) -> impl Filter<Extract = (ApiConfig,), Error = Infallible> + Clone {
+    warp::any().map(move || config.clone())
+}
+
+fn with_network_id(network_id: (String, u64)) -> impl Filter<Extract = ((String, u64),), Error = Infallible> + Clone {
+    warp::any().map(move || network_id.clone())
+}
+
+fn with_version(version: String) -> impl Filter<Extract = (String,), Error = Infallible> + Clone {
+    warp::any().map(move || version.clone())
+}
+
+fn with_protocol_config(
+    protocol_config: ProtocolConfig,
+) -> impl Filter<Extract = (ProtocolConfig,), Error = Infallible> + Clone {
+    warp::any().map(move || protocol_config.clone())
+}
+
+fn with_tangle<B: Backend>(
+    tangle: ResHandle<MsTangle<B>>,
+) -> impl Filter<Extract = (ResHandle<MsTangle<B>>,), Error = Infallible> + Clone {
+    warp::any().map(move || tangle.clone())
+}
+
+fn with_storage<B: Backend>(
+    storage: ResHandle<B>,
+) -> impl Filter<Extract = (ResHandle<B>,), Error = Infallible> + Clone {
+    warp::any().map(move || storage.clone())
+}
+
+fn with_message_submitter(
+    message_submitter: flume::Sender<MessageSubmitterWorkerEvent>,
+) -> impl Filter<Extract = (flume::Sender<MessageSubmitterWorkerEvent>,), Error = Infallible> + Clone {
+    warp::any().map(move || message_submitter.clone())
+}
+
+fn with_pow_permits(
+    pow_permits: Arc<Semaphore>,
+) -> impl Filter<Extract = (Arc<Semaphore>,), Error = Infallible> + Clone {
+    warp::any().map(move || pow_permits.clone())
+}
+
+fn with_network(network: Network) -> impl Filter<Extract = (Network,), Error = Infallible> + Clone {
+    warp::any().map(move || network.clone())
+}
+
+fn with_known_peers(known_peers: KnownPeers) -> impl Filter<Extract = (KnownPeers,), Error = Infallible> + Clone {
+    warp::any().map(move || known_peers.clone())
+}
+
+fn with_manual_peers(manual_peers: ManualPeers) -> impl Filter<Extract = (ManualPeers,), Error = Infallible> + Clone {
+    warp::any().map(move || manual_peers.clone())
+}
+
+fn with_broker(broker: Arc<Broker>) -> impl Filter<Extract = (Arc<Broker>,), Error = Infallible> + Clone {
+    warp::any().map(move || broker.clone())
+}
+
+fn with_worker_queues(
+    worker_queues: Arc<Vec<WorkerQueue>>,
+) -> impl Filter<Extract = (Arc<Vec<WorkerQueue>>,), Error = Infallible> + Clone {
+    warp::any().map(move || worker_queues.clone())
+}
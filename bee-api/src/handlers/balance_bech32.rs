@@ -0,0 +1,93 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::{
+    handlers::outputs_ed25519::fetch_output_ids, rejection::CustomRejection, storage::Backend, types::DataResponse,
+};
+
+use bee_common_ext::node::ResHandle;
+use bee_ledger::{output::Output, unspent::Unspent};
+use bee_message::payload::transaction::{self, Address, OutputId};
+use bee_storage::access::{Exist, Fetch};
+
+use log::error;
+use serde::Serialize;
+use warp::{reject, Rejection, Reply};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BalanceResponse {
+    pub(crate) address_type: u8,
+    pub(crate) address: String,
+    pub(crate) balance: u64,
+}
+
+async fn unspent_amount<B: Backend>(storage: &B, output_id: &OutputId) -> Result<u64, Rejection> {
+    let internal_error = |e| {
+        error!("Fetching output {} failed: {:?}.", output_id, e);
+        reject::custom(CustomRejection::InternalError)
+    };
+
+    if !Exist::<Unspent, ()>::exist(storage, &Unspent::new(*output_id))
+        .await
+        .map_err(internal_error)?
+    {
+        return Ok(0);
+    }
+
+    match Fetch::<OutputId, Output>::fetch(storage, output_id)
+        .await
+        .map_err(internal_error)?
+    {
+        Some(output) => match output.inner() {
+            transaction::Output::SignatureLockedSingle(output) => Ok(output.amount().get()),
+            _ => Ok(0),
+        },
+        None => Ok(0),
+    }
+}
+
+pub(crate) async fn balance_bech32<B: Backend>(
+    address: Address,
+    storage: ResHandle<B>,
+) -> Result<impl Reply, Rejection> {
+    let ed25519_address = match &address {
+        Address::Ed25519(address) => address,
+        _ => {
+            return Err(reject::custom(CustomRejection::BadRequest(
+                "Only Ed25519 addresses are supported.".to_string(),
+            )))
+        }
+    };
+
+    let mut balance = 0u64;
+    let mut cursor = None;
+
+    // Walks through all the pages so that the balance isn't capped by the storage fetch limit.
+    loop {
+        let (output_ids, next_cursor) = fetch_output_ids(&*storage, ed25519_address, cursor).await?;
+
+        for output_id in output_ids.iter() {
+            balance = balance.saturating_add(unspent_amount(&*storage, output_id).await?);
+        }
+
+        match next_cursor {
+            Some(next_cursor) => cursor = Some(next_cursor),
+            None => break,
+        }
+    }
+
+    Ok(warp::reply::json(&DataResponse::new(BalanceResponse {
+        address_type: 1,
+        address: address.to_bech32(),
+        balance,
+    })))
+}
@@ -0,0 +1,109 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::events::{is_valid_filter, Broker};
+
+use futures::{select, SinkExt, StreamExt};
+use log::{debug, trace};
+use serde::{Deserialize, Serialize};
+use warp::{
+    ws::{Message, WebSocket, Ws},
+    Rejection, Reply,
+};
+
+use std::sync::Arc;
+
+/// Requests a client can send, e.g. `{"type":"subscribe","topics":["milestones/latest","messages/indexation/+"]}`.
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum EventsRequest {
+    Subscribe { topics: Vec<String> },
+    Unsubscribe { topics: Vec<String> },
+}
+
+#[derive(Serialize)]
+struct EventsError {
+    error: String,
+}
+
+pub(crate) async fn events(ws: Ws, broker: Arc<Broker>) -> Result<impl Reply, Rejection> {
+    Ok(ws.on_upgrade(move |socket| session(socket, broker)))
+}
+
+async fn session(socket: WebSocket, broker: Arc<Broker>) {
+    let (id, rx) = broker.connect();
+    let (mut sink, stream) = socket.split();
+    let mut requests = stream.fuse();
+    let mut events = rx.into_stream().fuse();
+    let mut shutdown = broker.shutdown_signal().into_stream().fuse();
+
+    debug!("Events subscriber {} connected.", id);
+
+    loop {
+        select! {
+            _ = shutdown.next() => {
+                let _ = sink.send(Message::close()).await;
+                break;
+            }
+            request = requests.next() => {
+                let request = match request {
+                    Some(Ok(request)) if request.is_text() => request,
+                    Some(Ok(request)) if !request.is_close() => continue,
+                    _ => break,
+                };
+
+                if let Err(error) = handle_request(&broker, id, request) {
+                    let error = serde_json::to_string(&EventsError { error }).unwrap_or_default();
+
+                    if sink.send(Message::text(error)).await.is_err() {
+                        break;
+                    }
+                }
+            }
+            event = events.next() => match event {
+                Some(event) => {
+                    if sink.send(Message::text(event)).await.is_err() {
+                        break;
+                    }
+                }
+                None => break,
+            },
+        }
+    }
+
+    broker.disconnect(id);
+
+    debug!("Events subscriber {} disconnected.", id);
+}
+
+fn handle_request(broker: &Broker, id: u64, request: Message) -> Result<(), String> {
+    let request = request
+        .to_str()
+        .ok()
+        .and_then(|request| serde_json::from_str::<EventsRequest>(request).ok())
+        .ok_or_else(|| "Invalid request.".to_string())?;
+
+    match request {
+        EventsRequest::Subscribe { topics } => {
+            if let Some(topic) = topics.iter().find(|topic| !is_valid_filter(topic)) {
+                return Err(format!("Invalid topic {}.", topic));
+            }
+            trace!("Events subscriber {} subscribed to {:?}.", id, topics);
+            broker.subscribe(id, topics);
+        }
+        EventsRequest::Unsubscribe { topics } => {
+            trace!("Events subscriber {} unsubscribed from {:?}.", id, topics);
+            broker.unsubscribe(id, topics);
+        }
+    }
+
+    Ok(())
+}
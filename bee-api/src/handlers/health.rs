@@ -0,0 +1,65 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::{config::ApiConfig, storage::Backend};
+
+use bee_common_ext::node::ResHandle;
+use bee_protocol::tangle::MsTangle;
+
+use warp::{http::StatusCode, Reply};
+
+use std::convert::Infallible;
+
+/// A node is healthy when its latest solid milestone is within the configured distance of its latest milestone.
+pub(crate) fn is_healthy<B: Backend>(tangle: &MsTangle<B>, config: &ApiConfig) -> bool {
+    within_distance(
+        *tangle.get_latest_milestone_index(),
+        *tangle.get_latest_solid_milestone_index(),
+        config.health_milestone_distance,
+    )
+}
+
+fn within_distance(latest: u32, solid: u32, distance: u32) -> bool {
+    solid >= latest.saturating_sub(distance)
+}
+
+pub(crate) async fn health<B: Backend>(
+    config: ApiConfig,
+    tangle: ResHandle<MsTangle<B>>,
+) -> Result<impl Reply, Infallible> {
+    if is_healthy(&tangle, &config) {
+        Ok(StatusCode::OK)
+    } else {
+        Ok(StatusCode::SERVICE_UNAVAILABLE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn distance_boundary() {
+        assert!(within_distance(10, 10, 2));
+        assert!(within_distance(10, 8, 2));
+        assert!(!within_distance(10, 7, 2));
+        assert!(within_distance(10, 10, 0));
+        assert!(!within_distance(10, 9, 0));
+    }
+
+    #[test]
+    fn no_latest_milestone() {
+        assert!(within_distance(0, 0, 0));
+        assert!(within_distance(0, 0, 2));
+        assert!(within_distance(1, 0, 2));
+    }
+}
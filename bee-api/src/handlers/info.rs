@@ -0,0 +1,108 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::{config::ApiConfig, handlers::health::is_healthy, storage::Backend, types::DataResponse};
+
+use bee_common_ext::node::ResHandle;
+use bee_protocol::{tangle::MsTangle, Protocol};
+
+use serde::Serialize;
+use warp::Reply;
+
+use std::convert::Infallible;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct InfoResponse {
+    pub(crate) name: String,
+    pub(crate) version: String,
+    pub(crate) is_healthy: bool,
+    pub(crate) network_name: String,
+    pub(crate) network_id: String,
+    pub(crate) latest_milestone_index: u32,
+    pub(crate) solid_milestone_index: u32,
+    pub(crate) snapshot_index: u32,
+    pub(crate) pruning_index: u32,
+    pub(crate) connected_peers: u8,
+    pub(crate) synced_peers: u8,
+}
+
+pub(crate) async fn info<B: Backend>(
+    config: ApiConfig,
+    network_id: (String, u64),
+    version: String,
+    tangle: ResHandle<MsTangle<B>>,
+) -> Result<impl Reply, Infallible> {
+    Ok(warp::reply::json(&DataResponse::new(InfoResponse {
+        name: "Bee".to_string(),
+        version,
+        is_healthy: is_healthy(&tangle, &config),
+        network_name: network_id.0,
+        // Serialized as a string since JSON consumers usually can't represent a `u64` without loss.
+        network_id: network_id.1.to_string(),
+        latest_milestone_index: *tangle.get_latest_milestone_index(),
+        solid_milestone_index: *tangle.get_latest_solid_milestone_index(),
+        snapshot_index: *tangle.get_snapshot_index(),
+        pruning_index: *tangle.get_pruning_index(),
+        connected_peers: Protocol::connected_peers(),
+        synced_peers: Protocol::synced_peers(),
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn field_names() {
+        let response = serde_json::to_value(InfoResponse {
+            name: "Bee".to_string(),
+            version: "0.1.0".to_string(),
+            is_healthy: true,
+            network_name: "testnet".to_string(),
+            network_id: u64::MAX.to_string(),
+            latest_milestone_index: 4,
+            solid_milestone_index: 3,
+            snapshot_index: 2,
+            pruning_index: 1,
+            connected_peers: 8,
+            synced_peers: 7,
+        })
+        .unwrap();
+
+        let mut names = response
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+
+        assert_eq!(
+            names,
+            vec![
+                "connectedPeers",
+                "isHealthy",
+                "latestMilestoneIndex",
+                "name",
+                "networkId",
+                "networkName",
+                "pruningIndex",
+                "snapshotIndex",
+                "solidMilestoneIndex",
+                "syncedPeers",
+                "version",
+            ]
+        );
+        assert_eq!(response["networkId"], serde_json::json!("18446744073709551615"));
+    }
+}
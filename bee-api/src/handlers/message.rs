@@ -0,0 +1,65 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::{
+    rejection::CustomRejection,
+    storage::Backend,
+    types::{DataResponse, MessageDto},
+};
+
+use bee_common_ext::node::ResHandle;
+use bee_message::{Message, MessageId};
+use bee_protocol::tangle::MsTangle;
+use bee_storage::access::Fetch;
+
+use log::error;
+use warp::{reject, Rejection, Reply};
+
+use std::convert::TryFrom;
+
+/// Looks a message up in the tangle first and then falls back to the storage.
+pub(crate) async fn fetch_message<B: Backend>(
+    message_id: &MessageId,
+    tangle: &MsTangle<B>,
+    storage: &B,
+) -> Result<Message, Rejection> {
+    if let Some(message) = tangle.get(message_id).await {
+        return Ok((*message).clone());
+    }
+
+    match Fetch::<MessageId, Message>::fetch(storage, message_id).await {
+        Ok(Some(message)) => Ok(message),
+        Ok(None) => Err(reject::custom(CustomRejection::NotFound(format!(
+            "Message {} not found.",
+            message_id
+        )))),
+        Err(e) => {
+            error!("Fetching message {} failed: {:?}.", message_id, e);
+            Err(reject::custom(CustomRejection::InternalError))
+        }
+    }
+}
+
+pub(crate) async fn message<B: Backend>(
+    message_id: MessageId,
+    tangle: ResHandle<MsTangle<B>>,
+    storage: ResHandle<B>,
+) -> Result<impl Reply, Rejection> {
+    let message = fetch_message(&message_id, &tangle, &storage).await?;
+
+    match MessageDto::try_from(&message) {
+        Ok(dto) => Ok(warp::reply::json(&DataResponse::new(dto))),
+        Err(e) => {
+            error!("Converting message {} failed: {}.", message_id, e);
+            Err(reject::custom(CustomRejection::InternalError))
+        }
+    }
+}
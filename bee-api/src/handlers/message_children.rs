@@ -0,0 +1,57 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::{rejection::CustomRejection, storage::Backend, types::DataResponse};
+
+use bee_common_ext::node::ResHandle;
+use bee_message::MessageId;
+use bee_protocol::tangle::MsTangle;
+use bee_storage::access::Fetch;
+
+use log::error;
+use serde::Serialize;
+use warp::{reject, Rejection, Reply};
+
+use std::collections::HashSet;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MessageChildrenResponse {
+    pub(crate) message_id: String,
+    pub(crate) count: usize,
+    pub(crate) children_message_ids: Vec<String>,
+}
+
+pub(crate) async fn message_children<B: Backend>(
+    message_id: MessageId,
+    tangle: ResHandle<MsTangle<B>>,
+    storage: ResHandle<B>,
+) -> Result<impl Reply, Rejection> {
+    // Children still held by the tangle may not have been written to the edge column family yet.
+    let mut children: HashSet<MessageId> = tangle.get_children(&message_id);
+
+    match Fetch::<MessageId, Vec<MessageId>>::fetch(&*storage, &message_id).await {
+        Ok(Some(stored)) => children.extend(stored),
+        Ok(None) => {}
+        Err(e) => {
+            error!("Fetching children of message {} failed: {:?}.", message_id, e);
+            return Err(reject::custom(CustomRejection::InternalError));
+        }
+    }
+
+    let children_message_ids = children.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+    Ok(warp::reply::json(&DataResponse::new(MessageChildrenResponse {
+        message_id: message_id.to_string(),
+        count: children_message_ids.len(),
+        children_message_ids,
+    })))
+}
@@ -0,0 +1,100 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::{handlers::message::fetch_message, rejection::CustomRejection, storage::Backend, types::DataResponse};
+
+use bee_common_ext::node::ResHandle;
+use bee_message::{payload::Payload, MessageId};
+use bee_protocol::tangle::{MessageMetadata, MsTangle};
+use bee_storage::access::Fetch;
+
+use log::error;
+use serde::Serialize;
+use warp::{reject, Rejection, Reply};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MessageMetadataResponse {
+    pub(crate) message_id: String,
+    pub(crate) parent1_message_id: String,
+    pub(crate) parent2_message_id: String,
+    pub(crate) is_solid: bool,
+    pub(crate) milestone_index: Option<u32>,
+    pub(crate) referenced_by_milestone_index: Option<u32>,
+    pub(crate) ledger_inclusion_state: Option<LedgerInclusionState>,
+    pub(crate) otrsi: Option<u32>,
+    pub(crate) ytrsi: Option<u32>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum LedgerInclusionState {
+    Included,
+    Conflicting,
+    NoTransaction,
+}
+
+pub(crate) async fn message_metadata<B: Backend>(
+    message_id: MessageId,
+    tangle: ResHandle<MsTangle<B>>,
+    storage: ResHandle<B>,
+) -> Result<impl Reply, Rejection> {
+    let message = fetch_message(&message_id, &tangle, &storage).await?;
+
+    let metadata = match tangle.get_metadata(&message_id) {
+        Some(metadata) => metadata,
+        None => match Fetch::<MessageId, MessageMetadata>::fetch(&*storage, &message_id).await {
+            Ok(Some(metadata)) => metadata,
+            Ok(None) => {
+                return Err(reject::custom(CustomRejection::NotFound(format!(
+                    "Metadata of message {} not found.",
+                    message_id
+                ))))
+            }
+            Err(e) => {
+                error!("Fetching metadata of message {} failed: {:?}.", message_id, e);
+                return Err(reject::custom(CustomRejection::InternalError));
+            }
+        },
+    };
+
+    let flags = metadata.flags();
+
+    let ledger_inclusion_state = if !flags.is_confirmed() {
+        None
+    } else if flags.is_conflicting() {
+        Some(LedgerInclusionState::Conflicting)
+    } else if let Some(Payload::Transaction(_)) = message.payload() {
+        Some(LedgerInclusionState::Included)
+    } else {
+        Some(LedgerInclusionState::NoTransaction)
+    };
+
+    Ok(warp::reply::json(&DataResponse::new(MessageMetadataResponse {
+        message_id: message_id.to_string(),
+        parent1_message_id: message.parent1().to_string(),
+        parent2_message_id: message.parent2().to_string(),
+        is_solid: tangle.is_solid_message(&message_id) || flags.is_solid(),
+        milestone_index: if flags.is_milestone() {
+            Some(*metadata.milestone_index())
+        } else {
+            None
+        },
+        referenced_by_milestone_index: if flags.is_confirmed() {
+            Some(*metadata.milestone_index())
+        } else {
+            None
+        },
+        ledger_inclusion_state,
+        otrsi: tangle.otrsi(&message_id).or_else(|| metadata.otrsi()).map(|i| *i),
+        ytrsi: tangle.ytrsi(&message_id).or_else(|| metadata.ytrsi()).map(|i| *i),
+    })))
+}
@@ -0,0 +1,28 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::{handlers::message::fetch_message, storage::Backend};
+
+use bee_common::packable::Packable;
+use bee_common_ext::node::ResHandle;
+use bee_message::MessageId;
+use bee_protocol::tangle::MsTangle;
+
+use warp::{Rejection, Reply};
+
+/// Replies with the packed bytes of a message, which hash to its `MessageId`.
+pub(crate) async fn message_raw<B: Backend>(
+    message_id: MessageId,
+    tangle: ResHandle<MsTangle<B>>,
+    storage: ResHandle<B>,
+) -> Result<impl Reply, Rejection> {
+    Ok(fetch_message(&message_id, &tangle, &storage).await?.pack_new())
+}
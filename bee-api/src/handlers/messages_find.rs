@@ -0,0 +1,106 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::{rejection::CustomRejection, storage::Backend, types::DataResponse};
+
+use bee_common_ext::node::ResHandle;
+use bee_message::{payload::indexation::HashedIndex, MessageId};
+use bee_storage::access::Fetch;
+
+use log::error;
+use serde::{Deserialize, Serialize};
+use warp::{reject, Rejection, Reply};
+
+#[derive(Deserialize)]
+pub(crate) struct MessagesQuery {
+    pub(crate) index: String,
+    /// Either `utf8` (default) or `hex`, the latter allowing to search for indexes that are not valid UTF-8.
+    pub(crate) encoding: Option<String>,
+    pub(crate) cursor: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MessagesResponse {
+    pub(crate) index: String,
+    pub(crate) count: usize,
+    pub(crate) message_ids: Vec<String>,
+    /// Message id to pass as `cursor` to fetch the next page, absent on the last page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) next_cursor: Option<String>,
+}
+
+/// Fetches one page of the message ids of an index, starting right after `cursor` or from the beginning, along with
+/// the cursor of the next page if there is one.
+/// The size of a page is bounded by the storage configuration.
+pub(crate) async fn fetch_message_ids<B: Backend>(
+    storage: &B,
+    index: &HashedIndex,
+    cursor: Option<MessageId>,
+) -> Result<(Vec<MessageId>, Option<MessageId>), Rejection> {
+    match Fetch::<(HashedIndex, Option<MessageId>), (Vec<MessageId>, bool)>::fetch(storage, &(index.clone(), cursor))
+        .await
+    {
+        Ok(page) => {
+            let (message_ids, more) = page.unwrap_or_default();
+            let next_cursor = if more { message_ids.last().copied() } else { None };
+            Ok((message_ids, next_cursor))
+        }
+        Err(e) => {
+            error!("Fetching messages of index {:?} failed: {:?}.", index, e);
+            Err(reject::custom(CustomRejection::InternalError))
+        }
+    }
+}
+
+pub(crate) async fn messages_find<B: Backend>(
+    query: MessagesQuery,
+    storage: ResHandle<B>,
+) -> Result<impl Reply, Rejection> {
+    let index = match query.encoding.as_deref() {
+        None | Some("utf8") => query.index.as_bytes().to_vec(),
+        Some("hex") => hex::decode(&query.index).map_err(|_| {
+            reject::custom(CustomRejection::BadRequest(format!(
+                "Invalid hex encoded index {}.",
+                query.index
+            )))
+        })?,
+        Some(encoding) => {
+            return Err(reject::custom(CustomRejection::BadRequest(format!(
+                "Invalid index encoding {}, expected utf8 or hex.",
+                encoding
+            ))))
+        }
+    };
+
+    if index.is_empty() {
+        return Err(reject::custom(CustomRejection::BadRequest("Empty index.".to_string())));
+    }
+
+    let cursor = match query.cursor {
+        Some(cursor) => Some(
+            cursor
+                .parse::<MessageId>()
+                .map_err(|_| reject::custom(CustomRejection::BadRequest(format!("Invalid cursor {}.", cursor))))?,
+        ),
+        None => None,
+    };
+
+    let hashed_index = HashedIndex::from_index(&index);
+    let (message_ids, next_cursor) = fetch_message_ids(&*storage, &hashed_index, cursor).await?;
+
+    Ok(warp::reply::json(&DataResponse::new(MessagesResponse {
+        index: query.index,
+        count: message_ids.len(),
+        message_ids: message_ids.iter().map(ToString::to_string).collect(),
+        next_cursor: next_cursor.map(|cursor| cursor.to_string()),
+    })))
+}
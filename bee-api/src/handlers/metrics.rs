@@ -0,0 +1,346 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::storage::Backend;
+
+use bee_common_ext::node::ResHandle;
+use bee_protocol::{tangle::MsTangle, Peer, PeerMetrics, Protocol, ProtocolMetrics, WorkerQueue};
+use bee_storage::storage;
+
+use warp::{Rejection, Reply};
+
+use std::{fmt::Write, sync::Arc};
+
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+type Counter<T> = (&'static str, &'static str, fn(&T) -> u64);
+
+const PROTOCOL_COUNTERS: &[Counter<ProtocolMetrics>] = &[
+    (
+        "invalid_messages",
+        "Invalid messages.",
+        ProtocolMetrics::invalid_messages,
+    ),
+    ("new_messages", "New messages.", ProtocolMetrics::new_messages),
+    (
+        "known_messages",
+        "Already known messages.",
+        ProtocolMetrics::known_messages,
+    ),
+    ("invalid_packets", "Invalid packets.", ProtocolMetrics::invalid_packets),
+    (
+        "milestone_requests_received",
+        "Received milestone requests.",
+        ProtocolMetrics::milestone_requests_received,
+    ),
+    (
+        "messages_received",
+        "Received messages.",
+        ProtocolMetrics::messages_received,
+    ),
+    (
+        "message_requests_received",
+        "Received message requests.",
+        ProtocolMetrics::message_requests_received,
+    ),
+    (
+        "heartbeats_received",
+        "Received heartbeats.",
+        ProtocolMetrics::heartbeats_received,
+    ),
+    (
+        "milestone_requests_sent",
+        "Sent milestone requests.",
+        ProtocolMetrics::milestone_requests_sent,
+    ),
+    ("messages_sent", "Sent messages.", ProtocolMetrics::messages_sent),
+    (
+        "message_requests_sent",
+        "Sent message requests.",
+        ProtocolMetrics::message_requests_sent,
+    ),
+    ("heartbeats_sent", "Sent heartbeats.", ProtocolMetrics::heartbeats_sent),
+    ("value_bundles", "Value bundles.", ProtocolMetrics::value_bundles),
+    (
+        "non_value_bundles",
+        "Non-value bundles.",
+        ProtocolMetrics::non_value_bundles,
+    ),
+    (
+        "confirmed_bundles",
+        "Confirmed bundles.",
+        ProtocolMetrics::confirmed_bundles,
+    ),
+    (
+        "conflicting_bundles",
+        "Conflicting bundles.",
+        ProtocolMetrics::conflicting_bundles,
+    ),
+];
+
+const PEER_COUNTERS: &[Counter<PeerMetrics>] = &[
+    (
+        "invalid_messages",
+        "Invalid messages received from the peer.",
+        PeerMetrics::invalid_messages,
+    ),
+    (
+        "new_messages",
+        "New messages received from the peer.",
+        PeerMetrics::new_messages,
+    ),
+    (
+        "known_messages",
+        "Already known messages received from the peer.",
+        PeerMetrics::known_messages,
+    ),
+    (
+        "invalid_packets",
+        "Invalid packets received from the peer.",
+        PeerMetrics::invalid_packets,
+    ),
+    (
+        "milestone_requests_received",
+        "Milestone requests received from the peer.",
+        PeerMetrics::milestone_requests_received,
+    ),
+    (
+        "messages_received",
+        "Messages received from the peer.",
+        PeerMetrics::messages_received,
+    ),
+    (
+        "message_requests_received",
+        "Message requests received from the peer.",
+        PeerMetrics::message_requests_received,
+    ),
+    (
+        "heartbeats_received",
+        "Heartbeats received from the peer.",
+        PeerMetrics::heartbeats_received,
+    ),
+    (
+        "milestone_requests_sent",
+        "Milestone requests sent to the peer.",
+        PeerMetrics::milestone_requests_sent,
+    ),
+    (
+        "messages_sent",
+        "Messages sent to the peer.",
+        PeerMetrics::messages_sent,
+    ),
+    (
+        "message_requests_sent",
+        "Message requests sent to the peer.",
+        PeerMetrics::message_requests_sent,
+    ),
+    (
+        "heartbeats_sent",
+        "Heartbeats sent to the peer.",
+        PeerMetrics::heartbeats_sent,
+    ),
+];
+
+/// Writes a metric family in the Prometheus text exposition format.
+fn family(out: &mut String, name: &str, kind: &str, help: &str, samples: impl IntoIterator<Item = (String, u64)>) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+
+    for (labels, value) in samples {
+        let _ = writeln!(out, "{}{} {}", name, labels, value);
+    }
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Formats label pairs, e.g. `{worker="TangleWorker"}`, escaping their values.
+fn labels(pairs: &[(&str, &str)]) -> String {
+    format!(
+        "{{{}}}",
+        pairs
+            .iter()
+            .map(|(name, value)| format!("{}=\"{}\"", name, escape(value)))
+            .collect::<Vec<String>>()
+            .join(",")
+    )
+}
+
+fn peer_labels(peer: &Peer) -> String {
+    labels(&[
+        ("peer_id", &peer.id().to_string()),
+        ("address", &peer.address().to_string()),
+    ])
+}
+
+/// Turns a backend statistic name, e.g. `rocksdb.block.cache.miss`, into a valid metric name.
+fn storage_metric_name(name: &str) -> String {
+    let sanitized = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+
+    format!("bee_storage_{}", sanitized)
+}
+
+pub(crate) async fn metrics<B: Backend>(
+    tangle: ResHandle<MsTangle<B>>,
+    storage: ResHandle<B>,
+    worker_queues: Arc<Vec<WorkerQueue>>,
+) -> Result<impl Reply, Rejection> {
+    let mut out = String::new();
+
+    let protocol_metrics = Protocol::metrics();
+    for (name, help, counter) in PROTOCOL_COUNTERS {
+        family(
+            &mut out,
+            &format!("bee_protocol_{}_total", name),
+            "counter",
+            help,
+            vec![(String::new(), counter(protocol_metrics))],
+        );
+    }
+
+    let peers = Protocol::peers();
+    family(
+        &mut out,
+        "bee_peers_connected",
+        "gauge",
+        "Connected peers.",
+        vec![(String::new(), peers.len() as u64)],
+    );
+    for (name, help, counter) in PEER_COUNTERS {
+        family(
+            &mut out,
+            &format!("bee_peer_{}_total", name),
+            "counter",
+            help,
+            peers
+                .iter()
+                .map(|peer| (peer_labels(peer), counter(peer.metrics())))
+                .collect::<Vec<(String, u64)>>(),
+        );
+    }
+
+    for (name, help, index) in &[
+        (
+            "latest_milestone_index",
+            "Index of the latest milestone.",
+            tangle.get_latest_milestone_index(),
+        ),
+        (
+            "latest_solid_milestone_index",
+            "Index of the latest solid milestone.",
+            tangle.get_latest_solid_milestone_index(),
+        ),
+        ("snapshot_index", "Index of the snapshot.", tangle.get_snapshot_index()),
+        ("pruning_index", "Index of the pruning.", tangle.get_pruning_index()),
+    ] {
+        family(
+            &mut out,
+            &format!("bee_tangle_{}", name),
+            "gauge",
+            help,
+            vec![(String::new(), **index as u64)],
+        );
+    }
+
+    family(
+        &mut out,
+        "bee_tangle_tips",
+        "gauge",
+        "Tips in the tip pool.",
+        vec![(String::new(), tangle.num_tips().await as u64)],
+    );
+    family(
+        &mut out,
+        "bee_tangle_non_lazy_tips",
+        "gauge",
+        "Non-lazy tips in the tip pool.",
+        vec![(String::new(), tangle.num_non_lazy_tips().await as u64)],
+    );
+
+    family(
+        &mut out,
+        "bee_worker_queue_depth",
+        "gauge",
+        "Events waiting in the queue of a worker.",
+        worker_queues
+            .iter()
+            .map(|queue| (labels(&[("worker", queue.name())]), queue.depth() as u64))
+            .collect::<Vec<(String, u64)>>(),
+    );
+
+    for (name, value) in storage::Backend::statistics(&*storage) {
+        family(
+            &mut out,
+            &storage_metric_name(&name),
+            "untyped",
+            &format!("Storage statistic {}.", name),
+            vec![(String::new(), value)],
+        );
+    }
+
+    Ok(warp::reply::with_header(out, "content-type", CONTENT_TYPE))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn exposition() {
+        let mut out = String::new();
+
+        family(
+            &mut out,
+            "bee_peer_messages_sent_total",
+            "counter",
+            "Messages sent to the peer.",
+            vec![
+                (labels(&[("peer_id", "12D3KooW"), ("address", "/dns/a\"b\\c\nd")]), 42),
+                (labels(&[("peer_id", "12D3KooX"), ("address", "/ip4/1.2.3.4")]), 0),
+            ],
+        );
+        family(
+            &mut out,
+            "bee_peers_connected",
+            "gauge",
+            "Connected peers.",
+            vec![(String::new(), 2)],
+        );
+
+        assert_eq!(
+            out,
+            "# HELP bee_peer_messages_sent_total Messages sent to the peer.\n\
+             # TYPE bee_peer_messages_sent_total counter\n\
+             bee_peer_messages_sent_total{peer_id=\"12D3KooW\",address=\"/dns/a\\\"b\\\\c\\nd\"} 42\n\
+             bee_peer_messages_sent_total{peer_id=\"12D3KooX\",address=\"/ip4/1.2.3.4\"} 0\n\
+             # HELP bee_peers_connected Connected peers.\n\
+             # TYPE bee_peers_connected gauge\n\
+             bee_peers_connected 2\n"
+        );
+    }
+
+    #[test]
+    fn storage_metric_names() {
+        assert_eq!(
+            storage_metric_name("rocksdb.block.cache.miss"),
+            "bee_storage_rocksdb_block_cache_miss"
+        );
+        assert_eq!(
+            storage_metric_name("rocksdb.db.get.micros.count"),
+            "bee_storage_rocksdb_db_get_micros_count"
+        );
+    }
+}
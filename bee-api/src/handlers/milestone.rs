@@ -0,0 +1,112 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::{handlers::message::fetch_message, rejection::CustomRejection, storage::Backend, types::DataResponse};
+
+use bee_common_ext::node::ResHandle;
+use bee_ledger::diff::Diff;
+use bee_message::{payload::Payload, MessageId};
+use bee_protocol::{tangle::MsTangle, MilestoneIndex};
+use bee_storage::access::Fetch;
+
+use log::error;
+use serde::Serialize;
+use warp::{reject, Rejection, Reply};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MilestoneResponse {
+    pub(crate) index: u32,
+    pub(crate) message_id: String,
+    pub(crate) timestamp: u64,
+    /// Absent until the milestone has been confirmed by the ledger.
+    pub(crate) confirmation: Option<ConfirmationDto>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ConfirmationDto {
+    pub(crate) messages_referenced: u32,
+    pub(crate) messages_excluded_no_transaction: u32,
+    pub(crate) messages_excluded_conflicting: u32,
+    pub(crate) messages_included: u32,
+}
+
+impl From<&Diff> for ConfirmationDto {
+    fn from(diff: &Diff) -> Self {
+        ConfirmationDto {
+            messages_referenced: diff.messages_referenced(),
+            messages_excluded_no_transaction: diff.messages_excluded_no_transaction(),
+            messages_excluded_conflicting: diff.messages_excluded_conflicting(),
+            messages_included: diff.messages_included(),
+        }
+    }
+}
+
+/// Looks the message id of a milestone up in the tangle first and then falls back to the storage.
+pub(crate) async fn fetch_milestone_message_id<B: Backend>(
+    index: MilestoneIndex,
+    tangle: &MsTangle<B>,
+    storage: &B,
+) -> Result<MessageId, Rejection> {
+    if let Some(message_id) = tangle.get_milestone_message_id(index) {
+        return Ok(message_id);
+    }
+
+    match Fetch::<MilestoneIndex, MessageId>::fetch(storage, &index).await {
+        Ok(Some(message_id)) => Ok(message_id),
+        Ok(None) => Err(reject::custom(CustomRejection::NotFound(format!(
+            "Milestone {} not found.",
+            *index
+        )))),
+        Err(e) => {
+            error!("Fetching milestone {} failed: {:?}.", *index, e);
+            Err(reject::custom(CustomRejection::InternalError))
+        }
+    }
+}
+
+/// Fetches the ledger diff of a confirmed milestone, if any.
+pub(crate) async fn fetch_diff<B: Backend>(index: MilestoneIndex, storage: &B) -> Result<Option<Diff>, Rejection> {
+    Fetch::<MilestoneIndex, Diff>::fetch(storage, &index)
+        .await
+        .map_err(|e| {
+            error!("Fetching diff of milestone {} failed: {:?}.", *index, e);
+            reject::custom(CustomRejection::InternalError)
+        })
+}
+
+pub(crate) async fn milestone<B: Backend>(
+    index: MilestoneIndex,
+    tangle: ResHandle<MsTangle<B>>,
+    storage: ResHandle<B>,
+) -> Result<impl Reply, Rejection> {
+    let message_id = fetch_milestone_message_id(index, &tangle, &storage).await?;
+    let message = fetch_message(&message_id, &tangle, &storage).await?;
+
+    let timestamp = match message.payload() {
+        Some(Payload::Milestone(milestone)) => milestone.essence().timestamp(),
+        _ => {
+            error!(
+                "Message {} of milestone {} has no milestone payload.",
+                message_id, *index
+            );
+            return Err(reject::custom(CustomRejection::InternalError));
+        }
+    };
+
+    Ok(warp::reply::json(&DataResponse::new(MilestoneResponse {
+        index: *index,
+        message_id: message_id.to_string(),
+        timestamp,
+        confirmation: fetch_diff(index, &storage).await?.as_ref().map(ConfirmationDto::from),
+    })))
+}
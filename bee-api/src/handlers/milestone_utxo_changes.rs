@@ -0,0 +1,44 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::{handlers::milestone::fetch_diff, rejection::CustomRejection, storage::Backend, types::DataResponse};
+
+use bee_common_ext::node::ResHandle;
+use bee_protocol::MilestoneIndex;
+
+use serde::Serialize;
+use warp::{reject, Rejection, Reply};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct UtxoChangesResponse {
+    pub(crate) index: u32,
+    pub(crate) created_outputs: Vec<String>,
+    pub(crate) consumed_outputs: Vec<String>,
+}
+
+pub(crate) async fn milestone_utxo_changes<B: Backend>(
+    index: MilestoneIndex,
+    storage: ResHandle<B>,
+) -> Result<impl Reply, Rejection> {
+    let diff = fetch_diff(index, &storage).await?.ok_or_else(|| {
+        reject::custom(CustomRejection::NotFound(format!(
+            "No UTXO changes found for milestone {}.",
+            *index
+        )))
+    })?;
+
+    Ok(warp::reply::json(&DataResponse::new(UtxoChangesResponse {
+        index: *index,
+        created_outputs: diff.created_outputs().iter().map(ToString::to_string).collect(),
+        consumed_outputs: diff.consumed_outputs().iter().map(ToString::to_string).collect(),
+    })))
+}
@@ -0,0 +1,29 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+pub(crate) mod balance_bech32;
+pub(crate) mod events;
+pub(crate) mod health;
+pub(crate) mod info;
+pub(crate) mod message;
+pub(crate) mod message_children;
+pub(crate) mod message_metadata;
+pub(crate) mod message_raw;
+pub(crate) mod messages_find;
+pub(crate) mod metrics;
+pub(crate) mod milestone;
+pub(crate) mod milestone_utxo_changes;
+pub(crate) mod output;
+pub(crate) mod outputs_ed25519;
+pub(crate) mod peers;
+pub(crate) mod search;
+pub(crate) mod submit_message;
+pub(crate) mod tips;
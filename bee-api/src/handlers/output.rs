@@ -0,0 +1,92 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::{
+    rejection::CustomRejection,
+    storage::Backend,
+    types::{DataResponse, OutputDto},
+};
+
+use bee_common_ext::node::ResHandle;
+use bee_ledger::{output::Output, spent::Spent};
+use bee_message::payload::transaction::OutputId;
+use bee_storage::access::Fetch;
+
+use log::error;
+use serde::Serialize;
+use warp::{reject, Rejection, Reply};
+
+use std::convert::TryFrom;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OutputResponse {
+    pub(crate) message_id: String,
+    pub(crate) transaction_id: String,
+    pub(crate) output_index: u16,
+    pub(crate) is_spent: bool,
+    pub(crate) spent: Option<SpentDto>,
+    pub(crate) output: OutputDto,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SpentDto {
+    pub(crate) transaction_id: String,
+    pub(crate) milestone_index: u32,
+}
+
+impl From<&Spent> for SpentDto {
+    fn from(spent: &Spent) -> Self {
+        SpentDto {
+            transaction_id: spent.target().to_string(),
+            milestone_index: *spent.index(),
+        }
+    }
+}
+
+pub(crate) async fn output<B: Backend>(output_id: OutputId, storage: ResHandle<B>) -> Result<impl Reply, Rejection> {
+    let output = match Fetch::<OutputId, Output>::fetch(&*storage, &output_id).await {
+        Ok(Some(output)) => output,
+        Ok(None) => {
+            return Err(reject::custom(CustomRejection::NotFound(format!(
+                "Output {} not found.",
+                output_id
+            ))))
+        }
+        Err(e) => {
+            error!("Fetching output {} failed: {:?}.", output_id, e);
+            return Err(reject::custom(CustomRejection::InternalError));
+        }
+    };
+    let spent = match Fetch::<OutputId, Spent>::fetch(&*storage, &output_id).await {
+        Ok(spent) => spent,
+        Err(e) => {
+            error!("Fetching spent status of output {} failed: {:?}.", output_id, e);
+            return Err(reject::custom(CustomRejection::InternalError));
+        }
+    };
+
+    match OutputDto::try_from(output.inner()) {
+        Ok(dto) => Ok(warp::reply::json(&DataResponse::new(OutputResponse {
+            message_id: output.message_id().to_string(),
+            transaction_id: output_id.transaction_id().to_string(),
+            output_index: output_id.index(),
+            is_spent: spent.is_some(),
+            spent: spent.as_ref().map(SpentDto::from),
+            output: dto,
+        }))),
+        Err(e) => {
+            error!("Converting output {} failed: {}.", output_id, e);
+            Err(reject::custom(CustomRejection::InternalError))
+        }
+    }
+}
@@ -0,0 +1,85 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::{rejection::CustomRejection, storage::Backend, types::DataResponse};
+
+use bee_common_ext::node::ResHandle;
+use bee_message::payload::transaction::{Ed25519Address, OutputId};
+use bee_storage::access::Fetch;
+
+use log::error;
+use serde::{Deserialize, Serialize};
+use warp::{reject, Rejection, Reply};
+
+#[derive(Deserialize)]
+pub(crate) struct OutputsQuery {
+    pub(crate) cursor: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OutputsResponse {
+    pub(crate) address_type: u8,
+    pub(crate) address: String,
+    pub(crate) count: usize,
+    pub(crate) output_ids: Vec<String>,
+    /// Output id to pass as `cursor` to fetch the next page, absent on the last page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) next_cursor: Option<String>,
+}
+
+/// Fetches one page of the output ids of an address, starting right after `cursor` or from the beginning, along with
+/// the cursor of the next page if there is one.
+/// The size of a page is bounded by the storage configuration.
+pub(crate) async fn fetch_output_ids<B: Backend>(
+    storage: &B,
+    address: &Ed25519Address,
+    cursor: Option<OutputId>,
+) -> Result<(Vec<OutputId>, Option<OutputId>), Rejection> {
+    match Fetch::<(Ed25519Address, Option<OutputId>), (Vec<OutputId>, bool)>::fetch(storage, &(address.clone(), cursor))
+        .await
+    {
+        Ok(page) => {
+            let (output_ids, more) = page.unwrap_or_default();
+            let next_cursor = if more { output_ids.last().copied() } else { None };
+            Ok((output_ids, next_cursor))
+        }
+        Err(e) => {
+            error!("Fetching outputs of address {} failed: {:?}.", address, e);
+            Err(reject::custom(CustomRejection::InternalError))
+        }
+    }
+}
+
+pub(crate) async fn outputs_ed25519<B: Backend>(
+    address: Ed25519Address,
+    query: OutputsQuery,
+    storage: ResHandle<B>,
+) -> Result<impl Reply, Rejection> {
+    let cursor = match query.cursor {
+        Some(cursor) => Some(
+            cursor
+                .parse::<OutputId>()
+                .map_err(|_| reject::custom(CustomRejection::BadRequest(format!("Invalid cursor {}.", cursor))))?,
+        ),
+        None => None,
+    };
+
+    let (output_ids, next_cursor) = fetch_output_ids(&*storage, &address, cursor).await?;
+
+    Ok(warp::reply::json(&DataResponse::new(OutputsResponse {
+        address_type: 1,
+        address: address.to_string(),
+        count: output_ids.len(),
+        output_ids: output_ids.iter().map(ToString::to_string).collect(),
+        next_cursor: next_cursor.map(|cursor| cursor.to_string()),
+    })))
+}
@@ -0,0 +1,290 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::{rejection::CustomRejection, types::DataResponse};
+
+use bee_network::{Command, Multiaddr, Network, PeerId, Protocol as MultiaddrProtocol};
+use bee_peering::ManualPeers;
+use bee_protocol::{Peer, Protocol};
+
+use dashmap::DashMap;
+use log::error;
+use serde::{Deserialize, Serialize};
+use warp::{http::StatusCode, reject, Rejection, Reply};
+
+use std::sync::Arc;
+
+/// A peer added through the API, remembered so that it is listed even while disconnected.
+pub(crate) struct KnownPeer {
+    pub(crate) address: Multiaddr,
+    pub(crate) alias: Option<String>,
+}
+
+pub(crate) type KnownPeers = Arc<DashMap<PeerId, KnownPeer>>;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AddPeerRequest {
+    pub(crate) multi_address: String,
+    pub(crate) alias: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AddressRequest {
+    pub(crate) multi_address: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PeerDto {
+    pub(crate) id: String,
+    pub(crate) alias: Option<String>,
+    pub(crate) multi_address: String,
+    pub(crate) connected: bool,
+    pub(crate) metrics: Option<PeerMetricsDto>,
+    pub(crate) heartbeat: Option<HeartbeatDto>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PeerMetricsDto {
+    pub(crate) invalid_messages: u64,
+    pub(crate) new_messages: u64,
+    pub(crate) known_messages: u64,
+    pub(crate) invalid_packets: u64,
+    pub(crate) messages_received: u64,
+    pub(crate) message_requests_received: u64,
+    pub(crate) milestone_requests_received: u64,
+    pub(crate) heartbeats_received: u64,
+    pub(crate) messages_sent: u64,
+    pub(crate) message_requests_sent: u64,
+    pub(crate) milestone_requests_sent: u64,
+    pub(crate) heartbeats_sent: u64,
+}
+
+/// Milestone ranges and neighbour counts announced by the last heartbeat of a peer.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HeartbeatDto {
+    pub(crate) solid_milestone_index: u32,
+    pub(crate) pruned_milestone_index: u32,
+    pub(crate) latest_milestone_index: u32,
+    pub(crate) connected_neighbors: u8,
+    pub(crate) synced_neighbors: u8,
+}
+
+impl From<&Peer> for PeerMetricsDto {
+    fn from(peer: &Peer) -> Self {
+        let metrics = peer.metrics();
+
+        PeerMetricsDto {
+            invalid_messages: metrics.invalid_messages(),
+            new_messages: metrics.new_messages(),
+            known_messages: metrics.known_messages(),
+            invalid_packets: metrics.invalid_packets(),
+            messages_received: metrics.messages_received(),
+            message_requests_received: metrics.message_requests_received(),
+            milestone_requests_received: metrics.milestone_requests_received(),
+            heartbeats_received: metrics.heartbeats_received(),
+            messages_sent: metrics.messages_sent(),
+            message_requests_sent: metrics.message_requests_sent(),
+            milestone_requests_sent: metrics.milestone_requests_sent(),
+            heartbeats_sent: metrics.heartbeats_sent(),
+        }
+    }
+}
+
+impl From<&Peer> for HeartbeatDto {
+    fn from(peer: &Peer) -> Self {
+        HeartbeatDto {
+            solid_milestone_index: *peer.latest_solid_milestone_index(),
+            pruned_milestone_index: *peer.pruned_index(),
+            latest_milestone_index: *peer.latest_milestone_index(),
+            connected_neighbors: peer.connected_peers(),
+            synced_neighbors: peer.synced_peers(),
+        }
+    }
+}
+
+fn peer_dto(id: &PeerId, known_peers: &KnownPeers, manual_peers: &ManualPeers) -> Option<PeerDto> {
+    let known = known_peers.get(id);
+    let connected = Protocol::peer(id);
+
+    let multi_address = match (&known, &connected) {
+        (_, Some(peer)) => peer.address().to_string(),
+        (Some(known), None) => known.address.to_string(),
+        (None, None) => return None,
+    };
+
+    Some(PeerDto {
+        id: id.to_string(),
+        // Peers added through the API are forgotten on restart, unlike the configured ones.
+        alias: known
+            .and_then(|known| known.alias.clone())
+            .or_else(|| manual_peers.read().unwrap().alias(id).map(ToString::to_string)),
+        multi_address,
+        connected: connected.is_some(),
+        metrics: connected.as_deref().map(PeerMetricsDto::from),
+        heartbeat: connected.as_deref().map(HeartbeatDto::from),
+    })
+}
+
+fn not_found(id: &PeerId) -> Rejection {
+    reject::custom(CustomRejection::NotFound(format!("Peer {} not found.", id)))
+}
+
+fn send(network: &Network, command: Command) -> Result<(), Rejection> {
+    network.unbounded_send(command).map_err(|e| {
+        error!("Sending network command failed: {:?}", e);
+        reject::custom(CustomRejection::ServiceUnavailable(
+            "The network is not running.".to_string(),
+        ))
+    })
+}
+
+fn parse_address(address: &str) -> Result<Multiaddr, Rejection> {
+    address.parse::<Multiaddr>().map_err(|_| {
+        reject::custom(CustomRejection::BadRequest(format!(
+            "Invalid multiaddress {}.",
+            address
+        )))
+    })
+}
+
+pub(crate) async fn peers(known_peers: KnownPeers, manual_peers: ManualPeers) -> Result<impl Reply, Rejection> {
+    let mut ids = Protocol::peers()
+        .iter()
+        .map(|peer| peer.id().clone())
+        .collect::<Vec<PeerId>>();
+
+    for known in known_peers.iter() {
+        if !ids.contains(known.key()) {
+            ids.push(known.key().clone());
+        }
+    }
+
+    Ok(warp::reply::json(&DataResponse::new(
+        ids.iter()
+            .filter_map(|id| peer_dto(id, &known_peers, &manual_peers))
+            .collect::<Vec<PeerDto>>(),
+    )))
+}
+
+pub(crate) async fn peer(
+    id: PeerId,
+    known_peers: KnownPeers,
+    manual_peers: ManualPeers,
+) -> Result<impl Reply, Rejection> {
+    match peer_dto(&id, &known_peers, &manual_peers) {
+        Some(dto) => Ok(warp::reply::json(&DataResponse::new(dto))),
+        None => Err(not_found(&id)),
+    }
+}
+
+pub(crate) async fn add_peer(
+    request: AddPeerRequest,
+    network: Network,
+    known_peers: KnownPeers,
+    manual_peers: ManualPeers,
+) -> Result<impl Reply, Rejection> {
+    let mut address = parse_address(&request.multi_address)?;
+
+    // The peer id is expected as the last component of the multiaddress, e.g. `/ip4/1.2.3.4/tcp/15600/p2p/<id>`.
+    let id = match address.pop() {
+        Some(MultiaddrProtocol::P2p(multihash)) => PeerId::from_multihash(multihash).map_err(|_| {
+            reject::custom(CustomRejection::BadRequest(format!(
+                "Invalid peer id in {}.",
+                request.multi_address
+            )))
+        })?,
+        _ => {
+            return Err(reject::custom(CustomRejection::BadRequest(format!(
+                "Missing peer id in {}.",
+                request.multi_address
+            ))))
+        }
+    };
+
+    send(
+        &network,
+        Command::ConnectPeer {
+            address: address.clone(),
+            id: id.clone(),
+        },
+    )?;
+
+    known_peers.insert(
+        id.clone(),
+        KnownPeer {
+            address,
+            alias: request.alias,
+        },
+    );
+
+    match peer_dto(&id, &known_peers, &manual_peers) {
+        Some(dto) => Ok(warp::reply::with_status(
+            warp::reply::json(&DataResponse::new(dto)),
+            StatusCode::CREATED,
+        )),
+        None => Err(not_found(&id)),
+    }
+}
+
+pub(crate) async fn remove_peer(
+    id: PeerId,
+    network: Network,
+    known_peers: KnownPeers,
+) -> Result<impl Reply, Rejection> {
+    let known = known_peers.remove(&id).is_some();
+
+    if !known && Protocol::peer(&id).is_none() {
+        return Err(not_found(&id));
+    }
+
+    send(&network, Command::DisconnectPeer { id })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub(crate) async fn ban_peer(id: PeerId, network: Network) -> Result<impl Reply, Rejection> {
+    send(&network, Command::BanPeer { id })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub(crate) async fn unban_peer(id: PeerId, network: Network) -> Result<impl Reply, Rejection> {
+    send(&network, Command::UnbanPeer { id })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub(crate) async fn ban_address(request: AddressRequest, network: Network) -> Result<impl Reply, Rejection> {
+    send(
+        &network,
+        Command::BanAddr {
+            address: parse_address(&request.multi_address)?,
+        },
+    )?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub(crate) async fn unban_address(request: AddressRequest, network: Network) -> Result<impl Reply, Rejection> {
+    send(
+        &network,
+        Command::UnbanAddr {
+            address: parse_address(&request.multi_address)?,
+        },
+    )?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,220 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::{
+    handlers::{messages_find::fetch_message_ids, outputs_ed25519::fetch_output_ids},
+    rejection::CustomRejection,
+    storage::Backend,
+    types::DataResponse,
+};
+
+use bee_common_ext::node::ResHandle;
+use bee_ledger::output::Output;
+use bee_message::{
+    payload::{
+        indexation::HashedIndex,
+        transaction::{Address, Ed25519Address, OutputId, TransactionId},
+    },
+    Message, MessageId,
+};
+use bee_protocol::{tangle::MsTangle, MilestoneIndex};
+use bee_storage::access::Fetch;
+
+use log::error;
+use percent_encoding::percent_decode_str;
+use serde::Serialize;
+use warp::{reject, Rejection, Reply};
+
+use std::fmt::Debug;
+
+/// Something a search query was found to refer to.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub(crate) enum SearchResultDto {
+    #[serde(rename_all = "camelCase")]
+    Message { message_id: String },
+    #[serde(rename_all = "camelCase")]
+    Transaction { transaction_id: String, message_id: String },
+    #[serde(rename_all = "camelCase")]
+    Output { output_id: String, message_id: String },
+    #[serde(rename_all = "camelCase")]
+    Address { address_type: u8, address: String },
+    #[serde(rename_all = "camelCase")]
+    Milestone { index: u32, message_id: String },
+    #[serde(rename_all = "camelCase")]
+    Indexation { index: String },
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SearchResponse {
+    pub(crate) query: String,
+    pub(crate) count: usize,
+    /// A query may match several kinds of entities, e.g. hex strings of the same length are both message ids and
+    /// addresses, so all the matches are listed.
+    pub(crate) results: Vec<SearchResultDto>,
+}
+
+fn internal_error<E: Debug>(what: String) -> impl FnOnce(E) -> Rejection {
+    move |e| {
+        error!("Searching {} failed: {:?}.", what, e);
+        reject::custom(CustomRejection::InternalError)
+    }
+}
+
+async fn search_message<B: Backend>(
+    message_id: MessageId,
+    tangle: &MsTangle<B>,
+    storage: &B,
+) -> Result<Option<SearchResultDto>, Rejection> {
+    let found = tangle.contains(&message_id).await
+        || Fetch::<MessageId, Message>::fetch(storage, &message_id)
+            .await
+            .map_err(internal_error(format!("message {}", message_id)))?
+            .is_some();
+
+    Ok(if found {
+        Some(SearchResultDto::Message {
+            message_id: message_id.to_string(),
+        })
+    } else {
+        None
+    })
+}
+
+async fn fetch_output<B: Backend>(output_id: &OutputId, storage: &B) -> Result<Option<Output>, Rejection> {
+    Fetch::<OutputId, Output>::fetch(storage, output_id)
+        .await
+        .map_err(internal_error(format!("output {}", output_id)))
+}
+
+async fn search_transaction<B: Backend>(
+    transaction_id: TransactionId,
+    storage: &B,
+) -> Result<Option<SearchResultDto>, Rejection> {
+    // Transactions are not stored as such but every transaction has a first output, referencing its message.
+    let output_id = match OutputId::new(transaction_id, 0) {
+        Ok(output_id) => output_id,
+        Err(_) => return Ok(None),
+    };
+
+    Ok(fetch_output(&output_id, storage)
+        .await?
+        .map(|output| SearchResultDto::Transaction {
+            transaction_id: transaction_id.to_string(),
+            message_id: output.message_id().to_string(),
+        }))
+}
+
+async fn search_output<B: Backend>(output_id: OutputId, storage: &B) -> Result<Option<SearchResultDto>, Rejection> {
+    Ok(fetch_output(&output_id, storage)
+        .await?
+        .map(|output| SearchResultDto::Output {
+            output_id: output_id.to_string(),
+            message_id: output.message_id().to_string(),
+        }))
+}
+
+async fn search_address<B: Backend>(
+    address: Ed25519Address,
+    storage: &B,
+) -> Result<Option<SearchResultDto>, Rejection> {
+    Ok(if fetch_output_ids(storage, &address, None).await?.0.is_empty() {
+        None
+    } else {
+        Some(SearchResultDto::Address {
+            address_type: 1,
+            address: Address::Ed25519(address).to_bech32(),
+        })
+    })
+}
+
+async fn search_milestone<B: Backend>(
+    index: MilestoneIndex,
+    tangle: &MsTangle<B>,
+    storage: &B,
+) -> Result<Option<SearchResultDto>, Rejection> {
+    let message_id = match tangle.get_milestone_message_id(index) {
+        Some(message_id) => Some(message_id),
+        None => Fetch::<MilestoneIndex, MessageId>::fetch(storage, &index)
+            .await
+            .map_err(internal_error(format!("milestone {}", *index)))?,
+    };
+
+    Ok(message_id.map(|message_id| SearchResultDto::Milestone {
+        index: *index,
+        message_id: message_id.to_string(),
+    }))
+}
+
+async fn search_indexation<B: Backend>(index: &str, storage: &B) -> Result<Option<SearchResultDto>, Rejection> {
+    Ok(
+        if fetch_message_ids(storage, &HashedIndex::from_index(index.as_bytes()), None)
+            .await?
+            .0
+            .is_empty()
+        {
+            None
+        } else {
+            Some(SearchResultDto::Indexation {
+                index: index.to_string(),
+            })
+        },
+    )
+}
+
+pub(crate) async fn search<B: Backend>(
+    query: String,
+    tangle: ResHandle<MsTangle<B>>,
+    storage: ResHandle<B>,
+) -> Result<impl Reply, Rejection> {
+    // Path segments are not decoded by the router and indexes may contain any character.
+    let query = percent_decode_str(&query)
+        .decode_utf8()
+        .map_err(|_| reject::custom(CustomRejection::BadRequest("Invalid UTF-8 query.".to_string())))?
+        .into_owned();
+
+    if query.is_empty() {
+        return Err(reject::custom(CustomRejection::BadRequest("Empty query.".to_string())));
+    }
+
+    let mut results = Vec::new();
+
+    // The id parsers reject strings of the wrong length, so at most the lookups of one length class are done.
+    if let Ok(message_id) = query.parse::<MessageId>() {
+        results.push(search_message(message_id, &tangle, &storage).await?);
+    }
+    if let Ok(transaction_id) = query.parse::<TransactionId>() {
+        results.push(search_transaction(transaction_id, &storage).await?);
+    }
+    if let Ok(address) = query.parse::<Ed25519Address>() {
+        results.push(search_address(address, &storage).await?);
+    }
+    if let Ok(output_id) = query.parse::<OutputId>() {
+        results.push(search_output(output_id, &storage).await?);
+    }
+    if let Ok(Address::Ed25519(address)) = Address::try_from_bech32(&query) {
+        results.push(search_address(address, &storage).await?);
+    }
+    if let Ok(index) = query.parse::<u32>() {
+        results.push(search_milestone(MilestoneIndex(index), &tangle, &storage).await?);
+    }
+    // Any query is a potential index.
+    results.push(search_indexation(&query, &storage).await?);
+
+    let results = results.into_iter().flatten().collect::<Vec<_>>();
+
+    Ok(warp::reply::json(&DataResponse::new(SearchResponse {
+        query,
+        count: results.len(),
+        results,
+    })))
+}
@@ -0,0 +1,291 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::{
+    rejection::CustomRejection,
+    storage::Backend,
+    types::{DataResponse, PayloadDto},
+};
+
+use bee_common::packable::Packable;
+use bee_common_ext::node::ResHandle;
+use bee_message::prelude::*;
+use bee_protocol::{config::ProtocolConfig, tangle::MsTangle, MessageSubmitterError, MessageSubmitterWorkerEvent};
+
+use bytes::Bytes;
+use futures::channel::oneshot;
+use log::error;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Semaphore;
+use warp::{http::StatusCode, reject, Rejection, Reply};
+
+use std::{
+    convert::TryFrom,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// JSON representation of a message to submit; every field but the payload may be left out and is then filled in by
+/// the node.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SubmitMessageRequest {
+    pub(crate) network_id: Option<String>,
+    pub(crate) parent1_message_id: Option<String>,
+    pub(crate) parent2_message_id: Option<String>,
+    pub(crate) payload: Option<PayloadDto>,
+    pub(crate) nonce: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SubmitMessageResponse {
+    pub(crate) message_id: String,
+}
+
+/// A message as submitted by a client, with its missing fields set to `None`.
+struct PartialMessage {
+    network_id: Option<u64>,
+    parent1: Option<MessageId>,
+    parent2: Option<MessageId>,
+    payload: Option<Payload>,
+    nonce: Option<u64>,
+}
+
+/// A PoW permit owned by the mining thread, which keeps running for a while after its request is dropped.
+struct PowPermit(Arc<Semaphore>);
+
+impl Drop for PowPermit {
+    fn drop(&mut self) {
+        self.0.add_permits(1);
+    }
+}
+
+/// Stops the mining when the request is dropped, e.g. when the client disconnects.
+struct CancelOnDrop(Arc<AtomicBool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+fn bad_request(message: String) -> Rejection {
+    reject::custom(CustomRejection::BadRequest(message))
+}
+
+fn parse_binary(body: &[u8]) -> Result<PartialMessage, Rejection> {
+    let message = Message::unpack(&mut &body[..]).map_err(|e| bad_request(format!("Invalid message: {:?}.", e)))?;
+    // Binary messages can't omit fields so zeroed ones are considered missing.
+    let some_if = |parent: &MessageId| {
+        if *parent != MessageId::null() {
+            Some(*parent)
+        } else {
+            None
+        }
+    };
+
+    Ok(PartialMessage {
+        network_id: Some(message.network_id()).filter(|network_id| *network_id != 0),
+        parent1: some_if(message.parent1()),
+        parent2: some_if(message.parent2()),
+        payload: message.payload().clone(),
+        nonce: Some(message.nonce()).filter(|nonce| *nonce != 0),
+    })
+}
+
+fn parse_json(body: &[u8]) -> Result<PartialMessage, Rejection> {
+    let request = serde_json::from_slice::<SubmitMessageRequest>(body)
+        .map_err(|e| bad_request(format!("Invalid message: {}.", e)))?;
+    let parse_u64 = |field: &str, value: Option<String>| {
+        value
+            .map(|value| {
+                value
+                    .parse::<u64>()
+                    .map_err(|_| bad_request(format!("Invalid {} {}.", field, value)))
+            })
+            .transpose()
+    };
+    let parse_parent = |value: Option<String>| {
+        value
+            .map(|value| {
+                value
+                    .parse::<MessageId>()
+                    .map_err(|_| bad_request(format!("Invalid parent message id {}.", value)))
+            })
+            .transpose()
+    };
+
+    Ok(PartialMessage {
+        network_id: parse_u64("network id", request.network_id)?,
+        parent1: parse_parent(request.parent1_message_id)?,
+        parent2: parse_parent(request.parent2_message_id)?,
+        payload: request
+            .payload
+            .as_ref()
+            .map(Payload::try_from)
+            .transpose()
+            .map_err(|e| bad_request(format!("Invalid payload: {}.", e)))?,
+        nonce: parse_u64("nonce", request.nonce)?,
+    })
+}
+
+fn build_message(
+    network_id: u64,
+    parents: (MessageId, MessageId),
+    payload: Option<Payload>,
+    nonce: u64,
+) -> Result<Message, Rejection> {
+    let mut builder = Message::builder()
+        .with_network_id(network_id)
+        .with_parent1(parents.0)
+        .with_parent2(parents.1)
+        .with_nonce(nonce);
+
+    if let Some(payload) = payload {
+        builder = builder.with_payload(payload);
+    }
+
+    builder
+        .finish()
+        .map_err(|e| bad_request(format!("Invalid message: {:?}.", e)))
+}
+
+pub(crate) async fn submit_message<B: Backend>(
+    content_type: Option<String>,
+    body: Bytes,
+    network_id: (String, u64),
+    protocol_config: ProtocolConfig,
+    tangle: ResHandle<MsTangle<B>>,
+    message_submitter: flume::Sender<MessageSubmitterWorkerEvent>,
+    pow_permits: Arc<Semaphore>,
+) -> Result<impl Reply, Rejection> {
+    let partial = match content_type {
+        Some(content_type) if content_type.starts_with("application/octet-stream") => parse_binary(&body)?,
+        _ => parse_json(&body)?,
+    };
+
+    let parents = match (partial.parent1, partial.parent2) {
+        (Some(parent1), Some(parent2)) => (parent1, parent2),
+        (parent1, parent2) => match tangle.get_messages_to_approve().await {
+            Some(tips) => (parent1.unwrap_or(tips.0), parent2.unwrap_or(tips.1)),
+            None => {
+                return Err(reject::custom(CustomRejection::ServiceUnavailable(
+                    "No tips available to use as parents.".to_string(),
+                )))
+            }
+        },
+    };
+    let network_id = partial.network_id.unwrap_or(network_id.1);
+
+    let message = match partial.nonce {
+        Some(nonce) => build_message(network_id, parents, partial.payload, nonce)?,
+        None => {
+            // Mining competes with the node for CPU, so only a bounded number of clients are served at once.
+            pow_permits
+                .try_acquire()
+                .map_err(|_| {
+                    reject::custom(CustomRejection::ServiceUnavailable(
+                        "Too many messages are being mined, retry later or provide a nonce.".to_string(),
+                    ))
+                })?
+                .forget();
+            let permit = PowPermit(pow_permits);
+            let message = build_message(network_id, parents, partial.payload, 0)?;
+            let bytes = message.pack_new();
+            let target_score = protocol_config.minimum_pow_score();
+            let cancelled = Arc::new(AtomicBool::new(false));
+            let _cancel = CancelOnDrop(cancelled.clone());
+
+            let mining = tokio::task::spawn_blocking(move || {
+                let _permit = permit;
+                bee_pow::mine_cancellable(&bytes, target_score, &cancelled)
+            });
+
+            match mining.await {
+                Ok(Some(nonce)) => build_message(
+                    message.network_id(),
+                    (*message.parent1(), *message.parent2()),
+                    message.payload().clone(),
+                    nonce,
+                )?,
+                Ok(None) => {
+                    return Err(reject::custom(CustomRejection::ServiceUnavailable(
+                        "No nonce reaching the minimum PoW score was found.".to_string(),
+                    )))
+                }
+                Err(e) => {
+                    error!("Mining a nonce failed: {}.", e);
+                    return Err(reject::custom(CustomRejection::InternalError));
+                }
+            }
+        }
+    };
+
+    let (notifier, waiter) = oneshot::channel();
+
+    if message_submitter
+        .send(MessageSubmitterWorkerEvent { message, notifier })
+        .is_err()
+    {
+        return Err(reject::custom(CustomRejection::ServiceUnavailable(
+            "The message submitter is not running.".to_string(),
+        )));
+    }
+
+    match waiter.await {
+        Ok(Ok(message_id)) => Ok(warp::reply::with_status(
+            warp::reply::json(&DataResponse::new(SubmitMessageResponse {
+                message_id: message_id.to_string(),
+            })),
+            StatusCode::CREATED,
+        )),
+        Ok(Err(MessageSubmitterError::ChannelClosed)) | Err(_) => Err(reject::custom(
+            CustomRejection::ServiceUnavailable("The message processing pipeline is closed.".to_string()),
+        )),
+        Ok(Err(e)) => Err(bad_request(e.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[tokio::test]
+    async fn pow_permit_outlives_request() {
+        let permits = Arc::new(Semaphore::new(1));
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let (started_tx, started_rx) = std::sync::mpsc::channel();
+        let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+
+        permits.try_acquire().unwrap().forget();
+        let permit = PowPermit(permits.clone());
+        let cancel = CancelOnDrop(cancelled.clone());
+        let mining = tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            started_tx.send(()).unwrap();
+            let _ = release_rx.recv();
+        });
+
+        started_rx.recv().unwrap();
+        // The request is dropped while the mining thread still runs.
+        drop(cancel);
+        assert!(cancelled.load(Ordering::Relaxed));
+        assert_eq!(permits.available_permits(), 0);
+
+        release_tx.send(()).unwrap();
+        mining.await.unwrap();
+        assert_eq!(permits.available_permits(), 1);
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::{rejection::CustomRejection, storage::Backend, types::DataResponse};
+
+use bee_common_ext::node::ResHandle;
+use bee_protocol::tangle::MsTangle;
+
+use serde::Serialize;
+use warp::{reject, Rejection, Reply};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TipsResponse {
+    pub(crate) tip1_message_id: String,
+    pub(crate) tip2_message_id: String,
+}
+
+pub(crate) async fn tips<B: Backend>(tangle: ResHandle<MsTangle<B>>) -> Result<impl Reply, Rejection> {
+    // Tips are scored against the latest solid milestone, they are meaningless until the node caught up.
+    if !tangle.is_synced() {
+        return Err(reject::custom(CustomRejection::ServiceUnavailable(
+            "The node is not synchronized.".to_string(),
+        )));
+    }
+
+    match tangle.get_messages_to_approve().await {
+        Some((tip1, tip2)) => Ok(warp::reply::json(&DataResponse::new(TipsResponse {
+            tip1_message_id: tip1.to_string(),
+            tip2_message_id: tip2.to_string(),
+        }))),
+        None => Err(reject::custom(CustomRejection::ServiceUnavailable(
+            "No non-lazy tips available.".to_string(),
+        ))),
+    }
+}
@@ -8,3 +8,52 @@
 // Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
 // an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and limitations under the License.
+
+//! A crate that exposes the state of a Bee node over HTTP.
+
+pub mod auth;
+pub mod config;
+pub mod storage;
+
+mod cors;
+mod events;
+mod filters;
+mod handlers;
+mod rate_limit;
+mod rejection;
+mod tls;
+mod types;
+mod worker;
+
+use config::ApiConfig;
+use storage::Backend;
+use worker::ApiWorker;
+
+use bee_common_ext::node::{Node, NodeBuilder};
+use bee_network::{Keypair, Network};
+use bee_peering::ManualPeers;
+use bee_protocol::config::ProtocolConfig;
+
+pub fn init<N: Node>(
+    config: ApiConfig,
+    network_id: (String, u64),
+    version: String,
+    protocol_config: ProtocolConfig,
+    network: Network,
+    local_keypair: Keypair,
+    manual_peers: ManualPeers,
+    node_builder: N::Builder,
+) -> N::Builder
+where
+    N::Backend: Backend,
+{
+    node_builder.with_worker_cfg::<ApiWorker>((
+        config,
+        network_id,
+        version,
+        protocol_config,
+        network,
+        local_keypair,
+        manual_peers,
+    ))
+}
@@ -0,0 +1,206 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+//! Token bucket rate limiting of API requests.
+//!
+//! Each client IP gets a bucket per `Limit`, i.e. per route group and a separate one for message submission. A bucket
+//! holds up to `burst` tokens and refills at `rate` tokens per second; every request takes a token and is rejected with
+//! `429 Too Many Requests` when there is none left.
+
+use crate::{
+    auth::RouteGroup,
+    config::{ApiConfig, RateLimit},
+    filters,
+    rejection::CustomRejection,
+};
+
+use dashmap::DashMap;
+use warp::{http::Method, path::FullPath, reject, Filter, Rejection};
+
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+const SUBMIT_MESSAGE_ROUTE: &str = "/api/v1/messages";
+const MAX_RETRY_AFTER_SECS: f64 = 24.0 * 60.0 * 60.0;
+
+/// The quotas a request may be counted against.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) enum Limit {
+    Group(RouteGroup),
+    Submit,
+}
+
+impl Limit {
+    fn of(method: &Method, path: &str) -> Self {
+        if method == Method::POST && path.trim_end_matches('/') == SUBMIT_MESSAGE_ROUTE {
+            Limit::Submit
+        } else {
+            Limit::Group(RouteGroup::of(path))
+        }
+    }
+}
+
+struct Bucket {
+    tokens: f64,
+    last: Instant,
+}
+
+impl Bucket {
+    fn refill(&mut self, limit: &RateLimit, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last).as_secs_f64();
+
+        self.tokens = (self.tokens + elapsed * limit.rate()).min(limit.burst() as f64);
+        self.last = now;
+    }
+
+    /// Takes a token or returns how long to wait until one is available.
+    fn take(&mut self, limit: &RateLimit, now: Instant) -> Result<(), Duration> {
+        self.refill(limit, now);
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            // A very low rate would otherwise advertise an absurd delay.
+            let wait = (1.0 - self.tokens) / limit.rate();
+            Err(Duration::from_secs_f64(wait.min(MAX_RETRY_AFTER_SECS)))
+        }
+    }
+}
+
+/// Rounds the delay up so that retrying after the advertised `Retry-After` seconds succeeds.
+fn retry_after_secs(retry_after: Duration) -> u64 {
+    retry_after.as_secs() + (retry_after.subsec_nanos() > 0) as u64
+}
+
+#[derive(Clone)]
+pub(crate) struct RateLimiter {
+    config: ApiConfig,
+    buckets: Arc<DashMap<(IpAddr, Limit), Bucket>>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(config: ApiConfig) -> Self {
+        Self {
+            config,
+            buckets: Arc::new(DashMap::new()),
+        }
+    }
+
+    fn rate_limit(&self, limit: Limit) -> &RateLimit {
+        match limit {
+            Limit::Group(group) => self.config.rate_limit(group),
+            Limit::Submit => self.config.submit_rate_limit(),
+        }
+    }
+
+    /// Takes a token from the bucket of `ip` for `limit` or returns how long to wait until one is available.
+    pub(crate) fn acquire(&self, ip: IpAddr, limit: Limit) -> Result<(), Duration> {
+        let rate_limit = *self.rate_limit(limit);
+        let now = Instant::now();
+        let mut bucket = self.buckets.entry((ip, limit)).or_insert_with(|| Bucket {
+            tokens: rate_limit.burst() as f64,
+            last: now,
+        });
+
+        bucket.take(&rate_limit, now)
+    }
+
+    /// Drops the buckets that have refilled completely, they are indistinguishable from new ones.
+    pub(crate) fn purge(&self) {
+        let now = Instant::now();
+
+        self.buckets.retain(|(_, limit), bucket| {
+            let rate_limit = *self.rate_limit(*limit);
+
+            bucket.refill(&rate_limit, now);
+            bucket.tokens < rate_limit.burst() as f64
+        });
+    }
+
+    fn check(&self, method: &Method, path: &str, remote: Option<SocketAddr>) -> Result<(), Rejection> {
+        let remote = match remote {
+            Some(remote) => remote,
+            None => return Ok(()),
+        };
+
+        self.acquire(remote.ip(), Limit::of(method, path))
+            .map_err(|retry_after| reject::custom(CustomRejection::TooManyRequests(retry_after_secs(retry_after))))
+    }
+}
+
+/// Rejects the requests exceeding the quota of their client for the route they target.
+pub(crate) fn guard(rate_limiter: RateLimiter) -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    warp::method()
+        .and(warp::path::full())
+        .and(filters::remote())
+        .and_then(move |method: Method, path: FullPath, remote: Option<SocketAddr>| {
+            let result = rate_limiter.check(&method, path.as_str(), remote);
+            async move { result }
+        })
+        .untuple_one()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn refill() {
+        let limit = RateLimit::new(2.0, 3);
+        let start = Instant::now();
+        let mut bucket = Bucket {
+            tokens: 0.0,
+            last: start,
+        };
+
+        bucket.refill(&limit, start + Duration::from_millis(500));
+        assert!((bucket.tokens - 1.0).abs() < f64::EPSILON);
+
+        bucket.refill(&limit, start + Duration::from_secs(10));
+        assert!((bucket.tokens - 3.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn take_until_empty() {
+        let limit = RateLimit::new(4.0, 2);
+        let now = Instant::now();
+        let mut bucket = Bucket { tokens: 2.0, last: now };
+
+        assert_eq!(bucket.take(&limit, now), Ok(()));
+        assert_eq!(bucket.take(&limit, now), Ok(()));
+        assert_eq!(bucket.take(&limit, now), Err(Duration::from_millis(250)));
+        assert_eq!(bucket.take(&limit, now + Duration::from_millis(250)), Ok(()));
+    }
+
+    #[test]
+    fn retry_after_rounds_up() {
+        assert_eq!(retry_after_secs(Duration::from_millis(250)), 1);
+        assert_eq!(retry_after_secs(Duration::from_secs(2)), 2);
+        assert_eq!(retry_after_secs(Duration::from_millis(2001)), 3);
+    }
+
+    #[test]
+    fn retry_after_is_capped() {
+        let limit = RateLimit::new(1e-9, 1);
+        let now = Instant::now();
+        let mut bucket = Bucket { tokens: 0.0, last: now };
+
+        assert_eq!(
+            bucket.take(&limit, now),
+            Err(Duration::from_secs_f64(MAX_RETRY_AFTER_SECS))
+        );
+    }
+}
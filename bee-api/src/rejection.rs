@@ -0,0 +1,171 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use serde::Serialize;
+use warp::{
+    http::{header::RETRY_AFTER, HeaderValue, StatusCode},
+    reject::Reject,
+    Rejection, Reply,
+};
+
+use std::convert::Infallible;
+
+#[derive(Debug)]
+pub(crate) enum CustomRejection {
+    BadRequest(String),
+    Unauthorized(String),
+    Forbidden(String),
+    NotFound(String),
+    /// Carries the number of seconds after which the request may be retried.
+    TooManyRequests(u64),
+    ServiceUnavailable(String),
+    InternalError,
+}
+
+impl Reject for CustomRejection {}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: ErrorBody,
+}
+
+#[derive(Serialize)]
+struct ErrorBody {
+    code: String,
+    message: String,
+}
+
+pub(crate) async fn handle_rejection(err: Rejection) -> Result<impl Reply, Infallible> {
+    let (status, message) = if err.is_not_found() {
+        (StatusCode::NOT_FOUND, "Not found.".to_string())
+    } else if let Some(rejection) = err.find::<CustomRejection>() {
+        match rejection {
+            CustomRejection::BadRequest(message) => (StatusCode::BAD_REQUEST, message.clone()),
+            CustomRejection::Unauthorized(message) => (StatusCode::UNAUTHORIZED, message.clone()),
+            CustomRejection::Forbidden(message) => (StatusCode::FORBIDDEN, message.clone()),
+            CustomRejection::NotFound(message) => (StatusCode::NOT_FOUND, message.clone()),
+            CustomRejection::TooManyRequests(_) => (StatusCode::TOO_MANY_REQUESTS, "Too many requests.".to_string()),
+            CustomRejection::ServiceUnavailable(message) => (StatusCode::SERVICE_UNAVAILABLE, message.clone()),
+            CustomRejection::InternalError => (StatusCode::INTERNAL_SERVER_ERROR, "Internal error.".to_string()),
+        }
+    } else if err.find::<warp::cors::CorsForbidden>().is_some() {
+        (StatusCode::FORBIDDEN, "Cross-origin request not allowed.".to_string())
+    } else if err.find::<warp::reject::MethodNotAllowed>().is_some() {
+        (StatusCode::METHOD_NOT_ALLOWED, "Method not allowed.".to_string())
+    } else if err.find::<warp::reject::PayloadTooLarge>().is_some() {
+        (StatusCode::PAYLOAD_TOO_LARGE, "Payload too large.".to_string())
+    } else if let Some(e) = err.find::<warp::body::BodyDeserializeError>() {
+        (StatusCode::BAD_REQUEST, format!("{}.", e))
+    } else if err.find::<warp::reject::InvalidQuery>().is_some() {
+        (StatusCode::BAD_REQUEST, "Invalid query string.".to_string())
+    } else if let Some(e) = err.find::<warp::reject::MissingHeader>() {
+        (StatusCode::BAD_REQUEST, format!("{}.", e))
+    } else if let Some(e) = err.find::<warp::reject::InvalidHeader>() {
+        (StatusCode::BAD_REQUEST, format!("{}.", e))
+    } else if err.find::<warp::reject::UnsupportedMediaType>().is_some() {
+        (
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "Unsupported media type.".to_string(),
+        )
+    } else {
+        (StatusCode::INTERNAL_SERVER_ERROR, "Unhandled rejection.".to_string())
+    };
+
+    let mut response = warp::reply::with_status(
+        warp::reply::json(&ErrorResponse {
+            error: ErrorBody {
+                code: status.as_u16().to_string(),
+                message,
+            },
+        }),
+        status,
+    )
+    .into_response();
+
+    if let Some(CustomRejection::TooManyRequests(retry_after)) = err.find::<CustomRejection>() {
+        response
+            .headers_mut()
+            .insert(RETRY_AFTER, HeaderValue::from(*retry_after));
+    }
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use bytes::Bytes;
+    use serde::Deserialize;
+    use warp::{http::Response, Filter};
+
+    #[derive(Deserialize)]
+    struct Query {
+        #[allow(dead_code)]
+        index: u32,
+    }
+
+    fn assert_error(response: Response<Bytes>, status: StatusCode) {
+        let body = serde_json::from_slice::<serde_json::Value>(response.body()).unwrap();
+
+        assert_eq!(response.status(), status);
+        assert_eq!(body["error"]["code"], status.as_u16().to_string());
+    }
+
+    #[tokio::test]
+    async fn malformed_body() {
+        let routes = warp::body::json::<serde_json::Value>()
+            .map(|_| warp::reply())
+            .recover(handle_rejection);
+        let response = warp::test::request()
+            .method("POST")
+            .header("content-type", "application/json")
+            .body("{")
+            .reply(&routes)
+            .await;
+
+        assert_error(response, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn invalid_query() {
+        let routes = warp::query::<Query>().map(|_| warp::reply()).recover(handle_rejection);
+        let response = warp::test::request().path("/?index=abc").reply(&routes).await;
+
+        assert_error(response, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn unsupported_media_type() {
+        let routes = warp::body::json::<serde_json::Value>()
+            .map(|_| warp::reply())
+            .recover(handle_rejection);
+        let response = warp::test::request()
+            .method("POST")
+            .header("content-type", "text/plain")
+            .body("{}")
+            .reply(&routes)
+            .await;
+
+        assert_error(response, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
+    #[tokio::test]
+    async fn missing_header() {
+        let routes = warp::header::<String>("x-test")
+            .map(|_| warp::reply())
+            .recover(handle_rejection);
+        let response = warp::test::request().reply(&routes).await;
+
+        assert_error(response, StatusCode::BAD_REQUEST);
+    }
+}
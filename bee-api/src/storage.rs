@@ -0,0 +1,56 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use bee_ledger::{diff::Diff, output::Output, spent::Spent, unspent::Unspent};
+use bee_message::{
+    payload::{
+        indexation::HashedIndex,
+        transaction::{Ed25519Address, OutputId},
+    },
+    Message, MessageId,
+};
+use bee_protocol::{storage::Backend as ProtocolBackend, tangle::MessageMetadata, MilestoneIndex};
+use bee_storage::{
+    access::{Exist, Fetch},
+    storage,
+};
+
+pub trait Backend:
+    storage::Backend
+    + ProtocolBackend
+    + Exist<Unspent, ()>
+    + Fetch<MessageId, Message>
+    + Fetch<MessageId, MessageMetadata>
+    + Fetch<MessageId, Vec<MessageId>>
+    + Fetch<(HashedIndex, Option<MessageId>), (Vec<MessageId>, bool)>
+    + Fetch<OutputId, Output>
+    + Fetch<OutputId, Spent>
+    + Fetch<(Ed25519Address, Option<OutputId>), (Vec<OutputId>, bool)>
+    + Fetch<MilestoneIndex, MessageId>
+    + Fetch<MilestoneIndex, Diff>
+{
+}
+
+impl<T> Backend for T where
+    T: storage::Backend
+        + ProtocolBackend
+        + Exist<Unspent, ()>
+        + Fetch<MessageId, Message>
+        + Fetch<MessageId, MessageMetadata>
+        + Fetch<MessageId, Vec<MessageId>>
+        + Fetch<(HashedIndex, Option<MessageId>), (Vec<MessageId>, bool)>
+        + Fetch<OutputId, Output>
+        + Fetch<OutputId, Spent>
+        + Fetch<(Ed25519Address, Option<OutputId>), (Vec<OutputId>, bool)>
+        + Fetch<MilestoneIndex, MessageId>
+        + Fetch<MilestoneIndex, Diff>
+{
+}
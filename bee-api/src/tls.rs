@@ -0,0 +1,302 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+//! TLS termination for the API.
+//!
+//! The certificate chain and private key are read from PEM files and reloaded when the files change, so that renewed
+//! certificates are picked up without restarting the node. Connections established before a reload keep their session.
+
+use crate::config::ApiTlsConfig;
+
+use futures::{
+    future::{self, Future},
+    StreamExt,
+};
+use hyper::{
+    server::accept,
+    service::{make_service_fn, service_fn, Service},
+};
+use log::{debug, error, info, warn};
+use thiserror::Error;
+use tokio::{
+    net::{TcpListener, TcpStream},
+    time::{delay_for, timeout},
+};
+use tokio_rustls::{
+    rustls::{
+        internal::pemfile::{certs, pkcs8_private_keys, rsa_private_keys},
+        NoClientAuth, ServerConfig, TLSError,
+    },
+    server::TlsStream,
+    TlsAcceptor,
+};
+use warp::{Filter, Reply};
+
+use std::{
+    convert::Infallible,
+    fs::{self, File},
+    io::{self, BufReader},
+    net::SocketAddr,
+    path::Path,
+    sync::{Arc, RwLock},
+    time::{Duration, SystemTime},
+};
+
+const TLS_HANDSHAKE_TIMEOUT_SEC: u64 = 10;
+const ACCEPT_ERROR_DELAY_MILLIS: u64 = 100;
+
+#[derive(Debug, Error)]
+pub(crate) enum Error {
+    #[error("Reading {0} failed: {1}.")]
+    Io(String, io::Error),
+    #[error("No valid certificate found in {0}.")]
+    InvalidCertificate(String),
+    #[error("No valid private key found in {0}.")]
+    InvalidPrivateKey(String),
+    #[error("Invalid certificate or private key: {0}.")]
+    Tls(#[from] TLSError),
+}
+
+/// Remote address of a TLS connection, attached to its requests since warp only knows it for plain connections.
+#[derive(Clone, Copy)]
+pub(crate) struct RemoteAddress(pub(crate) SocketAddr);
+
+fn open(path: &Path) -> Result<BufReader<File>, Error> {
+    File::open(path)
+        .map(BufReader::new)
+        .map_err(|e| Error::Io(path.display().to_string(), e))
+}
+
+fn load(config: &ApiTlsConfig) -> Result<ServerConfig, Error> {
+    let cert_path = config.cert_path();
+    let key_path = config.key_path();
+
+    let cert_chain = certs(&mut open(cert_path)?)
+        .ok()
+        .filter(|certs| !certs.is_empty())
+        .ok_or_else(|| Error::InvalidCertificate(cert_path.display().to_string()))?;
+    // Keys may either be PKCS#8 or PKCS#1 (RSA) encoded.
+    let key = pkcs8_private_keys(&mut open(key_path)?)
+        .ok()
+        .filter(|keys| !keys.is_empty())
+        .or_else(|| rsa_private_keys(&mut open(key_path).ok()?).ok())
+        .and_then(|keys| keys.into_iter().next())
+        .ok_or_else(|| Error::InvalidPrivateKey(key_path.display().to_string()))?;
+
+    let mut server_config = ServerConfig::new(NoClientAuth::new());
+    server_config.set_single_cert(cert_chain, key)?;
+    server_config.set_protocols(&[b"http/1.1".to_vec()]);
+
+    Ok(server_config)
+}
+
+fn modified(config: &ApiTlsConfig) -> (Option<SystemTime>, Option<SystemTime>) {
+    let modified = |path: &Path| fs::metadata(path).and_then(|metadata| metadata.modified()).ok();
+
+    (modified(config.cert_path()), modified(config.key_path()))
+}
+
+struct State {
+    server_config: Arc<ServerConfig>,
+    modified: (Option<SystemTime>, Option<SystemTime>),
+}
+
+#[derive(Clone)]
+pub(crate) struct Tls {
+    config: ApiTlsConfig,
+    state: Arc<RwLock<State>>,
+}
+
+impl Tls {
+    pub(crate) fn new(config: ApiTlsConfig) -> Result<Self, Error> {
+        let modified = modified(&config);
+        let server_config = Arc::new(load(&config)?);
+
+        Ok(Self {
+            config,
+            state: Arc::new(RwLock::new(State {
+                server_config,
+                modified,
+            })),
+        })
+    }
+
+    fn acceptor(&self) -> TlsAcceptor {
+        TlsAcceptor::from(self.state.read().unwrap().server_config.clone())
+    }
+
+    /// Reloads the certificate and key if any of their files changed, keeping the current ones if the new ones are
+    /// invalid, e.g. because only one of the files has been replaced yet.
+    pub(crate) fn reload_if_modified(&self) {
+        let modified = modified(&self.config);
+
+        if modified == self.state.read().unwrap().modified {
+            return;
+        }
+
+        match load(&self.config) {
+            Ok(server_config) => {
+                *self.state.write().unwrap() = State {
+                    server_config: Arc::new(server_config),
+                    modified,
+                };
+                info!("Reloaded the TLS certificate.");
+            }
+            Err(e) => warn!("Reloading the TLS certificate failed: {}", e),
+        }
+    }
+}
+
+/// Serves `routes` over TLS on `bind_address` until `shutdown` completes.
+pub(crate) async fn serve<F>(routes: F, bind_address: SocketAddr, tls: Tls, shutdown: impl Future<Output = ()>)
+where
+    F: Filter<Error = Infallible> + Clone + Send + Sync + 'static,
+    F::Extract: Reply,
+{
+    match TcpListener::bind(bind_address).await {
+        Ok(listener) => serve_listener(routes, listener, tls, shutdown).await,
+        Err(e) => error!("Binding to {} failed: {}.", bind_address, e),
+    }
+}
+
+async fn serve_listener<F>(routes: F, mut listener: TcpListener, tls: Tls, shutdown: impl Future<Output = ()>)
+where
+    F: Filter<Error = Infallible> + Clone + Send + Sync + 'static,
+    F::Extract: Reply,
+{
+    let local_address = listener.local_addr();
+    // Handshakes are done concurrently so that a slow client can't hold the others back, the established
+    // connections are then handed over to the server.
+    let (tx, rx) = flume::unbounded::<TlsStream<TcpStream>>();
+
+    let accepting = async move {
+        loop {
+            let (stream, remote) = match listener.accept().await {
+                Ok(connection) => connection,
+                Err(e) => {
+                    debug!("Accepting a connection failed: {}.", e);
+                    delay_for(Duration::from_millis(ACCEPT_ERROR_DELAY_MILLIS)).await;
+                    continue;
+                }
+            };
+            let acceptor = tls.acceptor();
+            let tx = tx.clone();
+
+            tokio::spawn(async move {
+                match timeout(Duration::from_secs(TLS_HANDSHAKE_TIMEOUT_SEC), acceptor.accept(stream)).await {
+                    Ok(Ok(stream)) => {
+                        let _ = tx.send(stream);
+                    }
+                    Ok(Err(e)) => debug!("TLS handshake with {} failed: {}.", remote, e),
+                    Err(_) => debug!("TLS handshake with {} timed out.", remote),
+                }
+            });
+        }
+    };
+
+    let service = warp::service(routes);
+    let make_service = make_service_fn(move |stream: &TlsStream<TcpStream>| {
+        let remote = stream.get_ref().0.peer_addr().ok();
+        let service = service.clone();
+
+        async move {
+            Ok::<_, Infallible>(service_fn(move |mut request: hyper::Request<hyper::Body>| {
+                if let Some(remote) = remote {
+                    request.extensions_mut().insert(RemoteAddress(remote));
+                }
+                service.clone().call(request)
+            }))
+        }
+    });
+    let serving = hyper::Server::builder(accept::from_stream(rx.into_stream().map(Ok::<_, io::Error>)))
+        .serve(make_service)
+        .with_graceful_shutdown(shutdown);
+
+    if let Ok(local_address) = local_address {
+        info!("Listening on {} with TLS.", local_address);
+    }
+
+    // Accepting only ends with the server since it holds the listener.
+    if let future::Either::Left((Err(e), _)) = future::select(Box::pin(serving), Box::pin(accepting)).await {
+        error!("Serving failed: {}.", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use crate::config::ApiConfigBuilder;
+
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        sync::oneshot,
+    };
+    use tokio_rustls::{rustls::ClientConfig, webpki::DNSNameRef, TlsConnector};
+
+    use std::{env, process};
+
+    #[tokio::test]
+    async fn serve_with_self_signed_certificate() {
+        let dir = env::temp_dir().join(format!("bee-api-tls-{}", process::id()));
+        let (cert_path, key_path) = (dir.join("cert.pem"), dir.join("key.pem"));
+        let certificate = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&cert_path, certificate.serialize_pem().unwrap()).unwrap();
+        fs::write(&key_path, certificate.serialize_private_key_pem()).unwrap();
+
+        let config = ApiConfigBuilder::new()
+            .tls(cert_path.clone(), key_path)
+            .finish()
+            .unwrap();
+        let tls = Tls::new(config.tls().unwrap().clone()).unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        // Replies whether the remote address of the connection reached the routes.
+        let routes = warp::ext::optional::<RemoteAddress>().map(|remote: Option<RemoteAddress>| match remote {
+            Some(RemoteAddress(remote)) if remote.ip().is_loopback() => "remote",
+            _ => "unknown",
+        });
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let server = tokio::spawn(serve_listener(routes, listener, tls, async move {
+            let _ = shutdown_rx.await;
+        }));
+
+        let mut client_config = ClientConfig::new();
+        client_config
+            .root_store
+            .add_pem_file(&mut open(&cert_path).unwrap())
+            .unwrap();
+        let stream = TcpStream::connect(address).await.unwrap();
+        let mut stream = TlsConnector::from(Arc::new(client_config))
+            .connect(DNSNameRef::try_from_ascii_str("localhost").unwrap(), stream)
+            .await
+            .unwrap();
+        stream
+            .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = Vec::new();
+        // The server may close the connection without a TLS close notification, the response is complete either way.
+        let _ = stream.read_to_end(&mut response).await;
+        let response = String::from_utf8(response).unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.ends_with("remote"));
+
+        drop(stream);
+        shutdown_tx.send(()).unwrap();
+        server.await.unwrap();
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
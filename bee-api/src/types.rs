@@ -0,0 +1,633 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use bee_message::{payload::milestone::MilestoneEssence, prelude::*};
+
+use serde::{
+    de::{self, DeserializeOwned},
+    Deserialize, Deserializer, Serialize,
+};
+use serde_json::Value;
+
+use std::{
+    convert::{TryFrom, TryInto},
+    num::NonZeroU64,
+};
+
+/// Envelope of all successful responses.
+#[derive(Serialize)]
+pub(crate) struct DataResponse<T> {
+    data: T,
+}
+
+impl<T> DataResponse<T> {
+    pub(crate) fn new(data: T) -> Self {
+        Self { data }
+    }
+}
+
+// The DTOs below mirror the types of `bee-message` with hex encoded identifiers and a numerical `type` field
+// identifying the kind of each payload, input, output, address, unlock block and signature.
+
+fn kind<E: de::Error>(value: &Value) -> Result<u64, E> {
+    value
+        .get("type")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| E::custom("missing or invalid type"))
+}
+
+fn from_value<T: DeserializeOwned, E: de::Error>(value: Value) -> Result<T, E> {
+    serde_json::from_value(value).map_err(E::custom)
+}
+
+fn hex_decode(value: &str) -> Result<Vec<u8>, String> {
+    hex::decode(value).map_err(|_| format!("invalid hex value {}", value))
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MessageDto {
+    // Serialized as a string since JSON consumers usually can't represent a `u64` without loss.
+    pub(crate) network_id: String,
+    pub(crate) parent1_message_id: String,
+    pub(crate) parent2_message_id: String,
+    pub(crate) payload: Option<PayloadDto>,
+    pub(crate) nonce: String,
+}
+
+impl TryFrom<&Message> for MessageDto {
+    type Error = String;
+
+    fn try_from(message: &Message) -> Result<Self, Self::Error> {
+        Ok(MessageDto {
+            network_id: message.network_id().to_string(),
+            parent1_message_id: message.parent1().to_string(),
+            parent2_message_id: message.parent2().to_string(),
+            payload: message.payload().as_ref().map(TryInto::try_into).transpose()?,
+            nonce: message.nonce().to_string(),
+        })
+    }
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub(crate) enum PayloadDto {
+    Transaction(Box<TransactionPayloadDto>),
+    Milestone(Box<MilestonePayloadDto>),
+    Indexation(Box<IndexationPayloadDto>),
+}
+
+impl TryFrom<&Payload> for PayloadDto {
+    type Error = String;
+
+    fn try_from(payload: &Payload) -> Result<Self, Self::Error> {
+        match payload {
+            Payload::Transaction(transaction) => Ok(PayloadDto::Transaction(Box::new(
+                TransactionPayloadDto::try_from(transaction.as_ref())?,
+            ))),
+            Payload::Milestone(milestone) => Ok(PayloadDto::Milestone(Box::new(MilestonePayloadDto::from(
+                milestone.as_ref(),
+            )))),
+            Payload::Indexation(indexation) => Ok(PayloadDto::Indexation(Box::new(IndexationPayloadDto::from(
+                indexation.as_ref(),
+            )))),
+            _ => Err("unsupported payload kind".to_string()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for PayloadDto {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+
+        match kind(&value)? {
+            0 => Ok(PayloadDto::Transaction(Box::new(from_value(value)?))),
+            1 => Ok(PayloadDto::Milestone(Box::new(from_value(value)?))),
+            2 => Ok(PayloadDto::Indexation(Box::new(from_value(value)?))),
+            kind => Err(de::Error::custom(format!("invalid payload type {}", kind))),
+        }
+    }
+}
+
+impl TryFrom<&PayloadDto> for Payload {
+    type Error = String;
+
+    fn try_from(payload: &PayloadDto) -> Result<Self, Self::Error> {
+        match payload {
+            PayloadDto::Transaction(transaction) => Ok(Payload::Transaction(Box::new(Transaction::try_from(
+                transaction.as_ref(),
+            )?))),
+            PayloadDto::Milestone(milestone) => {
+                Ok(Payload::Milestone(Box::new(Milestone::try_from(milestone.as_ref())?)))
+            }
+            PayloadDto::Indexation(indexation) => Ok(Payload::Indexation(Box::new(Indexation::try_from(
+                indexation.as_ref(),
+            )?))),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TransactionPayloadDto {
+    #[serde(rename = "type")]
+    pub(crate) kind: u32,
+    pub(crate) essence: TransactionEssenceDto,
+    pub(crate) unlock_blocks: Vec<UnlockBlockDto>,
+}
+
+impl TryFrom<&Transaction> for TransactionPayloadDto {
+    type Error = String;
+
+    fn try_from(transaction: &Transaction) -> Result<Self, Self::Error> {
+        Ok(TransactionPayloadDto {
+            kind: 0,
+            essence: transaction.essence().try_into()?,
+            unlock_blocks: transaction
+                .unlock_blocks()
+                .iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl TryFrom<&TransactionPayloadDto> for Transaction {
+    type Error = String;
+
+    fn try_from(transaction: &TransactionPayloadDto) -> Result<Self, Self::Error> {
+        let mut builder = Transaction::builder().with_essence((&transaction.essence).try_into()?);
+
+        for unlock_block in &transaction.unlock_blocks {
+            builder = builder.add_unlock_block(unlock_block.try_into()?);
+        }
+
+        builder.finish().map_err(|e| e.to_string())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct TransactionEssenceDto {
+    #[serde(rename = "type")]
+    pub(crate) kind: u8,
+    pub(crate) inputs: Vec<InputDto>,
+    pub(crate) outputs: Vec<OutputDto>,
+    pub(crate) payload: Option<PayloadDto>,
+}
+
+impl TryFrom<&TransactionEssence> for TransactionEssenceDto {
+    type Error = String;
+
+    fn try_from(essence: &TransactionEssence) -> Result<Self, Self::Error> {
+        Ok(TransactionEssenceDto {
+            kind: 0,
+            inputs: essence
+                .inputs()
+                .iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            outputs: essence
+                .outputs()
+                .iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            payload: essence.payload().as_ref().map(TryInto::try_into).transpose()?,
+        })
+    }
+}
+
+impl TryFrom<&TransactionEssenceDto> for TransactionEssence {
+    type Error = String;
+
+    fn try_from(essence: &TransactionEssenceDto) -> Result<Self, Self::Error> {
+        let mut builder = TransactionEssence::builder();
+
+        for input in &essence.inputs {
+            builder = builder.add_input(input.try_into()?);
+        }
+
+        for output in &essence.outputs {
+            builder = builder.add_output(output.try_into()?);
+        }
+
+        if let Some(payload) = &essence.payload {
+            builder = builder.with_payload(payload.try_into()?);
+        }
+
+        builder.finish().map_err(|e| e.to_string())
+    }
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub(crate) enum InputDto {
+    UTXO(UtxoInputDto),
+}
+
+impl TryFrom<&Input> for InputDto {
+    type Error = String;
+
+    fn try_from(input: &Input) -> Result<Self, Self::Error> {
+        match input {
+            Input::UTXO(input) => Ok(InputDto::UTXO(UtxoInputDto {
+                kind: 0,
+                transaction_id: input.output_id().transaction_id().to_string(),
+                transaction_output_index: input.output_id().index(),
+            })),
+            _ => Err("unsupported input kind".to_string()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for InputDto {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+
+        match kind(&value)? {
+            0 => Ok(InputDto::UTXO(from_value(value)?)),
+            kind => Err(de::Error::custom(format!("invalid input type {}", kind))),
+        }
+    }
+}
+
+impl TryFrom<&InputDto> for Input {
+    type Error = String;
+
+    fn try_from(input: &InputDto) -> Result<Self, Self::Error> {
+        match input {
+            InputDto::UTXO(input) => Ok(UTXOInput::new(
+                input
+                    .transaction_id
+                    .parse::<TransactionId>()
+                    .map_err(|e| e.to_string())?,
+                input.transaction_output_index,
+            )
+            .map_err(|e| e.to_string())?
+            .into()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct UtxoInputDto {
+    #[serde(rename = "type")]
+    pub(crate) kind: u8,
+    pub(crate) transaction_id: String,
+    pub(crate) transaction_output_index: u16,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub(crate) enum OutputDto {
+    SignatureLockedSingle(SignatureLockedSingleOutputDto),
+}
+
+impl TryFrom<&Output> for OutputDto {
+    type Error = String;
+
+    fn try_from(output: &Output) -> Result<Self, Self::Error> {
+        match output {
+            Output::SignatureLockedSingle(output) => {
+                Ok(OutputDto::SignatureLockedSingle(SignatureLockedSingleOutputDto {
+                    kind: 0,
+                    address: output.address().try_into()?,
+                    amount: output.amount().get(),
+                }))
+            }
+            _ => Err("unsupported output kind".to_string()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for OutputDto {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+
+        match kind(&value)? {
+            0 => Ok(OutputDto::SignatureLockedSingle(from_value(value)?)),
+            kind => Err(de::Error::custom(format!("invalid output type {}", kind))),
+        }
+    }
+}
+
+impl TryFrom<&OutputDto> for Output {
+    type Error = String;
+
+    fn try_from(output: &OutputDto) -> Result<Self, Self::Error> {
+        match output {
+            OutputDto::SignatureLockedSingle(output) => Ok(SignatureLockedSingleOutput::new(
+                (&output.address).try_into()?,
+                NonZeroU64::new(output.amount).ok_or_else(|| "invalid amount 0".to_string())?,
+            )
+            .into()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct SignatureLockedSingleOutputDto {
+    #[serde(rename = "type")]
+    pub(crate) kind: u8,
+    pub(crate) address: AddressDto,
+    pub(crate) amount: u64,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub(crate) enum AddressDto {
+    Wots(WotsAddressDto),
+    Ed25519(Ed25519AddressDto),
+}
+
+impl TryFrom<&Address> for AddressDto {
+    type Error = String;
+
+    fn try_from(address: &Address) -> Result<Self, Self::Error> {
+        match address {
+            Address::Wots(address) => Ok(AddressDto::Wots(WotsAddressDto {
+                kind: 0,
+                address: hex::encode(address),
+            })),
+            Address::Ed25519(address) => Ok(AddressDto::Ed25519(Ed25519AddressDto {
+                kind: 1,
+                address: address.to_string(),
+            })),
+            _ => Err("unsupported address kind".to_string()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for AddressDto {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+
+        match kind(&value)? {
+            0 => Ok(AddressDto::Wots(from_value(value)?)),
+            1 => Ok(AddressDto::Ed25519(from_value(value)?)),
+            kind => Err(de::Error::custom(format!("invalid address type {}", kind))),
+        }
+    }
+}
+
+impl TryFrom<&AddressDto> for Address {
+    type Error = String;
+
+    fn try_from(address: &AddressDto) -> Result<Self, Self::Error> {
+        match address {
+            AddressDto::Wots(_) => Err("unsupported WOTS address".to_string()),
+            AddressDto::Ed25519(address) => Ok(address
+                .address
+                .parse::<Ed25519Address>()
+                .map_err(|e| e.to_string())?
+                .into()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct WotsAddressDto {
+    #[serde(rename = "type")]
+    pub(crate) kind: u8,
+    pub(crate) address: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct Ed25519AddressDto {
+    #[serde(rename = "type")]
+    pub(crate) kind: u8,
+    pub(crate) address: String,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub(crate) enum UnlockBlockDto {
+    Signature(SignatureUnlockDto),
+    Reference(ReferenceUnlockDto),
+}
+
+impl TryFrom<&UnlockBlock> for UnlockBlockDto {
+    type Error = String;
+
+    fn try_from(unlock_block: &UnlockBlock) -> Result<Self, Self::Error> {
+        match unlock_block {
+            UnlockBlock::Signature(signature) => Ok(UnlockBlockDto::Signature(SignatureUnlockDto {
+                kind: 0,
+                signature: signature.try_into()?,
+            })),
+            UnlockBlock::Reference(reference) => Ok(UnlockBlockDto::Reference(ReferenceUnlockDto {
+                kind: 1,
+                reference: reference.index(),
+            })),
+            _ => Err("unsupported unlock block kind".to_string()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for UnlockBlockDto {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+
+        match kind(&value)? {
+            0 => Ok(UnlockBlockDto::Signature(from_value(value)?)),
+            1 => Ok(UnlockBlockDto::Reference(from_value(value)?)),
+            kind => Err(de::Error::custom(format!("invalid unlock block type {}", kind))),
+        }
+    }
+}
+
+impl TryFrom<&UnlockBlockDto> for UnlockBlock {
+    type Error = String;
+
+    fn try_from(unlock_block: &UnlockBlockDto) -> Result<Self, Self::Error> {
+        match unlock_block {
+            UnlockBlockDto::Signature(signature) => Ok(UnlockBlock::Signature((&signature.signature).try_into()?)),
+            UnlockBlockDto::Reference(reference) => Ok(ReferenceUnlock::new(reference.reference)
+                .map_err(|e| e.to_string())?
+                .into()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct SignatureUnlockDto {
+    #[serde(rename = "type")]
+    pub(crate) kind: u8,
+    pub(crate) signature: SignatureDto,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct ReferenceUnlockDto {
+    #[serde(rename = "type")]
+    pub(crate) kind: u8,
+    pub(crate) reference: u16,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub(crate) enum SignatureDto {
+    Wots(WotsSignatureDto),
+    Ed25519(Ed25519SignatureDto),
+}
+
+impl TryFrom<&SignatureUnlock> for SignatureDto {
+    type Error = String;
+
+    fn try_from(signature: &SignatureUnlock) -> Result<Self, Self::Error> {
+        match signature {
+            SignatureUnlock::Wots(signature) => Ok(SignatureDto::Wots(WotsSignatureDto {
+                kind: 0,
+                signature: hex::encode(signature),
+            })),
+            SignatureUnlock::Ed25519(signature) => Ok(SignatureDto::Ed25519(Ed25519SignatureDto {
+                kind: 1,
+                public_key: hex::encode(signature.public_key()),
+                signature: hex::encode(signature.signature()),
+            })),
+            _ => Err("unsupported signature kind".to_string()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for SignatureDto {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+
+        match kind(&value)? {
+            0 => Ok(SignatureDto::Wots(from_value(value)?)),
+            1 => Ok(SignatureDto::Ed25519(from_value(value)?)),
+            kind => Err(de::Error::custom(format!("invalid signature type {}", kind))),
+        }
+    }
+}
+
+impl TryFrom<&SignatureDto> for SignatureUnlock {
+    type Error = String;
+
+    fn try_from(signature: &SignatureDto) -> Result<Self, Self::Error> {
+        match signature {
+            SignatureDto::Wots(_) => Err("unsupported WOTS signature".to_string()),
+            SignatureDto::Ed25519(signature) => {
+                let public_key = hex_decode(&signature.public_key)?
+                    .as_slice()
+                    .try_into()
+                    .map_err(|_| format!("invalid public key {}", signature.public_key))?;
+
+                Ok(Ed25519Signature::new(public_key, hex_decode(&signature.signature)?.into_boxed_slice()).into())
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct WotsSignatureDto {
+    #[serde(rename = "type")]
+    pub(crate) kind: u8,
+    pub(crate) signature: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Ed25519SignatureDto {
+    #[serde(rename = "type")]
+    pub(crate) kind: u8,
+    pub(crate) public_key: String,
+    pub(crate) signature: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MilestonePayloadDto {
+    #[serde(rename = "type")]
+    pub(crate) kind: u32,
+    pub(crate) index: u32,
+    pub(crate) timestamp: u64,
+    pub(crate) parent1_message_id: String,
+    pub(crate) parent2_message_id: String,
+    pub(crate) inclusion_merkle_proof: String,
+    pub(crate) public_keys: Vec<String>,
+    pub(crate) signatures: Vec<String>,
+}
+
+impl From<&Milestone> for MilestonePayloadDto {
+    fn from(milestone: &Milestone) -> Self {
+        MilestonePayloadDto {
+            kind: 1,
+            index: milestone.essence().index(),
+            timestamp: milestone.essence().timestamp(),
+            parent1_message_id: milestone.essence().parent1().to_string(),
+            parent2_message_id: milestone.essence().parent2().to_string(),
+            inclusion_merkle_proof: hex::encode(milestone.essence().merkle_proof()),
+            public_keys: milestone.essence().public_keys().iter().map(hex::encode).collect(),
+            signatures: milestone.signatures().iter().map(hex::encode).collect(),
+        }
+    }
+}
+
+impl TryFrom<&MilestonePayloadDto> for Milestone {
+    type Error = String;
+
+    fn try_from(milestone: &MilestonePayloadDto) -> Result<Self, Self::Error> {
+        let public_keys = milestone
+            .public_keys
+            .iter()
+            .map(|public_key| {
+                hex_decode(public_key)?
+                    .as_slice()
+                    .try_into()
+                    .map_err(|_| format!("invalid public key {}", public_key))
+            })
+            .collect::<Result<_, _>>()?;
+        let signatures = milestone
+            .signatures
+            .iter()
+            .map(|signature| Ok(hex_decode(signature)?.into_boxed_slice()))
+            .collect::<Result<_, String>>()?;
+
+        Ok(Milestone::new(
+            MilestoneEssence::new(
+                milestone.index,
+                milestone.timestamp,
+                milestone.parent1_message_id.parse().map_err(|e: Error| e.to_string())?,
+                milestone.parent2_message_id.parse().map_err(|e: Error| e.to_string())?,
+                hex_decode(&milestone.inclusion_merkle_proof)?.into_boxed_slice(),
+                public_keys,
+            ),
+            signatures,
+        ))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct IndexationPayloadDto {
+    #[serde(rename = "type")]
+    pub(crate) kind: u32,
+    pub(crate) index: String,
+    pub(crate) data: String,
+}
+
+impl From<&Indexation> for IndexationPayloadDto {
+    fn from(indexation: &Indexation) -> Self {
+        IndexationPayloadDto {
+            kind: 2,
+            index: indexation.index().clone(),
+            data: hex::encode(indexation.data()),
+        }
+    }
+}
+
+impl TryFrom<&IndexationPayloadDto> for Indexation {
+    type Error = String;
+
+    fn try_from(indexation: &IndexationPayloadDto) -> Result<Self, Self::Error> {
+        Indexation::new(indexation.index.clone(), &hex_decode(&indexation.data)?).map_err(|e| e.to_string())
+    }
+}
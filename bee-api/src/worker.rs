@@ -0,0 +1,174 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::{
+    auth::Auth,
+    config::ApiConfig,
+    cors,
+    events::{self, Broker},
+    filters,
+    rate_limit::RateLimiter,
+    rejection::handle_rejection,
+    storage::Backend,
+    tls::{self, Tls},
+};
+
+use bee_common::shutdown_stream::ShutdownStream;
+use bee_common_ext::{node::Node, worker::Worker};
+use bee_network::{Keypair, Network};
+use bee_peering::ManualPeers;
+use bee_protocol::{
+    config::ProtocolConfig, tangle::MsTangle, MessageSubmitterWorker, Protocol, StorageWorker, TangleWorker,
+};
+
+use async_trait::async_trait;
+use futures::{channel::oneshot, StreamExt};
+use log::{error, info};
+use tokio::time::interval;
+use warp::{Filter, Reply};
+
+use std::{any::TypeId, convert::Infallible, net::SocketAddr, sync::Arc, time::Duration};
+
+const RATE_LIMITER_PURGE_INTERVAL_SEC: u64 = 60;
+const TLS_RELOAD_INTERVAL_SEC: u64 = 10;
+
+async fn serve<F>(routes: F, bind_address: SocketAddr, tls: Option<Tls>, shutdown: oneshot::Receiver<()>)
+where
+    F: Filter<Error = Infallible> + Clone + Send + Sync + 'static,
+    F::Extract: Reply,
+{
+    let shutdown = async move {
+        let _ = shutdown.await;
+    };
+
+    match tls {
+        Some(tls) => tls::serve(routes, bind_address, tls, shutdown).await,
+        None => match warp::serve(routes).try_bind_with_graceful_shutdown(bind_address, shutdown) {
+            Ok((address, server)) => {
+                info!("Listening on {}.", address);
+                server.await;
+            }
+            Err(e) => error!("Binding to {} failed: {}.", bind_address, e),
+        },
+    }
+}
+
+pub(crate) struct ApiWorker;
+
+#[async_trait]
+impl<N: Node> Worker<N> for ApiWorker
+where
+    N::Backend: Backend,
+{
+    type Config = (
+        ApiConfig,
+        (String, u64),
+        String,
+        ProtocolConfig,
+        Network,
+        Keypair,
+        ManualPeers,
+    );
+    type Error = Infallible;
+
+    fn dependencies() -> &'static [TypeId] {
+        let mut dependencies = vec![
+            TypeId::of::<StorageWorker>(),
+            TypeId::of::<TangleWorker>(),
+            TypeId::of::<MessageSubmitterWorker>(),
+        ];
+        // The metrics report the depth of their queues.
+        dependencies.extend(Protocol::queue_workers());
+        dependencies.leak()
+    }
+
+    async fn start(node: &mut N, config: Self::Config) -> Result<Self, Self::Error> {
+        let (config, network_id, version, protocol_config, network, local_keypair, manual_peers) = config;
+
+        let tls = match config.tls() {
+            Some(tls_config) => match Tls::new(tls_config.clone()) {
+                Ok(tls) => Some(tls),
+                Err(e) => {
+                    // Falling back to plain HTTP would expose what was meant to be encrypted.
+                    error!("Loading the TLS certificate failed, the API is disabled: {}", e);
+                    return Ok(Self);
+                }
+            },
+            None => None,
+        };
+
+        let tangle = node.resource::<MsTangle<N::Backend>>();
+        let storage = node.storage();
+        let message_submitter = node.worker::<MessageSubmitterWorker>().unwrap().tx.clone();
+
+        let auth = Auth::new(config.clone(), &local_keypair);
+        let rate_limiter = RateLimiter::new(config.clone());
+        let worker_queues = Arc::new(Protocol::worker_queues::<N>(node));
+        let broker = Arc::new(Broker::default());
+        events::register::<N>(node, broker.clone());
+
+        let purged_rate_limiter = rate_limiter.clone();
+        node.spawn::<Self, _, _>(|shutdown| async move {
+            let mut ticker =
+                ShutdownStream::new(shutdown, interval(Duration::from_secs(RATE_LIMITER_PURGE_INTERVAL_SEC)));
+
+            while ticker.next().await.is_some() {
+                purged_rate_limiter.purge();
+            }
+        });
+
+        if let Some(tls) = tls.clone() {
+            node.spawn::<Self, _, _>(|shutdown| async move {
+                let mut ticker = ShutdownStream::new(shutdown, interval(Duration::from_secs(TLS_RELOAD_INTERVAL_SEC)));
+
+                while ticker.next().await.is_some() {
+                    tls.reload_if_modified();
+                }
+            });
+        }
+
+        node.spawn::<Self, _, _>(|shutdown| async move {
+            info!("Running.");
+
+            let bind_address = config.bind_address;
+            let cors = cors::cors(&config);
+            let sessions = broker.clone();
+            let routes = filters::all(
+                config,
+                network_id,
+                version,
+                protocol_config,
+                tangle,
+                storage,
+                message_submitter,
+                network,
+                manual_peers,
+                broker,
+                worker_queues,
+                auth,
+                rate_limiter,
+            )
+            .recover(handle_rejection);
+
+            match cors {
+                // Recovering again since the CORS policy itself rejects forbidden requests.
+                Some(cors) => serve(routes.with(cors).recover(handle_rejection), bind_address, tls, shutdown).await,
+                None => serve(routes, bind_address, tls, shutdown).await,
+            }
+            // Upgraded WebSocket connections outlive the server.
+            sessions.shutdown();
+
+            info!("Stopped.");
+        });
+
+        Ok(Self)
+    }
+}
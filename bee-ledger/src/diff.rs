@@ -0,0 +1,142 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::Error;
+
+use bee_common::packable::{Packable, Read, Write};
+use bee_message::payload::transaction::OutputId;
+
+/// Effects of the confirmation of a milestone on the ledger.
+pub struct Diff {
+    messages_referenced: u32,
+    messages_excluded_no_transaction: u32,
+    messages_excluded_conflicting: u32,
+    messages_included: u32,
+    created_outputs: Vec<OutputId>,
+    consumed_outputs: Vec<OutputId>,
+}
+
+impl Diff {
+    pub fn new(
+        messages_referenced: u32,
+        messages_excluded_no_transaction: u32,
+        messages_excluded_conflicting: u32,
+        messages_included: u32,
+        created_outputs: Vec<OutputId>,
+        consumed_outputs: Vec<OutputId>,
+    ) -> Self {
+        Self {
+            messages_referenced,
+            messages_excluded_no_transaction,
+            messages_excluded_conflicting,
+            messages_included,
+            created_outputs,
+            consumed_outputs,
+        }
+    }
+
+    pub fn messages_referenced(&self) -> u32 {
+        self.messages_referenced
+    }
+
+    pub fn messages_excluded_no_transaction(&self) -> u32 {
+        self.messages_excluded_no_transaction
+    }
+
+    pub fn messages_excluded_conflicting(&self) -> u32 {
+        self.messages_excluded_conflicting
+    }
+
+    pub fn messages_included(&self) -> u32 {
+        self.messages_included
+    }
+
+    pub fn created_outputs(&self) -> &[OutputId] {
+        &self.created_outputs
+    }
+
+    pub fn consumed_outputs(&self) -> &[OutputId] {
+        &self.consumed_outputs
+    }
+}
+
+impl Packable for Diff {
+    type Error = Error;
+
+    fn packed_len(&self) -> usize {
+        self.messages_referenced.packed_len()
+            + self.messages_excluded_no_transaction.packed_len()
+            + self.messages_excluded_conflicting.packed_len()
+            + self.messages_included.packed_len()
+            + 0u32.packed_len()
+            + self
+                .created_outputs
+                .iter()
+                .map(|output_id| output_id.packed_len())
+                .sum::<usize>()
+            + 0u32.packed_len()
+            + self
+                .consumed_outputs
+                .iter()
+                .map(|output_id| output_id.packed_len())
+                .sum::<usize>()
+    }
+
+    fn pack<W: Write>(&self, writer: &mut W) -> Result<(), Self::Error> {
+        self.messages_referenced.pack(writer)?;
+        self.messages_excluded_no_transaction.pack(writer)?;
+        self.messages_excluded_conflicting.pack(writer)?;
+        self.messages_included.pack(writer)?;
+
+        (self.created_outputs.len() as u32).pack(writer)?;
+        for output_id in self.created_outputs.iter() {
+            output_id.pack(writer)?;
+        }
+
+        (self.consumed_outputs.len() as u32).pack(writer)?;
+        for output_id in self.consumed_outputs.iter() {
+            output_id.pack(writer)?;
+        }
+
+        Ok(())
+    }
+
+    fn unpack<R: Read + ?Sized>(reader: &mut R) -> Result<Self, Self::Error>
+    where
+        Self: Sized,
+    {
+        let messages_referenced = u32::unpack(reader)?;
+        let messages_excluded_no_transaction = u32::unpack(reader)?;
+        let messages_excluded_conflicting = u32::unpack(reader)?;
+        let messages_included = u32::unpack(reader)?;
+
+        let created_outputs_len = u32::unpack(reader)? as usize;
+        let mut created_outputs = Vec::with_capacity(created_outputs_len);
+        for _ in 0..created_outputs_len {
+            created_outputs.push(OutputId::unpack(reader)?);
+        }
+
+        let consumed_outputs_len = u32::unpack(reader)? as usize;
+        let mut consumed_outputs = Vec::with_capacity(consumed_outputs_len);
+        for _ in 0..consumed_outputs_len {
+            consumed_outputs.push(OutputId::unpack(reader)?);
+        }
+
+        Ok(Self {
+            messages_referenced,
+            messages_excluded_no_transaction,
+            messages_excluded_conflicting,
+            messages_included,
+            created_outputs,
+            consumed_outputs,
+        })
+    }
+}
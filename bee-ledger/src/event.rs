@@ -9,6 +9,9 @@
 // an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and limitations under the License.
 
+use crate::output::Output;
+
+use bee_message::{payload::transaction::OutputId, MessageId};
 use bee_protocol::MilestoneIndex;
 
 pub struct MilestoneConfirmed {
@@ -19,3 +22,15 @@ pub struct MilestoneConfirmed {
     pub messages_excluded_conflicting: usize,
     pub messages_included: usize,
 }
+
+/// A message has been referenced by a confirmed milestone.
+pub struct MessageReferenced {
+    pub message_id: MessageId,
+    pub index: MilestoneIndex,
+}
+
+/// An output has been created by a transaction included in a confirmed milestone.
+pub struct OutputCreated {
+    pub output_id: OutputId,
+    pub output: Output,
+}
@@ -11,6 +11,7 @@
 
 //#![warn(missing_docs)]
 
+pub mod diff;
 mod error;
 pub mod event;
 mod merkle_hasher;
@@ -24,8 +25,8 @@ mod worker;
 
 pub use error::Error;
 use storage::Backend;
-use worker::LedgerWorker;
-pub use worker::LedgerWorkerEvent;
+pub use white_flag::merkle_proof;
+pub use worker::{LedgerIndex, LedgerWorker, LedgerWorkerEvent};
 
 use bee_common_ext::{
     event::Bus,
@@ -35,16 +36,13 @@ use bee_protocol::{config::ProtocolCoordinatorConfig, MilestoneIndex};
 
 use std::sync::Arc;
 
-pub fn init<N: Node>(
-    index: u32,
-    coo_config: ProtocolCoordinatorConfig,
-    node_builder: N::Builder,
-    bus: Arc<Bus<'static>>,
-) -> N::Builder
+pub fn init<N: Node>(index: u32, coo_config: ProtocolCoordinatorConfig, node_builder: N::Builder) -> N::Builder
 where
     N::Backend: Backend,
 {
-    node_builder.with_worker_cfg::<LedgerWorker>((MilestoneIndex(index), coo_config, bus.clone()))
+    node_builder
+        .with_resource(LedgerIndex::new(MilestoneIndex(index)))
+        .with_worker_cfg::<LedgerWorker>(coo_config)
 }
 
 pub fn events<N: Node>(_node: &N, _bus: Arc<Bus<'static>>) {
@@ -24,8 +24,8 @@ pub(crate) struct WhiteFlagMetadata {
     /// Timestamp of the confirming milestone.
     #[allow(dead_code)]
     pub(crate) timestamp: u64,
-    /// The number of messages which were referenced by the confirming milestone.
-    pub(crate) num_messages_referenced: usize,
+    /// The messages which were referenced by the confirming milestone.
+    pub(crate) messages_referenced: Vec<MessageId>,
     /// The number of messages which were excluded because they did not include a value transaction.
     pub(crate) num_messages_excluded_no_transaction: usize,
     /// The number of messages which were excluded as they were conflicting with the ledger state.
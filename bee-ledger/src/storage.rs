@@ -9,9 +9,10 @@
 // an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and limitations under the License.
 
-use crate::{error::Error, output::Output, spent::Spent, unspent::Unspent};
+use crate::{diff::Diff, error::Error, output::Output, spent::Spent, unspent::Unspent};
 
 use bee_message::payload::transaction::OutputId;
+use bee_protocol::MilestoneIndex;
 use bee_storage::{
     access::{Batch, BatchBuilder, Delete, Exist, Fetch, Insert},
     storage,
@@ -23,6 +24,7 @@ pub trait Backend:
     + Batch<OutputId, Output>
     + Batch<OutputId, Spent>
     + Batch<Unspent, ()>
+    + Batch<MilestoneIndex, Diff>
     + Delete<OutputId, Output>
     + Delete<OutputId, Spent>
     + Delete<Unspent, ()>
@@ -43,6 +45,7 @@ impl<T> Backend for T where
         + Batch<OutputId, Output>
         + Batch<OutputId, Spent>
         + Batch<Unspent, ()>
+        + Batch<MilestoneIndex, Diff>
         + Delete<OutputId, Output>
         + Delete<OutputId, Spent>
         + Delete<Unspent, ()>
@@ -11,6 +11,7 @@
 
 use crate::{
     error::Error,
+    merkle_hasher::MerkleHasher,
     metadata::WhiteFlagMetadata,
     output::Output,
     spent::Spent,
@@ -25,9 +26,11 @@ use bee_message::{
     },
     Message, MessageId,
 };
-use bee_protocol::tangle::MsTangle;
+use bee_protocol::{tangle::MsTangle, MilestoneIndex};
 use bee_storage::access::Fetch;
 
+use blake2::Blake2b;
+
 use std::{
     collections::{HashMap, HashSet},
     ops::Deref,
@@ -42,13 +45,14 @@ async fn on_message<N: Node>(
     message_id: &MessageId,
     message: &Message,
     metadata: &mut WhiteFlagMetadata,
+    confirm: bool,
 ) -> Result<(), Error>
 where
     N::Backend: Backend,
 {
     let mut conflicting = false;
 
-    metadata.num_messages_referenced += 1;
+    metadata.messages_referenced.push(*message_id);
 
     if let Some(Payload::Transaction(transaction)) = message.payload() {
         let transaction_id = transaction.id();
@@ -114,12 +118,14 @@ where
         metadata.num_messages_excluded_no_transaction += 1;
     }
 
-    tangle.update_metadata(message_id, |message_metadata| {
-        message_metadata.flags_mut().set_conflicting(conflicting);
-        message_metadata.set_milestone_index(metadata.index);
-        // TODO pass actual ms timestamp
-        message_metadata.confirm();
-    });
+    if confirm {
+        tangle.update_metadata(message_id, |message_metadata| {
+            message_metadata.flags_mut().set_conflicting(conflicting);
+            message_metadata.set_milestone_index(metadata.index);
+            // TODO pass actual ms timestamp
+            message_metadata.confirm();
+        });
+    }
 
     Ok(())
 }
@@ -133,9 +139,48 @@ pub(crate) async fn visit_dfs<N: Node>(
 where
     N::Backend: Backend,
 {
-    let mut messages_ids = vec![root];
+    traverse::<N>(tangle, storage, root, metadata, &mut HashSet::new(), true).await
+}
+
+/// Computes the merkle proof of the messages a milestone with these parents would include, without confirming them.
+pub async fn merkle_proof<N: Node>(
+    tangle: &MsTangle<N::Backend>,
+    storage: &ResHandle<N::Backend>,
+    index: MilestoneIndex,
+    parent1: MessageId,
+    parent2: MessageId,
+) -> Result<Vec<u8>, Error>
+where
+    N::Backend: Backend,
+{
+    let mut metadata = WhiteFlagMetadata::new(index, 0);
     let mut visited = HashSet::new();
 
+    // Same order as the traversal from the milestone itself, which visits the cone of its first parent first.
+    for parent in &[parent1, parent2] {
+        traverse::<N>(tangle, storage, *parent, &mut metadata, &mut visited, false).await?;
+    }
+
+    Ok(MerkleHasher::<Blake2b>::new().digest(&metadata.messages_included))
+}
+
+async fn traverse<N: Node>(
+    tangle: &MsTangle<N::Backend>,
+    storage: &ResHandle<N::Backend>,
+    root: MessageId,
+    metadata: &mut WhiteFlagMetadata,
+    visited: &mut HashSet<MessageId>,
+    confirm: bool,
+) -> Result<(), Error>
+where
+    N::Backend: Backend,
+{
+    if visited.contains(&root) {
+        return Ok(());
+    }
+
+    let mut messages_ids = vec![root];
+
     // TODO Tangle get message AND meta at the same time
 
     while let Some(message_id) = messages_ids.last() {
@@ -164,7 +209,7 @@ where
                 let parent2 = message.parent2();
 
                 if visited.contains(parent1) && visited.contains(parent2) {
-                    on_message::<N>(tangle, storage, message_id, &message, metadata).await?;
+                    on_message::<N>(tangle, storage, message_id, &message, metadata, confirm).await?;
                     visited.insert(*message_id);
                     messages_ids.pop();
                 } else if !visited.contains(parent1) {
@@ -10,8 +10,13 @@
 // See the License for the specific language governing permissions and limitations under the License.
 
 use crate::{
-    error::Error, event::MilestoneConfirmed, merkle_hasher::MerkleHasher, metadata::WhiteFlagMetadata,
-    storage::Backend, white_flag::visit_dfs,
+    diff::Diff,
+    error::Error,
+    event::{MessageReferenced, MilestoneConfirmed, OutputCreated},
+    merkle_hasher::MerkleHasher,
+    metadata::WhiteFlagMetadata,
+    storage::Backend,
+    white_flag::visit_dfs,
 };
 
 use bee_common::shutdown_stream::ShutdownStream;
@@ -22,14 +27,16 @@ use bee_common_ext::{
 };
 use bee_message::{payload::Payload, MessageId};
 use bee_protocol::{config::ProtocolCoordinatorConfig, tangle::MsTangle, MilestoneIndex, StorageWorker, TangleWorker};
-use bee_storage::access::BatchBuilder;
+use bee_storage::access::{Batch, BatchBuilder};
 
 use async_trait::async_trait;
 use blake2::Blake2b;
 use futures::stream::StreamExt;
 use log::{error, info};
 
-use std::{any::TypeId, convert::Infallible, sync::Arc};
+use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use std::{any::TypeId, convert::Infallible, ops::Deref};
 
 // TODO refactor errors
 
@@ -38,17 +45,36 @@ pub enum LedgerWorkerEvent {
     // GetBalance(Address, oneshot::Sender<u64>),
 }
 
-pub(crate) struct LedgerWorker {
+pub struct LedgerWorker {
     pub(crate) tx: flume::Sender<LedgerWorkerEvent>,
 }
 
+/// Index of the milestone the ledger was last confirmed at. Holding it keeps the ledger from being updated, e.g. while
+/// it is exported as a snapshot.
+pub struct LedgerIndex(RwLock<MilestoneIndex>);
+
+impl LedgerIndex {
+    pub(crate) fn new(index: MilestoneIndex) -> Self {
+        Self(RwLock::new(index))
+    }
+
+    /// Waits for the ongoing update, if any, and holds back the next ones until the guard is dropped.
+    pub async fn read(&self) -> RwLockReadGuard<'_, MilestoneIndex> {
+        self.0.read().await
+    }
+
+    pub(crate) async fn write(&self) -> RwLockWriteGuard<'_, MilestoneIndex> {
+        self.0.write().await
+    }
+}
+
 async fn confirm<N: Node>(
     tangle: &MsTangle<N::Backend>,
     storage: &ResHandle<N::Backend>,
     message_id: MessageId,
     index: &mut MilestoneIndex,
     _coo_config: &ProtocolCoordinatorConfig,
-    bus: &Arc<Bus<'static>>,
+    bus: &Bus<'static>,
 ) -> Result<(), Error>
 where
     N::Backend: Backend,
@@ -75,7 +101,7 @@ where
         milestone.essence().timestamp(),
     );
 
-    let batch = N::Backend::batch_begin();
+    let mut batch = N::Backend::batch_begin();
 
     if let Err(e) = visit_dfs::<N>(tangle, storage, message_id, &mut metadata).await {
         error!(
@@ -98,7 +124,7 @@ where
         return Err(Error::MerkleProofMismatch);
     }
 
-    if metadata.num_messages_referenced
+    if metadata.messages_referenced.len()
         != metadata.num_messages_excluded_no_transaction
             + metadata.num_messages_excluded_conflicting
             + metadata.messages_included.len()
@@ -106,7 +132,7 @@ where
         error!(
             "Invalid messages count at {}: referenced ({}) != no transaction ({}) + conflicting ({}) + included ({}).",
             milestone.essence().index(),
-            metadata.num_messages_referenced,
+            metadata.messages_referenced.len(),
             metadata.num_messages_excluded_no_transaction,
             metadata.num_messages_excluded_conflicting,
             metadata.messages_included.len()
@@ -114,8 +140,21 @@ where
         return Err(Error::InvalidMessagesCount);
     }
 
-    // TODO unwrap
-    storage.batch_commit(batch, true).await.unwrap();
+    let diff = Diff::new(
+        metadata.messages_referenced.len() as u32,
+        metadata.num_messages_excluded_no_transaction as u32,
+        metadata.num_messages_excluded_conflicting as u32,
+        metadata.messages_included.len() as u32,
+        metadata.created_outputs.keys().copied().collect(),
+        metadata.spent_outputs.keys().copied().collect(),
+    );
+
+    Batch::<MilestoneIndex, Diff>::batch_insert(storage.deref(), &mut batch, &metadata.index, &diff)
+        .map_err(|e| Error::Storage(Box::new(e)))?;
+    storage
+        .batch_commit(batch, true)
+        .await
+        .map_err(|e| Error::Storage(Box::new(e)))?;
 
     // TODO update meta only when sure everything is fine
 
@@ -124,7 +163,7 @@ where
     info!(
         "Confirmed milestone {}: referenced {}, zero value {}, conflicting {}, included {}.",
         milestone.essence().index(),
-        metadata.num_messages_referenced,
+        metadata.messages_referenced.len(),
         metadata.num_messages_excluded_no_transaction,
         metadata.num_messages_excluded_conflicting,
         metadata.messages_included.len()
@@ -133,12 +172,23 @@ where
     bus.dispatch(MilestoneConfirmed {
         index: milestone.essence().index().into(),
         timestamp: milestone.essence().timestamp(),
-        messages_referenced: metadata.num_messages_referenced,
+        messages_referenced: metadata.messages_referenced.len(),
         messages_excluded_no_transaction: metadata.num_messages_excluded_no_transaction,
         messages_excluded_conflicting: metadata.num_messages_excluded_conflicting,
         messages_included: metadata.messages_included.len(),
     });
 
+    for message_id in metadata.messages_referenced {
+        bus.dispatch(MessageReferenced {
+            message_id,
+            index: metadata.index,
+        });
+    }
+
+    for (output_id, output) in metadata.created_outputs {
+        bus.dispatch(OutputCreated { output_id, output });
+    }
+
     Ok(())
 }
 
@@ -153,7 +203,7 @@ impl<N: Node> Worker<N> for LedgerWorker
 where
     N::Backend: Backend,
 {
-    type Config = (MilestoneIndex, ProtocolCoordinatorConfig, Arc<Bus<'static>>);
+    type Config = ProtocolCoordinatorConfig;
     type Error = Infallible;
 
     fn dependencies() -> &'static [TypeId] {
@@ -165,20 +215,20 @@ where
 
         let tangle = node.resource::<MsTangle<N::Backend>>();
         let storage = node.storage();
+        let bus = node.resource::<Bus>();
+        let index = node.resource::<LedgerIndex>();
 
         node.spawn::<Self, _, _>(|shutdown| async move {
             info!("Running.");
 
             let mut receiver = ShutdownStream::new(shutdown, rx.into_stream());
 
-            let mut index = config.0;
-            let coo_config = config.1;
-            let bus = config.2;
-
             while let Some(event) = receiver.next().await {
                 match event {
                     LedgerWorkerEvent::Confirm(message_id) => {
-                        if confirm::<N>(&tangle, &storage, message_id, &mut index, &coo_config, &bus)
+                        let mut index = index.write().await;
+
+                        if confirm::<N>(&tangle, &storage, message_id, &mut index, &config, &bus)
                             .await
                             .is_err()
                         {
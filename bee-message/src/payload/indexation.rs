@@ -50,10 +50,7 @@ impl Indexation {
     }
 
     pub fn hash(&self) -> HashedIndex {
-        let mut hasher = Blake2s::new();
-        hasher.update(self.index.as_bytes());
-        // `Blake2s` output is `HASHED_INDEX_LENGTH` bytes long.
-        HashedIndex(hasher.finalize_reset().as_slice().try_into().unwrap())
+        HashedIndex::from_index(self.index.as_bytes())
     }
 }
 
@@ -93,7 +90,7 @@ impl Packable for Indexation {
     }
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct HashedIndex([u8; HASHED_INDEX_LENGTH]);
 
 // TODO review when we have fixed size index
@@ -101,6 +98,14 @@ impl HashedIndex {
     pub fn new(bytes: [u8; HASHED_INDEX_LENGTH]) -> Self {
         Self(bytes)
     }
+
+    /// Hashes raw index bytes, allowing to look up indexes that are not valid UTF-8.
+    pub fn from_index(index: &[u8]) -> Self {
+        let mut hasher = Blake2s::new();
+        hasher.update(index);
+        // `Blake2s` output is `HASHED_INDEX_LENGTH` bytes long.
+        Self(hasher.finalize_reset().as_slice().try_into().unwrap())
+    }
 }
 
 impl AsRef<[u8]> for HashedIndex {
@@ -19,9 +19,10 @@ use crate::Error;
 
 use bee_common::packable::{Packable, Read, Write};
 
+use bech32::FromBase32;
 use serde::{Deserialize, Serialize};
 
-use alloc::string::String;
+use alloc::{string::String, vec::Vec};
 
 #[non_exhaustive]
 #[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize, Ord, PartialOrd)]
@@ -59,6 +60,13 @@ impl Address {
             Address::Ed25519(address) => address.to_bech32(),
         }
     }
+
+    pub fn try_from_bech32(address: &str) -> Result<Self, Error> {
+        let (_, data) = bech32::decode(address).map_err(|_| Error::InvalidAddress)?;
+        let bytes = Vec::<u8>::from_base32(&data).map_err(|_| Error::InvalidAddress)?;
+
+        Self::unpack(&mut bytes.as_slice())
+    }
 }
 
 impl Packable for Address {
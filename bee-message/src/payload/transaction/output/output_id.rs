@@ -46,16 +46,12 @@ impl FromStr for OutputId {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let bytes = hex::decode(s).map_err(|_| Self::Err::InvalidHex)?;
-        let transaction_id_bytes: [u8; TRANSACTION_ID_LENGTH] = bytes[..TRANSACTION_ID_LENGTH]
+        let bytes: [u8; OUTPUT_ID_LENGTH] = hex::decode(s)
+            .map_err(|_| Self::Err::InvalidHex)?
+            .as_slice()
             .try_into()
             .map_err(|_| Self::Err::InvalidHex)?;
-        let transaction_id = TransactionId::from(transaction_id_bytes);
-        let index = u16::from_le_bytes(
-            bytes[TRANSACTION_ID_LENGTH..]
-                .try_into()
-                .map_err(|_| Self::Err::InvalidHex)?,
-        );
+        let OutputId { transaction_id, index } = OutputId::from(bytes);
 
         OutputId::new(transaction_id, index)
     }
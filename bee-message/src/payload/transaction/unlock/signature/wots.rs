@@ -42,6 +42,13 @@ impl TryFrom<&TritBuf<T5B1Buf>> for WotsSignature {
     }
 }
 
+impl AsRef<[u8]> for WotsSignature {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
 // TODO builder ?
 impl WotsSignature {
     pub fn new(trits: &TritBuf<T5B1Buf>) -> Result<Self, Error> {
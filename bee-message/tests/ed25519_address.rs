@@ -37,6 +37,18 @@ fn generate_bech32_string() {
     );
 }
 
+#[test]
+fn bech32_string_round_trip() {
+    let address = Address::from(Ed25519Address::from_str(ED25519_ADDRESS).unwrap());
+
+    assert_eq!(Address::try_from_bech32(&address.to_bech32()).unwrap(), address);
+}
+
+#[test]
+fn invalid_bech32_string() {
+    assert!(Address::try_from_bech32("iot1qqqqqq").is_err());
+}
+
 #[test]
 fn from_to_str() {
     assert_eq!(
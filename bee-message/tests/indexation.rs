@@ -91,3 +91,11 @@ fn unpack_non_utf8_index() {
         matches!(Indexation::unpack(&mut bytes.as_slice()).err(), Some(Error::Utf8String(std::string::FromUtf8Error{..})))
     );
 }
+
+#[test]
+fn hash_from_index() {
+    let indexation = Indexation::new("indexation".to_string(), &[0x42, 0xff, 0x84, 0xa2]).unwrap();
+
+    assert_eq!(indexation.hash(), HashedIndex::from_index(b"indexation"));
+    assert_ne!(indexation.hash(), HashedIndex::from_index(&[0xff, 0xfe]));
+}
@@ -19,3 +19,10 @@ const OUTPUT_ID: &str = "52fdfc072182654f163f5f0f9a621d729566c74d10037c4d7bbb040
 fn from_to_str() {
     assert_eq!(OUTPUT_ID, OutputId::from_str(OUTPUT_ID).unwrap().to_string());
 }
+
+#[test]
+fn from_str_invalid_length() {
+    assert!(OutputId::from_str(&OUTPUT_ID[..64]).is_err());
+    assert!(OutputId::from_str(&OUTPUT_ID[..2]).is_err());
+    assert!(OutputId::from_str(&format!("{}00", OUTPUT_ID)).is_err());
+}
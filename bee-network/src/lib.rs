@@ -50,6 +50,11 @@ pub(crate) static RECONNECT_MILLIS: AtomicU64 = AtomicU64::new(DEFAULT_RECONNECT
 pub(crate) static PEER_LIMIT: AtomicUsize = AtomicUsize::new(DEFAULT_PEER_LIMIT);
 pub(crate) static NETWORK_ID: AtomicU64 = AtomicU64::new(0);
 
+/// Returns the id of the peer owning `keypair`.
+pub fn peer_id(keypair: &Keypair) -> PeerId {
+    PeerId::from_public_key(identity::PublicKey::Ed25519(keypair.public()))
+}
+
 pub async fn init(
     config: NetworkConfig,
     local_keys: Keypair,
@@ -61,8 +66,8 @@ pub async fn init(
     PEER_LIMIT.swap(config.peer_limit, Ordering::Relaxed);
     NETWORK_ID.swap(network_id, Ordering::Relaxed);
 
+    let local_id = peer_id(&local_keys);
     let local_keys = identity::Keypair::Ed25519(local_keys);
-    let local_id = PeerId::from_public_key(local_keys.public());
 
     let (command_sender, command_receiver) = commands::channel();
     let (event_sender, event_receiver) = events::channel::<Event>();
@@ -11,12 +11,15 @@
 
 use crate::config::NodeConfigBuilder;
 
+use bee_api::auth::RouteGroup;
 use bee_common::logger::LOGGER_STDOUT_NAME;
 use bee_storage::storage::Backend;
 
 use log::LevelFilter;
 use structopt::StructOpt;
 
+use std::path::{Path, PathBuf};
+
 #[derive(Debug, StructOpt)]
 pub struct CliArgs {
     #[structopt(
@@ -25,6 +28,78 @@ pub struct CliArgs {
         help = "Stdout log level amongst \"trace\", \"debug\", \"info\", \"warn\" and \"error\""
     )]
     log_level: Option<LevelFilter>,
+    #[structopt(
+        short = "n",
+        long = "network",
+        help = "Network to join with its built-in profile, e.g. \"alphanet\"; settings of the config file take precedence"
+    )]
+    network: Option<String>,
+    #[structopt(
+        short = "c",
+        long = "config",
+        help = "Path of the config file; \"./config.toml\" if omitted"
+    )]
+    config: Option<PathBuf>,
+    #[structopt(
+        long = "set",
+        number_of_values = 1,
+        help = "Overrides a setting, e.g. \"network.bind_address=/ip4/0.0.0.0/tcp/15600\"; takes precedence over the \
+                config file and BEE_ environment variables, e.g. BEE_NETWORK__BIND_ADDRESS"
+    )]
+    settings: Vec<String>,
+    #[structopt(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, StructOpt)]
+pub enum Command {
+    /// Issues a JWT granting access to API routes
+    #[structopt(name = "jwt")]
+    Jwt {
+        #[structopt(
+            long = "permission",
+            help = "Route group the token grants access to, amongst \"data\" and \"admin\"; all of them if omitted"
+        )]
+        permissions: Vec<RouteGroup>,
+        #[structopt(long = "ttl", help = "Validity of the token in seconds; unlimited if omitted")]
+        ttl: Option<u64>,
+    },
+    /// Shows a live view of a running node, through its RPC socket
+    #[structopt(name = "dashboard")]
+    Dashboard {
+        #[structopt(
+            long = "socket",
+            help = "Path of the RPC socket of the node; the configured one if omitted"
+        )]
+        socket: Option<PathBuf>,
+    },
+    /// Manages the key file holding the identity of the node
+    #[structopt(name = "identity")]
+    Identity {
+        #[structopt(subcommand)]
+        command: IdentityCommand,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+pub enum IdentityCommand {
+    /// Shows the peer id and public key of the node
+    #[structopt(name = "show")]
+    Show,
+    /// Generates a new identity and stores it in the key file
+    #[structopt(name = "generate")]
+    Generate {
+        #[structopt(long = "force", help = "Replaces an existing key file")]
+        force: bool,
+    },
+    /// Stores an existing hex encoded keypair in the key file
+    #[structopt(name = "import")]
+    Import {
+        #[structopt(help = "Hex encoded keypair, e.g. a former local_keypair setting; read from stdin if omitted")]
+        keypair: Option<String>,
+        #[structopt(long = "force", help = "Replaces an existing key file")]
+        force: bool,
+    },
 }
 
 impl Default for CliArgs {
@@ -34,9 +109,25 @@ impl Default for CliArgs {
 }
 
 impl CliArgs {
-    pub fn apply_to_config<B: Backend>(self, config: &mut NodeConfigBuilder<B>) {
+    pub fn apply_to_config<B: Backend>(&self, config: &mut NodeConfigBuilder<B>) {
         if let Some(log_level) = self.log_level {
             config.logger.level(LOGGER_STDOUT_NAME, log_level);
         }
     }
+
+    pub fn network(&self) -> Option<&str> {
+        self.network.as_deref()
+    }
+
+    pub fn config(&self) -> Option<&Path> {
+        self.config.as_deref()
+    }
+
+    pub fn settings(&self) -> &[String] {
+        &self.settings
+    }
+
+    pub fn command(&self) -> Option<&Command> {
+        self.command.as_ref()
+    }
 }
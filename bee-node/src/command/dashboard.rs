@@ -0,0 +1,289 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::{command::Error, config::NodeConfig};
+
+use bee_storage::storage::Backend;
+
+use serde::Deserialize;
+
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Write},
+    os::unix::net::UnixStream,
+    path::Path,
+    thread,
+    time::{Duration, Instant},
+};
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+const STATUS_REQUEST: &[u8] = b"{\"jsonrpc\":\"2.0\",\"method\":\"node.status\",\"id\":1}\n";
+// Clears the screen and moves the cursor to its top left corner.
+const CLEAR: &str = "\x1b[2J\x1b[H";
+
+#[derive(Deserialize)]
+struct RpcError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse {
+    result: Option<Status>,
+    error: Option<RpcError>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Status {
+    synced: bool,
+    milestones: Milestones,
+    tps: Tps,
+    tips: Tips,
+    queues: Vec<Queue>,
+    database_size: Option<u64>,
+    peers: Vec<Peer>,
+}
+
+#[derive(Deserialize)]
+struct Milestones {
+    latest: u32,
+    solid: u32,
+    snapshot: u32,
+    pruning: u32,
+}
+
+#[derive(Deserialize)]
+struct Tps {
+    incoming: u64,
+    new: u64,
+    known: u64,
+    invalid: u64,
+    outgoing: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Tips {
+    total: usize,
+    non_lazy: usize,
+}
+
+#[derive(Deserialize)]
+struct Queue {
+    name: String,
+    depth: usize,
+}
+
+#[derive(Deserialize)]
+struct Peer {
+    id: String,
+    alias: Option<String>,
+    address: String,
+    connected: bool,
+    metrics: Option<PeerMetrics>,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PeerMetrics {
+    messages_received: u64,
+    messages_sent: u64,
+    new_messages: u64,
+    known_messages: u64,
+    invalid_messages: u64,
+}
+
+/// Peer metrics are cumulative counters, rates are derived from the previous sample.
+#[derive(Default)]
+struct Rates {
+    samples: HashMap<String, (Instant, PeerMetrics)>,
+}
+
+impl Rates {
+    fn update(&mut self, id: &str, metrics: &PeerMetrics) -> Option<[f64; 5]> {
+        let now = Instant::now();
+        let previous = self.samples.insert(id.to_string(), (now, metrics.clone()));
+        let (then, previous) = previous?;
+        let elapsed = now.duration_since(then).as_secs_f64();
+
+        if elapsed == 0.0 {
+            return None;
+        }
+
+        let rate = |current: u64, previous: u64| current.saturating_sub(previous) as f64 / elapsed;
+
+        Some([
+            rate(metrics.messages_received, previous.messages_received),
+            rate(metrics.messages_sent, previous.messages_sent),
+            rate(metrics.new_messages, previous.new_messages),
+            rate(metrics.known_messages, previous.known_messages),
+            rate(metrics.invalid_messages, previous.invalid_messages),
+        ])
+    }
+}
+
+struct Client {
+    reader: BufReader<UnixStream>,
+    writer: UnixStream,
+}
+
+impl Client {
+    fn connect(path: &Path) -> Result<Self, Error> {
+        let stream = UnixStream::connect(path).map_err(|e| Error::Dashboard(path.display().to_string(), e))?;
+        stream
+            .set_read_timeout(Some(REQUEST_TIMEOUT))
+            .map_err(|e| Error::Dashboard(path.display().to_string(), e))?;
+        let writer = stream
+            .try_clone()
+            .map_err(|e| Error::Dashboard(path.display().to_string(), e))?;
+
+        Ok(Self {
+            reader: BufReader::new(stream),
+            writer,
+        })
+    }
+
+    fn status(&mut self) -> Result<Status, String> {
+        self.writer.write_all(STATUS_REQUEST).map_err(|e| e.to_string())?;
+
+        let mut line = String::new();
+        if self.reader.read_line(&mut line).map_err(|e| e.to_string())? == 0 {
+            return Err("connection closed by the node".to_string());
+        }
+
+        match serde_json::from_str::<RpcResponse>(&line).map_err(|e| e.to_string())? {
+            RpcResponse {
+                result: Some(status), ..
+            } => Ok(status),
+            RpcResponse { error: Some(error), .. } => Err(error.message),
+            _ => Err("empty response".to_string()),
+        }
+    }
+}
+
+fn human_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+fn render(path: &Path, status: &Status, rates: &mut Rates) -> String {
+    let mut screen = String::from(CLEAR);
+    let mut line = |text: String| {
+        screen.push_str(&text);
+        screen.push('\n');
+    };
+
+    line(format!(
+        "Bee dashboard - {} - refreshed every second, CTRL-C to quit",
+        path.display()
+    ));
+    line(String::new());
+    line(format!(
+        "Milestones  latest {}  solid {}  snapshot {}  pruning {}  [{}]",
+        status.milestones.latest,
+        status.milestones.solid,
+        status.milestones.snapshot,
+        status.milestones.pruning,
+        if status.synced { "synced" } else { "not synced" }
+    ));
+    line(format!(
+        "TPS         incoming {}  new {}  known {}  invalid {}  outgoing {}",
+        status.tps.incoming, status.tps.new, status.tps.known, status.tps.invalid, status.tps.outgoing
+    ));
+    line(format!(
+        "Tips        total {}  non-lazy {}",
+        status.tips.total, status.tips.non_lazy
+    ));
+    line(format!(
+        "Database    {}",
+        status.database_size.map_or_else(|| "unknown".to_string(), human_size)
+    ));
+    line(String::new());
+    line(format!(
+        "{:<54} {:<13} {:>8} {:>8} {:>8} {:>8} {:>8}",
+        "PEER", "STATE", "RX/s", "TX/s", "NEW/s", "KNOWN/s", "INVLD/s"
+    ));
+
+    for peer in status.peers.iter() {
+        let state = if peer.connected { "connected" } else { "disconnected" };
+        let rates = peer
+            .metrics
+            .as_ref()
+            .and_then(|metrics| rates.update(&peer.id, metrics));
+
+        match rates {
+            Some([received, sent, new, known, invalid]) => line(format!(
+                "{:<54} {:<13} {:>8.1} {:>8.1} {:>8.1} {:>8.1} {:>8.1}",
+                peer.id, state, received, sent, new, known, invalid
+            )),
+            None => line(format!(
+                "{:<54} {:<13} {:>8} {:>8} {:>8} {:>8} {:>8}",
+                peer.id, state, "-", "-", "-", "-", "-"
+            )),
+        }
+        match peer.alias.as_ref() {
+            Some(alias) => line(format!("  {} ({})", peer.address, alias)),
+            None => line(format!("  {}", peer.address)),
+        }
+    }
+
+    line(String::new());
+    line(format!("{:<24} {:>8}", "QUEUE", "LENGTH"));
+    for queue in status.queues.iter() {
+        line(format!("{:<24} {:>8}", queue.name, queue.depth));
+    }
+
+    screen
+}
+
+pub(crate) fn exec<B: Backend>(config: &NodeConfig<B>, socket: Option<&Path>) -> Result<(), Error> {
+    let path = socket.unwrap_or_else(|| config.rpc.path());
+    // Fails early when no node is listening, later failures are displayed and the connection retried.
+    let mut client = Some(Client::connect(path)?);
+    let mut rates = Rates::default();
+
+    loop {
+        let screen = match client.as_mut().map(Client::status) {
+            Some(Ok(status)) => render(path, &status, &mut rates),
+            Some(Err(e)) => {
+                client = None;
+                format!(
+                    "{}Lost connection to {}: {}. Reconnecting...\n",
+                    CLEAR,
+                    path.display(),
+                    e
+                )
+            }
+            None => match Client::connect(path) {
+                Ok(connected) => {
+                    client = Some(connected);
+                    continue;
+                }
+                Err(e) => format!("{}{}\n", CLEAR, e),
+            },
+        };
+
+        print!("{}", screen);
+        let _ = std::io::stdout().flush();
+
+        thread::sleep(REFRESH_INTERVAL);
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::{cli::IdentityCommand, command::Error, config::NodeConfig, identity};
+
+use bee_network::Keypair;
+use bee_storage::storage::Backend;
+
+use std::io::{self, Read};
+
+pub(crate) fn exec<B: Backend>(config: &NodeConfig<B>, command: &IdentityCommand) -> Result<(), Error> {
+    match command {
+        IdentityCommand::Show => show(config),
+        IdentityCommand::Generate { force } => replace(config, Keypair::generate(), *force),
+        IdentityCommand::Import { keypair, force } => {
+            let keypair = match keypair {
+                Some(keypair) => keypair.clone(),
+                None => {
+                    let mut keypair = String::new();
+                    io::stdin().read_to_string(&mut keypair).map_err(identity::Error::Io)?;
+                    keypair
+                }
+            };
+
+            replace(config, identity::decode(&keypair)?, *force)
+        }
+    }
+}
+
+fn print(keypair: &Keypair) {
+    println!("Peer id:    {}", bee_network::peer_id(keypair));
+    println!("Public key: {}", hex::encode(keypair.public().encode()));
+}
+
+fn show<B: Backend>(config: &NodeConfig<B>) -> Result<(), Error> {
+    let (configured, _, generated) = &config.peering.local_keypair;
+
+    if !generated {
+        println!("Source:     local_keypair setting of the peering section");
+        print(configured);
+        return Ok(());
+    }
+
+    match identity::load(&config.identity)? {
+        Some(keypair) => {
+            println!("Source:     {}", config.identity.path().display());
+            print(&keypair);
+        }
+        None => println!(
+            "No identity yet, one will be generated in {} at the first start.",
+            config.identity.path().display()
+        ),
+    }
+
+    Ok(())
+}
+
+fn replace<B: Backend>(config: &NodeConfig<B>, keypair: Keypair, force: bool) -> Result<(), Error> {
+    let path = config.identity.path();
+
+    if !force && path.exists() {
+        return Err(identity::Error::AlreadyExists(path.display().to_string()).into());
+    }
+
+    identity::store(&config.identity, &keypair)?;
+
+    println!(
+        "Stored in {}{}.",
+        path.display(),
+        if config.identity.passphrase().is_some() {
+            ", encrypted"
+        } else {
+            ""
+        }
+    );
+    print(&keypair);
+
+    if !config.peering.local_keypair.2 {
+        eprintln!("The local_keypair setting of the peering section takes precedence, remove it to use this identity.");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::{command::Error, config::NodeConfig, identity};
+
+use bee_api::auth::{issue_token, RouteGroup};
+use bee_storage::storage::Backend;
+
+pub(crate) fn exec<B: Backend>(
+    config: &NodeConfig<B>,
+    permissions: &[RouteGroup],
+    ttl: Option<u64>,
+) -> Result<(), Error> {
+    // Tokens are signed with the identity the node will use, which is created if there is none yet.
+    let (keypair, _) = identity::keypair(config)?;
+
+    let permissions = if permissions.is_empty() {
+        vec![RouteGroup::Data, RouteGroup::Admin]
+    } else {
+        permissions.to_vec()
+    };
+
+    println!("{}", issue_token(&config.api, &keypair, permissions, ttl)?);
+
+    Ok(())
+}
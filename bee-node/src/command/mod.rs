@@ -0,0 +1,41 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+mod dashboard;
+mod identity;
+mod jwt;
+
+use crate::{cli::Command, config::NodeConfig};
+
+use bee_storage::storage::Backend;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("{0}")]
+    Identity(#[from] crate::identity::Error),
+
+    #[error("{0}")]
+    Auth(#[from] bee_api::auth::Error),
+
+    #[error("Connecting to the node at {0} failed: {1}.")]
+    Dashboard(String, std::io::Error),
+}
+
+/// Executes a CLI subcommand instead of running the node.
+pub fn exec<B: Backend>(command: &Command, config: &NodeConfig<B>) -> Result<(), Error> {
+    match command {
+        Command::Jwt { permissions, ttl } => jwt::exec(config, permissions, *ttl),
+        Command::Dashboard { socket } => dashboard::exec(config, socket.as_deref()),
+        Command::Identity { command } => identity::exec(config, command),
+    }
+}
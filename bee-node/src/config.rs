@@ -9,6 +9,17 @@
 // an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and limitations under the License.
 
+use crate::{
+    identity::{IdentityConfig, IdentityConfigBuilder},
+    plugin::{PluginsConfig, PluginsConfigBuilder},
+    profile,
+    rpc::{RpcConfig, RpcConfigBuilder},
+    shutdown::{ShutdownConfig, ShutdownConfigBuilder},
+    version_checker::{VersionCheckerConfig, VersionCheckerConfigBuilder},
+    webhook::{WebhookConfig, WebhookConfigBuilder},
+};
+
+use bee_api::config::{ApiConfig, ApiConfigBuilder};
 use bee_common::logger::{LoggerConfig, LoggerConfigBuilder};
 use bee_network::{NetworkConfig, NetworkConfigBuilder};
 use bee_peering::{PeeringConfig, PeeringConfigBuilder};
@@ -23,9 +34,16 @@ use blake2::{
 use serde::Deserialize;
 use thiserror::Error;
 
-use std::{convert::TryInto, fs, path::Path};
+use std::{
+    collections::BTreeSet,
+    convert::TryInto,
+    env, fs, io,
+    path::{Path, PathBuf},
+};
 
 const DEFAULT_NETWORK_ID: &str = "alphanet1";
+const DEFAULT_CONFIG_PATH: &str = "./config.toml";
+const ENV_PREFIX: &str = "BEE_";
 
 #[derive(Debug, Error)]
 pub enum Error {
@@ -34,6 +52,233 @@ pub enum Error {
 
     #[error("Deserializing the node config builder failed: {0}.")]
     NodeConfigBuilderCreationFailure(#[from] toml::de::Error),
+
+    #[error("Unknown network {0}, available networks are: {1}.")]
+    UnknownNetwork(String, String),
+
+    #[error("Network {0} doesn't run the Chrysalis protocol yet, available networks are: {1}.")]
+    UnsupportedNetwork(String, String),
+
+    #[error("Invalid configuration:\n  - {0}")]
+    InvalidConfig(String),
+}
+
+/// A value overriding a single key of the config.
+struct Override {
+    source: String,
+    value: toml::Value,
+    // The value as a plain string, if it was read as another type, for keys expecting one, e.g. `network_id=1`.
+    string: Option<toml::Value>,
+}
+
+impl Override {
+    /// Nests `value` under the segments of `key`, returning `None` if one of them is empty.
+    fn new(source: String, key: &str, separator: &str, value: &str) -> Option<Self> {
+        let segments = key.split(separator).map(str::trim).collect::<Vec<_>>();
+        if segments.iter().any(|segment| segment.is_empty()) {
+            return None;
+        }
+
+        let nest = |value: toml::Value| {
+            segments.iter().rev().fold(value, |value, segment| {
+                let mut table = toml::value::Table::new();
+                table.insert(segment.to_string(), value);
+                toml::Value::Table(table)
+            })
+        };
+        let string = toml::Value::String(value.to_string());
+
+        let (value, string) = match toml::from_str::<toml::value::Table>(&format!("value = {}", value))
+            .ok()
+            .and_then(|mut table| table.remove("value"))
+        {
+            Some(toml::Value::String(value)) => (nest(toml::Value::String(value)), None),
+            Some(value) => (nest(value), Some(nest(string))),
+            None => (nest(string), None),
+        };
+
+        Some(Self { source, value, string })
+    }
+}
+
+/// Deserializes a node config builder from `config`, also returning the keys it ignored.
+fn check<B: Backend>(config: &toml::Value) -> (BTreeSet<String>, Result<NodeConfigBuilder<B>, String>) {
+    let mut unknown = BTreeSet::new();
+    let result = serde_ignored::deserialize(config.clone(), |path| {
+        unknown.insert(path.to_string());
+    })
+    .map_err(|e: toml::de::Error| e.to_string());
+
+    (unknown, result)
+}
+
+/// Checks `config` with `value` merged in, returning the unknown keys and the error it adds to the ones of `config`.
+fn check_override<B: Backend>(
+    config: &toml::Value,
+    (base_unknown, base_result): &(BTreeSet<String>, Result<NodeConfigBuilder<B>, String>),
+    value: &toml::Value,
+) -> (Vec<String>, Option<String>) {
+    let mut candidate = config.clone();
+    merge(&mut candidate, value.clone());
+    let (unknown, result) = check::<B>(&candidate);
+
+    let unknown = unknown.difference(base_unknown).cloned().collect();
+    let error = match result {
+        Err(e) if base_result.as_ref().err() != Some(&e) => Some(e),
+        _ => None,
+    };
+
+    (unknown, error)
+}
+
+/// Merges `overlay` into `base`: tables are merged recursively, any other value is replaced.
+fn merge(base: &mut toml::Value, overlay: toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base), toml::Value::Table(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Where the node config is loaded from, kept to load it again when the config file changes.
+#[derive(Clone)]
+pub struct ConfigSource {
+    network: Option<String>,
+    path: Option<PathBuf>,
+    settings: Vec<String>,
+}
+
+impl ConfigSource {
+    pub fn new(network: Option<&str>, path: Option<&Path>, settings: &[String]) -> Self {
+        Self {
+            network: network.map(ToString::to_string),
+            path: path.map(Path::to_path_buf),
+            settings: settings.to_vec(),
+        }
+    }
+
+    /// Path of the config file.
+    pub fn path(&self) -> &Path {
+        self.path.as_deref().unwrap_or_else(|| Path::new(DEFAULT_CONFIG_PATH))
+    }
+
+    /// Creates a node config builder by layering, from lowest to highest precedence:
+    /// - the built-in profile of the network, if any;
+    /// - the config file, which is optional when a network is selected and the path is the default one;
+    /// - `BEE_`-prefixed environment variables, nested keys being separated by `__`, e.g. `BEE_NETWORK__BIND_ADDRESS`;
+    /// - `key=value` settings, nested keys being separated by `.`, e.g. `network.bind_address=/ip4/0.0.0.0/tcp/15600`.
+    ///
+    /// Override values are read as TOML values, and as plain strings if they aren't valid TOML or if their key expects a
+    /// string, e.g. `network_id=1`. Every unknown key and mistyped value is reported at once.
+    pub fn load<B: Backend>(&self) -> Result<NodeConfigBuilder<B>, Error> {
+        self.load_value().map(|(_, builder)| builder)
+    }
+
+    /// Loads the layered config, also returning it as a TOML value.
+    pub(crate) fn load_value<B: Backend>(&self) -> Result<(toml::Value, NodeConfigBuilder<B>), Error> {
+        self.load_value_with(env::vars())
+    }
+
+    /// Loads the layered config with `vars` as the environment variables.
+    fn load_value_with<B: Backend, I: IntoIterator<Item = (String, String)>>(
+        &self,
+        vars: I,
+    ) -> Result<(toml::Value, NodeConfigBuilder<B>), Error> {
+        let mut config = match self.network.as_deref() {
+            Some(network) => {
+                let profile = profile::get(network).ok_or_else(|| {
+                    if profile::is_unsupported(network) {
+                        Error::UnsupportedNetwork(network.to_string(), profile::names().join(", "))
+                    } else {
+                        Error::UnknownNetwork(network.to_string(), profile::names().join(", "))
+                    }
+                })?;
+                toml::from_str::<toml::Value>(profile)?
+            }
+            None => toml::Value::Table(toml::value::Table::new()),
+        };
+
+        match fs::read_to_string(self.path()) {
+            Ok(toml) => merge(&mut config, toml::from_str(&toml)?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound && self.network.is_some() && self.path.is_none() => {}
+            Err(e) => return Err(Error::ConfigFileReadFailure(e)),
+        }
+
+        let mut issues = Vec::new();
+        let mut overrides = Vec::new();
+
+        let mut vars = vars
+            .into_iter()
+            .filter(|(name, _)| name.starts_with(ENV_PREFIX))
+            .collect::<Vec<_>>();
+        vars.sort();
+        for (name, value) in vars {
+            let key = name[ENV_PREFIX.len()..].to_lowercase();
+            match Override::new(format!("environment variable {}", name), &key, "__", &value) {
+                Some(o) => overrides.push(o),
+                None => issues.push(format!("environment variable {}: invalid key", name)),
+            }
+        }
+
+        for setting in self.settings.iter() {
+            let o = setting
+                .find('=')
+                .and_then(|i| Override::new(format!("setting {}", setting), &setting[..i], ".", &setting[i + 1..]));
+            match o {
+                Some(o) => overrides.push(o),
+                None => issues.push(format!("setting {}: expected key=value", setting)),
+            }
+        }
+
+        // Each override is checked on its own against the profile and the config file, so that one mistake does
+        // not hide the others.
+        let base = check::<B>(&config);
+
+        for o in overrides {
+            let mut value = o.value;
+            let (mut unknown, mut error) = check_override(&config, &base, &value);
+
+            if let (Some(_), Some(string)) = (&error, o.string) {
+                let (string_unknown, string_error) = check_override(&config, &base, &string);
+                if string_error.is_none() {
+                    value = string;
+                    unknown = string_unknown;
+                    error = None;
+                }
+            }
+
+            for key in unknown {
+                issues.push(format!("{}: unknown key {}", o.source, key));
+            }
+            match error {
+                Some(e) => issues.push(format!("{}: {}", o.source, e)),
+                None => merge(&mut config, value),
+            }
+        }
+
+        for key in base.0 {
+            issues.push(format!("config file: unknown key {}", key));
+        }
+
+        let (_, result) = check::<B>(&config);
+
+        match result {
+            Ok(builder) if issues.is_empty() => Ok((config, builder)),
+            Ok(_) => Err(Error::InvalidConfig(issues.join("\n  - "))),
+            Err(e) => {
+                issues.push(format!("config file: {}", e));
+                Err(Error::InvalidConfig(issues.join("\n  - ")))
+            }
+        }
+    }
 }
 
 #[derive(Default, Deserialize)]
@@ -42,8 +287,21 @@ pub struct NodeConfigBuilder<B: Backend> {
     pub(crate) logger: LoggerConfigBuilder,
     pub(crate) network: NetworkConfigBuilder,
     pub(crate) peering: PeeringConfigBuilder,
+    #[serde(default)]
+    pub(crate) identity: IdentityConfigBuilder,
     pub(crate) protocol: ProtocolConfigBuilder,
     pub(crate) snapshot: SnapshotConfigBuilder,
+    pub(crate) api: ApiConfigBuilder,
+    #[serde(default)]
+    pub(crate) rpc: RpcConfigBuilder,
+    #[serde(default)]
+    pub(crate) webhook: WebhookConfigBuilder,
+    #[serde(default)]
+    pub(crate) plugins: PluginsConfigBuilder,
+    #[serde(default)]
+    pub(crate) version_checker: VersionCheckerConfigBuilder,
+    #[serde(default)]
+    pub(crate) shutdown: ShutdownConfigBuilder,
     pub(crate) database: B::ConfigBuilder,
 }
 
@@ -56,21 +314,31 @@ impl<B: Backend> NodeConfigBuilder<B> {
         }
     }
 
-    pub fn finish(self) -> NodeConfig<B> {
+    pub fn finish(self) -> Result<NodeConfig<B>, Error> {
         let mut hasher = VarBlake2b::new(32).unwrap();
         let mut network_id: (String, u64) = (self.network_id.unwrap_or_else(|| DEFAULT_NETWORK_ID.to_string()), 0);
         hasher.update(network_id.0.as_bytes());
         hasher.finalize_variable(|res| network_id.1 = u64::from_le_bytes(res[0..8].try_into().unwrap()));
+        let database: B::Config = self.database.into();
+        // The files of the node are kept along the ones of the storage, unless their paths are set.
+        let data_dir = B::path(&database).unwrap_or_else(|| Path::new(".")).to_path_buf();
 
-        NodeConfig {
+        Ok(NodeConfig {
             network_id,
             logger: self.logger.finish(),
             network: self.network.finish(),
             peering: self.peering.finish(),
+            identity: self.identity.finish(&data_dir),
             protocol: self.protocol.finish(),
             snapshot: self.snapshot.finish(),
-            database: self.database.into(),
-        }
+            api: self.api.finish().map_err(|e| Error::InvalidConfig(e.to_string()))?,
+            rpc: self.rpc.finish(),
+            webhook: self.webhook.finish(),
+            plugins: self.plugins.finish(),
+            version_checker: self.version_checker.finish(),
+            shutdown: self.shutdown.finish(&data_dir),
+            database,
+        })
     }
 }
 
@@ -80,7 +348,145 @@ pub struct NodeConfig<B: Backend> {
     pub logger: LoggerConfig,
     pub network: NetworkConfig,
     pub peering: PeeringConfig,
+    pub identity: IdentityConfig,
     pub protocol: ProtocolConfig,
     pub snapshot: SnapshotConfig,
+    pub api: ApiConfig,
+    pub rpc: RpcConfig,
+    pub webhook: WebhookConfig,
+    pub plugins: PluginsConfig,
+    pub version_checker: VersionCheckerConfig,
+    pub shutdown: ShutdownConfig,
     pub database: B::Config,
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use bee_storage_rocksdb::storage::Storage;
+
+    #[test]
+    fn merge_overrides_base() {
+        let mut base = toml::from_str::<toml::Value>("a = 1\n[b]\nc = 2\nd = [1, 2]").unwrap();
+
+        merge(&mut base, toml::from_str("[b]\nd = [3]\ne = 4").unwrap());
+
+        assert_eq!(
+            base,
+            toml::from_str::<toml::Value>("a = 1\n[b]\nc = 2\nd = [3]\ne = 4").unwrap()
+        );
+    }
+
+    #[test]
+    fn override_nests_and_types_values() {
+        let o = Override::new("test".to_string(), "network__peer_limit", "__", "8").unwrap();
+        assert_eq!(
+            o.value,
+            toml::from_str::<toml::Value>("[network]\npeer_limit = 8").unwrap()
+        );
+
+        let o = Override::new(
+            "test".to_string(),
+            "network.bind_address",
+            ".",
+            "/ip4/0.0.0.0/tcp/15600",
+        )
+        .unwrap();
+        assert_eq!(
+            o.value,
+            toml::from_str::<toml::Value>("[network]\nbind_address = \"/ip4/0.0.0.0/tcp/15600\"").unwrap()
+        );
+
+        assert!(Override::new("test".to_string(), "network..peer_limit", ".", "8").is_none());
+        assert!(Override::new("test".to_string(), "", ".", "8").is_none());
+    }
+
+    fn config_file(name: &str, toml: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("bee-config-{}-{}.toml", name, std::process::id()));
+        fs::write(&path, toml).unwrap();
+        path
+    }
+
+    fn vars(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn override_keeps_string_alternative() {
+        let o = Override::new("test".to_string(), "network_id", ".", "1").unwrap();
+        assert_eq!(o.value, toml::from_str::<toml::Value>("network_id = 1").unwrap());
+        assert_eq!(
+            o.string,
+            Some(toml::from_str::<toml::Value>("network_id = \"1\"").unwrap())
+        );
+
+        let o = Override::new("test".to_string(), "network_id", ".", "\"1\"").unwrap();
+        assert_eq!(o.value, toml::from_str::<toml::Value>("network_id = \"1\"").unwrap());
+        assert!(o.string.is_none());
+    }
+
+    #[test]
+    fn load_applies_layers() {
+        let path = config_file("layers", "network_id = \"alphanet2\"\n[network]\npeer_limit = 4");
+        let settings = vec!["network_id=1".to_string(), "api.bind_address=0.0.0.0:14266".to_string()];
+
+        let result = ConfigSource::new(Some("alphanet"), Some(&path), &settings)
+            .load_value_with::<Storage, _>(vars(&[("BEE_NETWORK__PEER_LIMIT", "8"), ("PEER_LIMIT", "16")]));
+        fs::remove_file(path).unwrap();
+
+        match result {
+            Ok((config, builder)) => {
+                assert_eq!(builder.network_id.as_deref(), Some("1"));
+                assert_eq!(config["network"]["peer_limit"].as_integer(), Some(8));
+                assert_eq!(config["api"]["bind_address"].as_str(), Some("0.0.0.0:14266"));
+                // Settings the config file doesn't override come from the profile.
+                assert_eq!(
+                    config["snapshot"]["path"].as_str(),
+                    Some("./snapshots/alphanet/export.bin")
+                );
+            }
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn load_reports_every_issue() {
+        let path = config_file("issues", "[network]\npeer_limt = 4");
+        let settings = vec![
+            "network.peer_limit=eight".to_string(),
+            "network.bind_adress=/ip4/0.0.0.0/tcp/15600".to_string(),
+            "api".to_string(),
+        ];
+
+        let result = ConfigSource::new(Some("alphanet"), Some(&path), &settings)
+            .load_value_with::<Storage, _>(vars(&[("BEE_NETWORK____PEER_LIMIT", "8")]));
+        fs::remove_file(path).unwrap();
+
+        match result {
+            Err(Error::InvalidConfig(report)) => {
+                assert!(report.contains("setting network.peer_limit=eight: invalid type"));
+                assert!(report
+                    .contains("setting network.bind_adress=/ip4/0.0.0.0/tcp/15600: unknown key network.bind_adress"));
+                assert!(report.contains("setting api: expected key=value"));
+                assert!(report.contains("environment variable BEE_NETWORK____PEER_LIMIT: invalid key"));
+                assert!(report.contains("config file: unknown key network.peer_limt"));
+            }
+            Err(e) => panic!("{}", e),
+            Ok(_) => panic!("invalid settings were accepted"),
+        }
+    }
+
+    #[test]
+    fn load_requires_explicit_config_file() {
+        let path = env::temp_dir().join(format!("bee-config-missing-{}.toml", std::process::id()));
+
+        assert!(matches!(
+            ConfigSource::new(Some("alphanet"), Some(&path), &[]).load_value_with::<Storage, _>(Vec::new()),
+            Err(Error::ConfigFileReadFailure(_))
+        ));
+    }
+}
@@ -0,0 +1,405 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::{config::ConfigSource, inner::BeeNode, log_level, storage::Backend};
+
+use bee_common::shutdown_stream::ShutdownStream;
+use bee_common_ext::{node::Node, worker::Worker};
+use bee_peering::{ManualPeeringConfig, ManualPeeringConfigBuilder, ManualPeers};
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use log::{error, info, warn, LevelFilter};
+use tokio::time::interval;
+
+use std::{
+    collections::BTreeSet,
+    convert::Infallible,
+    fs,
+    path::Path,
+    str::FromStr,
+    time::{Duration, SystemTime},
+};
+
+const WATCH_INTERVAL_SEC: u64 = 2;
+// Values of keys containing one of these are not logged.
+const SECRET_KEYS: [&str; 4] = ["keypair", "passphrase", "password", "secret"];
+
+/// A value of the config that differs from the one applied.
+struct Change {
+    key: String,
+    old: Option<toml::Value>,
+    new: Option<toml::Value>,
+}
+
+pub(crate) struct ConfigWatcherWorker;
+
+#[async_trait]
+impl<B: Backend> Worker<BeeNode<B>> for ConfigWatcherWorker {
+    type Config = (ConfigSource, ManualPeers, flume::Sender<ManualPeeringConfig>);
+    type Error = Infallible;
+
+    async fn start(node: &mut BeeNode<B>, config: Self::Config) -> Result<Self, Self::Error> {
+        let (source, manual_peers, manual_peers_sender) = config;
+
+        node.spawn::<Self, _, _>(|shutdown| async move {
+            info!("Running.");
+
+            let mut modified = modified(source.path());
+            let mut applied = match source.load_value::<B>() {
+                Ok((config, _)) => config,
+                Err(e) => {
+                    error!("Loading the config failed, changes won't be applied: {}", e);
+                    info!("Stopped.");
+                    return;
+                }
+            };
+
+            let mut ticker = ShutdownStream::new(shutdown, interval(Duration::from_secs(WATCH_INTERVAL_SEC)));
+
+            while ticker.next().await.is_some() {
+                let latest = modified(source.path());
+                if latest == modified {
+                    continue;
+                }
+                modified = latest;
+
+                info!("Config file {} changed, reloading.", source.path().display());
+
+                match source.load_value::<B>() {
+                    Ok((config, _)) => apply(&mut applied, config, &manual_peers, &manual_peers_sender),
+                    Err(e) => error!("Reloading the config failed, keeping the current one: {}", e),
+                }
+            }
+
+            info!("Stopped.");
+        });
+
+        Ok(Self)
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+/// Applies the changes of the manual peers and of the log levels, and rejects every other one.
+fn apply(
+    applied: &mut toml::Value,
+    config: toml::Value,
+    manual_peers: &ManualPeers,
+    manual_peers_sender: &flume::Sender<ManualPeeringConfig>,
+) {
+    let mut changes = Vec::new();
+    diff("", Some(&*applied), Some(&config), &mut changes);
+
+    if changes.is_empty() {
+        info!("No config change to apply.");
+        return;
+    }
+
+    let mut peers_changed = false;
+    let mut levels_changed = false;
+    let mut rejected = Vec::new();
+
+    for change in changes {
+        if change.key == "peering.manual" || change.key.starts_with("peering.manual.") {
+            peers_changed = true;
+        } else if change.key == "logger.outputs" && output_names(applied) == output_names(&config) {
+            levels_changed = true;
+        } else {
+            rejected.push(change);
+        }
+    }
+
+    if peers_changed {
+        match manual_peering(&config) {
+            Ok(manual) => {
+                *manual_peers.write().unwrap() = manual.clone();
+                if manual_peers_sender.send(manual).is_err() {
+                    warn!("Updating the manual peers failed: the manual peer manager stopped.");
+                }
+                adopt(applied, &config, &["peering", "manual"]);
+            }
+            Err(e) => error!("Rejected the change of peering.manual: {}.", e),
+        }
+    }
+
+    if levels_changed {
+        // The outputs can't change their own levels while running, only the highest level across them applies.
+        let level = max_level(&config);
+
+        if level == max_level(applied) {
+            warn!(
+                "Rejected the change of logger.outputs, which requires a restart: only the highest level across \
+                 outputs can change while running and it remains {}.",
+                level
+            );
+        } else {
+            match log_level::set(level) {
+                Ok(previous) => {
+                    info!(
+                        "Log level changed from {} to {}, the highest level across outputs, for every output.",
+                        previous, level
+                    );
+                    adopt(applied, &config, &["logger", "outputs"]);
+                }
+                Err(ceiling) => warn!(
+                    "Rejected the change of logger.outputs, which requires a restart: {} is above {}, the level the \
+                     logger was set up with.",
+                    level, ceiling
+                ),
+            }
+        }
+    }
+
+    for change in rejected {
+        warn!(
+            "Rejected the change of {}, which requires a restart: {} -> {}.",
+            change.key,
+            display(&change.key, change.old.as_ref()),
+            display(&change.key, change.new.as_ref())
+        );
+    }
+}
+
+/// Lists the keys whose values differ between `old` and `new`, recursing into tables.
+fn diff(key: &str, old: Option<&toml::Value>, new: Option<&toml::Value>, changes: &mut Vec<Change>) {
+    let empty = toml::value::Table::new();
+    let as_table = |value: Option<&toml::Value>| match value {
+        Some(value) => value.as_table().cloned(),
+        None => Some(empty.clone()),
+    };
+
+    if old.or(new).map_or(false, toml::Value::is_table) {
+        if let (Some(old), Some(new)) = (as_table(old), as_table(new)) {
+            for name in old.keys().chain(new.keys()).collect::<BTreeSet<_>>() {
+                let path = if key.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}.{}", key, name)
+                };
+                diff(&path, old.get(name), new.get(name), changes);
+            }
+            return;
+        }
+    }
+
+    if old != new {
+        changes.push(Change {
+            key: key.to_string(),
+            old: old.cloned(),
+            new: new.cloned(),
+        });
+    }
+}
+
+/// Sets the value at `path` of `applied` to the one of `config`, removing it if `config` has none.
+fn adopt(applied: &mut toml::Value, config: &toml::Value, path: &[&str]) {
+    let (last, parents) = match path.split_last() {
+        Some(split) => split,
+        None => return,
+    };
+    let mut applied = applied;
+    let mut config = Some(config);
+
+    for key in parents {
+        config = config.and_then(|config| config.get(*key));
+        applied = match applied.as_table_mut() {
+            Some(table) => table
+                .entry(key.to_string())
+                .or_insert_with(|| toml::Value::Table(toml::value::Table::new())),
+            None => return,
+        };
+    }
+
+    if let Some(table) = applied.as_table_mut() {
+        match config.and_then(|config| config.get(*last)) {
+            Some(value) => {
+                table.insert(last.to_string(), value.clone());
+            }
+            None => {
+                table.remove(*last);
+            }
+        }
+    }
+}
+
+fn outputs(config: &toml::Value) -> Vec<&toml::Value> {
+    config
+        .get("logger")
+        .and_then(|logger| logger.get("outputs"))
+        .and_then(toml::Value::as_array)
+        .map(|outputs| outputs.iter().collect())
+        .unwrap_or_default()
+}
+
+fn output_names(config: &toml::Value) -> Vec<Option<&str>> {
+    outputs(config)
+        .into_iter()
+        .map(|output| output.get("name").and_then(toml::Value::as_str))
+        .collect()
+}
+
+fn output_levels(config: &toml::Value) -> Vec<LevelFilter> {
+    outputs(config)
+        .into_iter()
+        .filter_map(|output| output.get("level").and_then(toml::Value::as_str))
+        .filter_map(|level| LevelFilter::from_str(level).ok())
+        .collect()
+}
+
+fn max_level(config: &toml::Value) -> LevelFilter {
+    output_levels(config).into_iter().max().unwrap_or(LevelFilter::Off)
+}
+
+fn manual_peering(config: &toml::Value) -> Result<ManualPeeringConfig, String> {
+    let builder = config
+        .get("peering")
+        .and_then(|peering| peering.get("manual"))
+        .cloned()
+        .unwrap_or_else(|| toml::Value::Table(toml::value::Table::new()))
+        .try_into::<ManualPeeringConfigBuilder>()
+        .map_err(|e| e.to_string())?;
+
+    builder.validate()?;
+
+    Ok(builder.finish())
+}
+
+fn is_secret(key: &str) -> bool {
+    SECRET_KEYS.iter().any(|secret| key.contains(secret))
+}
+
+fn contains_secret(value: &toml::Value) -> bool {
+    match value {
+        toml::Value::Table(table) => table
+            .iter()
+            .any(|(key, value)| is_secret(key) || contains_secret(value)),
+        toml::Value::Array(values) => values.iter().any(contains_secret),
+        _ => false,
+    }
+}
+
+fn display(key: &str, value: Option<&toml::Value>) -> String {
+    match value {
+        None => "<unset>".to_string(),
+        Some(value) if is_secret(key.rsplit('.').next().unwrap_or(key)) || contains_secret(value) => {
+            "<hidden>".to_string()
+        }
+        Some(value) => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use bee_network::PeerId;
+
+    use std::sync::{Arc, RwLock};
+
+    fn keys(old: &str, new: &str) -> Vec<String> {
+        let mut changes = Vec::new();
+        diff(
+            "",
+            Some(&toml::from_str(old).unwrap()),
+            Some(&toml::from_str(new).unwrap()),
+            &mut changes,
+        );
+        changes.into_iter().map(|change| change.key).collect()
+    }
+
+    #[test]
+    fn diff_lists_changed_keys() {
+        assert_eq!(
+            keys(
+                "a = 1\n[b]\nc = 2\nd = [1]",
+                "a = 1\n[b]\nc = 3\nd = [1, 2]\n[e]\nf = \"g\""
+            ),
+            vec!["b.c", "b.d", "e.f"]
+        );
+        assert!(keys("[b]\nc = 2", "[b]\nc = 2").is_empty());
+    }
+
+    #[test]
+    fn adopt_replaces_and_removes() {
+        let mut applied = toml::from_str::<toml::Value>("[peering]\nx = 1\n[peering.manual]\nlimit = 5").unwrap();
+
+        adopt(
+            &mut applied,
+            &toml::from_str("[peering.manual]\nlimit = 8").unwrap(),
+            &["peering", "manual"],
+        );
+        assert_eq!(
+            applied,
+            toml::from_str::<toml::Value>("[peering]\nx = 1\n[peering.manual]\nlimit = 8").unwrap()
+        );
+
+        adopt(&mut applied, &toml::from_str("").unwrap(), &["peering", "manual"]);
+        assert_eq!(applied, toml::from_str::<toml::Value>("[peering]\nx = 1").unwrap());
+    }
+
+    #[test]
+    fn secrets_are_hidden() {
+        assert_eq!(display("identity.passphrase", Some(&"p".into())), "<hidden>");
+        assert_eq!(
+            display(
+                "webhook.hooks",
+                Some(&toml::from_str("h = [{ secret = \"s\" }]").unwrap())
+            ),
+            "<hidden>"
+        );
+        assert_eq!(display("network.bind_address", Some(&"a".into())), "\"a\"");
+        assert_eq!(display("network.bind_address", None), "<unset>");
+    }
+
+    #[test]
+    fn apply_updates_aliases() {
+        let id = PeerId::random();
+        let config = |alias: &str| {
+            toml::from_str::<toml::Value>(&format!(
+                "[peering.manual]\npeers = [{{ address = \"/ip4/1.2.3.4/tcp/15600/p2p/{}\", alias = \"{}\" }}]",
+                id, alias
+            ))
+            .unwrap()
+        };
+        let mut applied = config("alice");
+        let manual_peers = Arc::new(RwLock::new(manual_peering(&applied).unwrap()));
+        let (sender, receiver) = flume::unbounded();
+
+        apply(&mut applied, config("bob"), &manual_peers, &sender);
+
+        assert_eq!(manual_peers.read().unwrap().alias(&id), Some("bob"));
+        assert_eq!(receiver.try_recv().unwrap().alias(&id), Some("bob"));
+        assert_eq!(applied, config("bob"));
+    }
+
+    #[test]
+    fn apply_rejects_levels_below_the_highest() {
+        let config = |levels: (&str, &str)| {
+            toml::from_str::<toml::Value>(&format!(
+                "[[logger.outputs]]\nname = \"stdout\"\nlevel = \"{}\"\n[[logger.outputs]]\nname = \"bee.log\"\n\
+                 level = \"{}\"",
+                levels.0, levels.1
+            ))
+            .unwrap()
+        };
+        let mut applied = config(("info", "debug"));
+        let manual_peers = Arc::new(RwLock::new(ManualPeeringConfigBuilder::default().finish()));
+        let (sender, _receiver) = flume::unbounded();
+
+        apply(&mut applied, config(("warn", "debug")), &manual_peers, &sender);
+
+        assert_eq!(applied, config(("info", "debug")));
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use serde::Deserialize;
+
+use std::path::{Path, PathBuf};
+
+const DEFAULT_FILE_NAME: &str = "identity.key";
+
+#[derive(Default, Deserialize)]
+pub struct IdentityConfigBuilder {
+    path: Option<PathBuf>,
+    passphrase: Option<String>,
+}
+
+impl IdentityConfigBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn path(mut self, path: PathBuf) -> Self {
+        self.path.replace(path);
+        self
+    }
+
+    pub fn passphrase(mut self, passphrase: String) -> Self {
+        self.passphrase.replace(passphrase);
+        self
+    }
+
+    /// Finishes the config, the key file being kept in `dir` unless a path is set.
+    pub fn finish(self, dir: &Path) -> IdentityConfig {
+        IdentityConfig {
+            path: self.path.unwrap_or_else(|| dir.join(DEFAULT_FILE_NAME)),
+            passphrase: self.passphrase.filter(|passphrase| !passphrase.is_empty()),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct IdentityConfig {
+    pub(crate) path: PathBuf,
+    pub(crate) passphrase: Option<String>,
+}
+
+impl IdentityConfig {
+    pub fn build() -> IdentityConfigBuilder {
+        IdentityConfigBuilder::new()
+    }
+
+    /// Path of the key file, only accessible to the user running the node.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Passphrase the key file is encrypted with, if any.
+    pub fn passphrase(&self) -> Option<&str> {
+        self.passphrase.as_deref()
+    }
+}
@@ -0,0 +1,270 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+mod config;
+
+pub use config::{IdentityConfig, IdentityConfigBuilder};
+
+use crate::config::NodeConfig;
+
+use bee_network::Keypair;
+use bee_storage::storage::Backend;
+
+use chacha20poly1305::{
+    aead::{Aead, NewAead},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use hmac::Hmac;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use thiserror::Error;
+
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, ErrorKind, Write},
+    os::unix::fs::OpenOptionsExt,
+};
+
+const KDF_ITERATIONS: u32 = 100_000;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Accessing the identity file failed: {0}.")]
+    Io(#[from] io::Error),
+
+    #[error("The identity file is malformed: {0}.")]
+    Malformed(String),
+
+    #[error("The identity file is encrypted, a passphrase must be configured in the identity section.")]
+    MissingPassphrase,
+
+    #[error("Decrypting the identity file failed, the passphrase is probably wrong.")]
+    Decryption,
+
+    #[error("Invalid keypair: {0}.")]
+    InvalidKeypair(String),
+
+    #[error("An identity file already exists at {0}, use --force to replace it.")]
+    AlreadyExists(String),
+}
+
+/// Content of the key file.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+enum KeyFile {
+    Plain {
+        keypair: String,
+    },
+    /// The keypair is encrypted with ChaCha20-Poly1305 under a key derived from the passphrase with
+    /// PBKDF2-HMAC-SHA256.
+    Encrypted {
+        iterations: u32,
+        salt: String,
+        nonce: String,
+        ciphertext: String,
+    },
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> Key {
+    let mut key = Key::default();
+    pbkdf2::pbkdf2::<Hmac<Sha256>>(passphrase.as_bytes(), salt, iterations, &mut key);
+    key
+}
+
+fn decode_hex(field: &str, value: &str) -> Result<Vec<u8>, Error> {
+    hex::decode(value.trim()).map_err(|e| Error::Malformed(format!("{}: {}", field, e)))
+}
+
+/// Decodes a hex encoded keypair, as found in the `local_keypair` setting of the peering section.
+pub(crate) fn decode(keypair: &str) -> Result<Keypair, Error> {
+    let mut bytes = hex::decode(keypair.trim()).map_err(|e| Error::InvalidKeypair(e.to_string()))?;
+    Keypair::decode(&mut bytes).map_err(|e| Error::InvalidKeypair(e.to_string()))
+}
+
+/// Reads the keypair of the key file, `None` if there is no key file.
+pub(crate) fn load(config: &IdentityConfig) -> Result<Option<Keypair>, Error> {
+    let content = match fs::read(config.path()) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(Error::Io(e)),
+    };
+
+    let mut bytes = match serde_json::from_slice(&content).map_err(|e| Error::Malformed(e.to_string()))? {
+        KeyFile::Plain { keypair } => decode_hex("keypair", &keypair)?,
+        KeyFile::Encrypted {
+            iterations,
+            salt,
+            nonce,
+            ciphertext,
+        } => {
+            let passphrase = config.passphrase().ok_or(Error::MissingPassphrase)?;
+            let nonce = decode_hex("nonce", &nonce)?;
+            if nonce.len() != 12 {
+                return Err(Error::Malformed(format!("nonce: invalid length {}", nonce.len())));
+            }
+
+            ChaCha20Poly1305::new(&derive_key(passphrase, &decode_hex("salt", &salt)?, iterations))
+                .decrypt(
+                    Nonce::from_slice(&nonce),
+                    decode_hex("ciphertext", &ciphertext)?.as_ref(),
+                )
+                .map_err(|_| Error::Decryption)?
+        }
+    };
+
+    Keypair::decode(&mut bytes)
+        .map(Some)
+        .map_err(|e| Error::Malformed(format!("keypair: {}", e)))
+}
+
+/// Writes the keypair to the key file, encrypted if a passphrase is configured.
+pub(crate) fn store(config: &IdentityConfig, keypair: &Keypair) -> Result<(), Error> {
+    let bytes = keypair.encode();
+
+    let key_file = match config.passphrase() {
+        Some(passphrase) => {
+            let salt = rand::random::<[u8; 16]>();
+            let nonce = rand::random::<[u8; 12]>();
+            let ciphertext = ChaCha20Poly1305::new(&derive_key(passphrase, &salt, KDF_ITERATIONS))
+                .encrypt(Nonce::from_slice(&nonce), bytes.as_ref())
+                .expect("encrypting a keypair can not fail");
+
+            KeyFile::Encrypted {
+                iterations: KDF_ITERATIONS,
+                salt: hex::encode(salt),
+                nonce: hex::encode(nonce),
+                ciphertext: hex::encode(ciphertext),
+            }
+        }
+        None => KeyFile::Plain {
+            keypair: hex::encode(&bytes[..]),
+        },
+    };
+
+    let path = config.path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    // Creates the file with restricted permissions before writing the keypair, then atomically replaces the previous
+    // key file. A leftover file is removed since it could have been created with broader permissions.
+    let tmp_path = path.with_extension("tmp");
+    match fs::remove_file(&tmp_path) {
+        Err(e) if e.kind() != ErrorKind::NotFound => return Err(e.into()),
+        _ => (),
+    }
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(&tmp_path)?;
+    file.write_all(&serde_json::to_vec_pretty(&key_file).map_err(io::Error::from)?)?;
+    file.sync_all()?;
+
+    Ok(fs::rename(&tmp_path, path)?)
+}
+
+/// Returns the keypair of the node and whether it was just generated.
+/// A `local_keypair` set in the peering section takes precedence over the key file, which is otherwise created on
+/// first use.
+pub(crate) fn keypair<B: Backend>(config: &NodeConfig<B>) -> Result<(Keypair, bool), Error> {
+    let (configured, _, generated) = &config.peering.local_keypair;
+
+    if !generated {
+        return Ok((configured.clone(), false));
+    }
+
+    if let Some(keypair) = load(&config.identity)? {
+        return Ok((keypair, false));
+    }
+
+    let keypair = Keypair::generate();
+    store(&config.identity, &keypair)?;
+
+    Ok((keypair, true))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use std::{
+        env,
+        os::unix::fs::PermissionsExt,
+        path::{Path, PathBuf},
+    };
+
+    fn config(name: &str, passphrase: Option<&str>) -> IdentityConfig {
+        let path = env::temp_dir().join(format!("bee-identity-{}-{}.key", name, std::process::id()));
+        let builder = IdentityConfig::build().path(path);
+
+        match passphrase {
+            Some(passphrase) => builder.passphrase(passphrase.to_string()).finish(&env::temp_dir()),
+            None => builder.finish(&env::temp_dir()),
+        }
+    }
+
+    fn cleanup(path: PathBuf) {
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn store_load_plain() {
+        let config = config("plain", None);
+        let keypair = Keypair::generate();
+
+        assert!(load(&config).unwrap().is_none());
+        store(&config, &keypair).unwrap();
+
+        assert_eq!(load(&config).unwrap().unwrap().encode()[..], keypair.encode()[..]);
+        assert_eq!(fs::metadata(config.path()).unwrap().permissions().mode() & 0o777, 0o600);
+
+        cleanup(config.path().to_path_buf());
+    }
+
+    #[test]
+    fn store_load_encrypted() {
+        let config = config("encrypted", Some("correct horse battery staple"));
+        let keypair = Keypair::generate();
+
+        store(&config, &keypair).unwrap();
+
+        assert_eq!(load(&config).unwrap().unwrap().encode()[..], keypair.encode()[..]);
+        assert!(matches!(
+            load(
+                &IdentityConfig::build()
+                    .path(config.path().to_path_buf())
+                    .finish(&env::temp_dir())
+            ),
+            Err(Error::MissingPassphrase)
+        ));
+        assert!(matches!(
+            load(
+                &IdentityConfig::build()
+                    .path(config.path().to_path_buf())
+                    .passphrase("wrong".to_string())
+                    .finish(&env::temp_dir())
+            ),
+            Err(Error::Decryption)
+        ));
+
+        cleanup(config.path().to_path_buf());
+    }
+
+    #[test]
+    fn default_path_in_dir() {
+        assert_eq!(
+            IdentityConfig::build().finish(Path::new("./database/alphanet")).path(),
+            Path::new("./database/alphanet/identity.key")
+        );
+    }
+}
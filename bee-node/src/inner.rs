@@ -9,7 +9,7 @@
 // an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and limitations under the License.
 
-use crate::storage::Backend;
+use crate::{shutdown::ShutdownConfig, storage::Backend};
 
 use bee_common::shutdown;
 use bee_common_ext::{
@@ -20,21 +20,31 @@ use bee_common_ext::{
 
 use anymap::{any::Any as AnyMapAny, Map};
 use async_trait::async_trait;
-use futures::{channel::oneshot, future::Future};
-use log::info;
-use tokio::spawn;
+use futures::{
+    channel::oneshot,
+    future::{Future, FutureExt},
+};
+use log::{error, info, warn};
+use tokio::{spawn, time};
 
 use std::{
     any::{type_name, Any, TypeId},
     collections::{HashMap, HashSet},
     marker::PhantomData,
+    panic::AssertUnwindSafe,
     pin::Pin,
 };
 
 type WorkerStart<N> = dyn for<'a> FnOnce(&'a mut N) -> Pin<Box<dyn Future<Output = ()> + 'a>>;
-type WorkerStop<N> = dyn for<'a> FnOnce(&'a mut N) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>> + Send;
+type WorkerStop<N> = dyn for<'a> FnOnce(&'a mut N) -> Pin<Box<dyn Future<Output = bool> + Send + 'a>> + Send;
 type ResourceRegister<N> = dyn for<'a> FnOnce(&'a mut N);
 
+/// Name of a worker type along with the names of the workers it depends on.
+pub(crate) struct WorkerInfo {
+    pub(crate) name: &'static str,
+    pub(crate) dependencies: Vec<&'static str>,
+}
+
 #[allow(clippy::type_complexity)]
 pub struct BeeNode<B> {
     workers: Map<dyn AnyMapAny + Send + Sync>,
@@ -49,6 +59,8 @@ pub struct BeeNode<B> {
     resources: Map<dyn AnyMapAny + Send + Sync>,
     worker_stops: HashMap<TypeId, Box<WorkerStop<Self>>>,
     worker_order: Vec<TypeId>,
+    worker_names: HashMap<TypeId, &'static str>,
+    worker_deps: HashMap<TypeId, &'static [TypeId]>,
     phantom: PhantomData<B>,
 }
 
@@ -62,6 +74,97 @@ impl<B: Backend> BeeNode<B> {
             .remove()
             .unwrap_or_else(|| panic!("Failed to remove worker `{}`", type_name::<W>()))
     }
+
+    /// Returns the registered workers in the order they are started.
+    pub(crate) fn worker_infos(&self) -> Vec<WorkerInfo> {
+        let name = |id: &TypeId| self.worker_names.get(id).copied().unwrap_or("unknown");
+
+        self.worker_order
+            .iter()
+            .map(|id| WorkerInfo {
+                name: name(id),
+                dependencies: self
+                    .worker_deps
+                    .get(id)
+                    .map(|deps| deps.iter().map(name).collect())
+                    .unwrap_or_default(),
+            })
+            .collect()
+    }
+
+    /// Stops the workers in the reverse order of their start, each within its configured timeout, then flushes the
+    /// storage if it is still alive, whether it is still registered or held elsewhere after a failed stop. Returns
+    /// whether every worker stopped in time and without failure.
+    pub(crate) async fn stop_gracefully(mut self) -> bool {
+        let config = self
+            .resources
+            .get::<ResHandle<ShutdownConfig>>()
+            .map(|config| (**config).clone())
+            .unwrap_or_default();
+        // A weak handle, so that the storage worker can still take ownership of the storage to shut it down.
+        let storage = self
+            .resources
+            .get::<ResHandle<B>>()
+            .map(|storage| storage.clone().into_weak());
+        let mut clean = true;
+
+        for worker_id in self.worker_order.clone().into_iter().rev() {
+            let name = self.worker_names.get(&worker_id).copied().unwrap_or("unknown");
+            let timeout = config.worker_timeout(name);
+            let stop = match self.worker_stops.remove(&worker_id) {
+                Some(stop) => stop,
+                None => {
+                    error!("Worker `{}` has no stop registered, skipping it.", name);
+                    clean = false;
+                    continue;
+                }
+            };
+            let tasks = self.tasks.remove(&worker_id).unwrap_or_default();
+            let node = &mut self;
+
+            let stopping = async move {
+                let mut stopped = true;
+                let mut task_futs = Vec::with_capacity(tasks.len());
+
+                for (shutdown, task_fut) in tasks {
+                    let _ = shutdown.send(());
+                    task_futs.push(task_fut);
+                }
+                for task_fut in task_futs {
+                    if let Err(e) = task_fut.await {
+                        error!("A task of worker `{}` failed: {}.", name, e);
+                        stopped = false;
+                    }
+                }
+
+                stop(node).await && stopped
+            };
+
+            match time::timeout(timeout, AssertUnwindSafe(stopping).catch_unwind()).await {
+                Ok(Ok(stopped)) => clean &= stopped,
+                Ok(Err(_)) => {
+                    error!("Worker `{}` panicked while stopping.", name);
+                    clean = false;
+                }
+                Err(_) => {
+                    error!("Worker `{}` didn't stop within {:?}, moving on.", name, timeout);
+                    clean = false;
+                }
+            }
+
+            self.resource::<Bus>().purge_worker_listeners(worker_id);
+        }
+
+        if let Some(storage) = storage.and_then(|storage| storage.upgrade()) {
+            warn!("The storage wasn't shut down by its worker, flushing it.");
+            if let Err(e) = storage.flush() {
+                error!("Flushing the storage failed: {}", e);
+                clean = false;
+            }
+        }
+
+        clean
+    }
 }
 
 #[async_trait]
@@ -69,19 +172,11 @@ impl<B: Backend> Node for BeeNode<B> {
     type Builder = BeeNodeBuilder<B>;
     type Backend = B;
 
-    async fn stop(mut self) -> Result<(), shutdown::Error>
+    async fn stop(self) -> Result<(), shutdown::Error>
     where
         Self: Sized,
     {
-        for worker_id in self.worker_order.clone().into_iter().rev() {
-            for (shutdown, task_fut) in self.tasks.remove(&worker_id).unwrap_or_default() {
-                let _ = shutdown.send(());
-                // TODO: Should we handle this error?
-                let _ = task_fut.await; //.map_err(|e| shutdown::Error::from(worker::Error(Box::new(e))))?;
-            }
-            self.worker_stops.remove(&worker_id).unwrap()(&mut self).await;
-            self.resource::<Bus>().purge_worker_listeners(worker_id);
-        }
+        self.stop_gracefully().await;
 
         Ok(())
     }
@@ -128,6 +223,7 @@ impl<B: Backend> Node for BeeNode<B> {
 
 pub struct BeeNodeBuilder<B: Backend> {
     deps: HashMap<TypeId, &'static [TypeId]>,
+    names: HashMap<TypeId, &'static str>,
     worker_starts: HashMap<TypeId, Box<WorkerStart<BeeNode<B>>>>,
     worker_stops: HashMap<TypeId, Box<WorkerStop<BeeNode<B>>>>,
     resource_registers: Vec<Box<ResourceRegister<BeeNode<B>>>>,
@@ -137,6 +233,7 @@ impl<B: Backend> Default for BeeNodeBuilder<B> {
     fn default() -> Self {
         Self {
             deps: HashMap::default(),
+            names: HashMap::default(),
             worker_starts: HashMap::default(),
             worker_stops: HashMap::default(),
             resource_registers: Vec::default(),
@@ -156,6 +253,7 @@ impl<B: Backend> NodeBuilder<BeeNode<B>> for BeeNodeBuilder<B> {
 
     fn with_worker_cfg<W: Worker<BeeNode<B>> + 'static>(mut self, config: W::Config) -> Self {
         self.deps.insert(TypeId::of::<W>(), W::dependencies());
+        self.names.insert(TypeId::of::<W>(), type_name::<W>());
         self.worker_starts.insert(
             TypeId::of::<W>(),
             Box::new(|node| {
@@ -174,8 +272,11 @@ impl<B: Backend> NodeBuilder<BeeNode<B>> for BeeNodeBuilder<B> {
                 Box::pin(async move {
                     info!("Stopping worker `{}`...", type_name::<W>());
                     match node.remove_worker::<W>().stop(node).await {
-                        Ok(()) => {}
-                        Err(e) => panic!("Worker `{}` failed to stop: {:?}.", type_name::<W>(), e),
+                        Ok(()) => true,
+                        Err(e) => {
+                            error!("Worker `{}` failed to stop: {:?}.", type_name::<W>(), e);
+                            false
+                        }
                     }
                 })
             }),
@@ -197,7 +298,9 @@ impl<B: Backend> NodeBuilder<BeeNode<B>> for BeeNodeBuilder<B> {
             resources: Map::new(),
             phantom: PhantomData,
             worker_stops: self.worker_stops,
-            worker_order: TopologicalOrder::sort(self.deps),
+            worker_order: TopologicalOrder::sort(self.deps.clone()),
+            worker_names: self.names,
+            worker_deps: self.deps,
         };
 
         for f in self.resource_registers {
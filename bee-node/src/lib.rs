@@ -11,15 +11,26 @@
 
 mod banner;
 mod cli;
+mod command;
 mod config;
+mod config_watcher;
 mod constants;
+mod identity;
 mod inner;
+mod log_level;
 mod node;
 mod plugin;
+mod profile;
+mod rpc;
+mod shutdown;
 mod storage;
 mod version_checker;
+mod webhook;
 
 pub use banner::print_banner_and_version;
-pub use cli::CliArgs;
-pub use config::NodeConfigBuilder;
-pub use node::{Error, Node};
+pub use cli::{CliArgs, Command, IdentityCommand};
+pub use command::{exec as exec_command, Error as CommandError};
+pub use config::{ConfigSource, NodeConfigBuilder};
+pub use node::{Error, Node, NodeBuilder};
+pub use plugin::Plugin;
+pub use version_checker::NewVersionAvailable;
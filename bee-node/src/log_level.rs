@@ -0,0 +1,71 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+//! Changes of the log level while running.
+//!
+//! The logger outputs keep the levels they were set up with, changes therefore only apply at the `log` facade: they can
+//! lower the verbosity, but can't raise it above the most verbose output.
+
+use log::LevelFilter;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const LEVELS: [LevelFilter; 6] = [
+    LevelFilter::Off,
+    LevelFilter::Error,
+    LevelFilter::Warn,
+    LevelFilter::Info,
+    LevelFilter::Debug,
+    LevelFilter::Trace,
+];
+
+static CEILING: AtomicUsize = AtomicUsize::new(LevelFilter::Trace as usize);
+
+/// Records the current level, as set up by the logger, as the most verbose one changes can go up to.
+pub(crate) fn init() {
+    CEILING.store(log::max_level() as usize, Ordering::Relaxed);
+}
+
+/// The most verbose level the logger outputs were set up with.
+pub(crate) fn ceiling() -> LevelFilter {
+    LEVELS[CEILING.load(Ordering::Relaxed)]
+}
+
+/// Sets the level and returns the previous one, or the ceiling if the level is more verbose than it.
+pub(crate) fn set(level: LevelFilter) -> Result<LevelFilter, LevelFilter> {
+    let ceiling = ceiling();
+
+    if level > ceiling {
+        return Err(ceiling);
+    }
+
+    let previous = log::max_level();
+    log::set_max_level(level);
+
+    Ok(previous)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn bounded_by_ceiling() {
+        log::set_max_level(LevelFilter::Info);
+        init();
+
+        assert_eq!(set(LevelFilter::Warn), Ok(LevelFilter::Info));
+        assert_eq!(set(LevelFilter::Info), Ok(LevelFilter::Warn));
+        assert_eq!(set(LevelFilter::Debug), Err(LevelFilter::Info));
+        assert_eq!(log::max_level(), LevelFilter::Info);
+    }
+}
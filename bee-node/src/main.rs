@@ -10,22 +10,38 @@
 // See the License for the specific language governing permissions and limitations under the License.
 
 use bee_common::logger::logger_init;
-use bee_node::{CliArgs, Node, NodeConfigBuilder};
+use bee_node::{exec_command, CliArgs, ConfigSource, Node};
 
 use log::error;
 
-const CONFIG_PATH: &str = "./config.toml";
-
 #[tokio::main]
 async fn main() {
-    match NodeConfigBuilder::from_file(CONFIG_PATH) {
+    let args = CliArgs::default();
+
+    let config_source = ConfigSource::new(args.network(), args.config(), args.settings());
+
+    match config_source.load() {
         Ok(mut config_builder) => {
-            CliArgs::default().apply_to_config(&mut config_builder);
-            let config = config_builder.finish();
+            args.apply_to_config(&mut config_builder);
+            let config = match config_builder.finish() {
+                Ok(config) => config,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+            };
+
+            if let Some(command) = args.command() {
+                if let Err(e) = exec_command::<bee_storage_rocksdb::storage::Storage>(command, &config) {
+                    eprintln!("{}", e);
+                }
+                return;
+            }
 
             logger_init(config.logger.clone()).unwrap();
 
             match Node::<bee_storage_rocksdb::storage::Storage>::builder(config)
+                .with_config_source(config_source)
                 .finish()
                 .await
             {
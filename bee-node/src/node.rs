@@ -12,8 +12,19 @@
 #![warn(missing_docs)]
 
 use crate::{
-    banner::print_banner_and_version, config::NodeConfig, inner::BeeNode, storage::Backend,
+    banner::print_banner_and_version,
+    config::{ConfigSource, NodeConfig},
+    config_watcher::ConfigWatcherWorker,
+    constants::BEE_VERSION,
+    identity,
+    inner::{BeeNode, BeeNodeBuilder},
+    log_level,
+    plugin::{CoordinatorPlugin, Plugin, PluginsConfig, TpsPlugin},
+    rpc::RpcWorker,
+    shutdown,
+    storage::Backend,
     version_checker::VersionCheckerWorker,
+    webhook::WebhookWorker,
 };
 
 use bee_common::shutdown_stream::ShutdownStream;
@@ -27,13 +38,22 @@ use bee_protocol::Protocol;
 
 use futures::{
     channel::oneshot,
+    future::{self, FutureExt},
+    select,
     stream::{Fuse, StreamExt},
 };
 use log::{error, info, trace, warn};
+use serde::de::DeserializeOwned;
 use thiserror::Error;
-use tokio::spawn;
+use tokio::{
+    signal::unix::{signal, SignalKind},
+    spawn,
+};
 
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
 
 type NetworkEventStream = ShutdownStream<Fuse<flume::r#async::RecvStream<'static, Event>>>;
 
@@ -51,18 +71,105 @@ pub enum Error {
     #[error("The snapshot network {0} doesn't match the configuration network {1}.")]
     NetworkMismatch(u64, u64),
 
+    /// Occurs when the identity of the node can't be loaded or stored.
+    #[error("Loading the node identity failed: {0}")]
+    IdentityError(identity::Error),
+
+    /// Occurs when an enabled plugin isn't registered.
+    #[error("Unknown plugin {0}, available plugins are: {1}.")]
+    UnknownPlugin(String, String),
+
+    /// Occurs when the config section of a plugin is invalid.
+    #[error("Invalid config of plugin {0}: {1}.")]
+    PluginConfigError(String, toml::de::Error),
+
+    /// Occurs when the shutdown marker can't be read or written.
+    #[error("Accessing the shutdown marker failed: {0}.")]
+    ShutdownMarkerError(std::io::Error),
+
     /// Occurs when there is an error while shutting down the node.
     #[error("Shutting down failed.")]
     ShutdownError(#[from] bee_common::shutdown::Error),
 }
 
+type PluginRegister<B> = dyn FnOnce(BeeNodeBuilder<B>, Option<&toml::Value>) -> Result<BeeNodeBuilder<B>, Error>;
+
+/// Returns how to register the plugin `P` with its config section.
+fn plugin_register<B, P>() -> Box<PluginRegister<B>>
+where
+    B: Backend,
+    P: Plugin<BeeNode<B>> + 'static,
+    P::Config: DeserializeOwned + Default,
+{
+    Box::new(
+        |node_builder: BeeNodeBuilder<B>, section: Option<&toml::Value>| -> Result<_, Error> {
+            let config = match section {
+                Some(section) => section
+                    .clone()
+                    .try_into::<P::Config>()
+                    .map_err(|e| Error::PluginConfigError(P::name().to_string(), e))?,
+                None => P::Config::default(),
+            };
+
+            Ok(node_builder.with_worker_cfg::<P>(config))
+        },
+    )
+}
+
+/// Registers the plugins enabled in `config`, failing if one of them is unknown.
+fn register_plugins<B: Backend>(
+    mut node_builder: BeeNodeBuilder<B>,
+    plugins: Vec<(&'static str, Box<PluginRegister<B>>)>,
+    config: &PluginsConfig,
+) -> Result<BeeNodeBuilder<B>, Error> {
+    if let Some(name) = config
+        .enabled()
+        .iter()
+        .find(|name| !plugins.iter().any(|(plugin, _)| plugin == name))
+    {
+        let available = plugins.iter().map(|(plugin, _)| *plugin).collect::<Vec<_>>();
+        return Err(Error::UnknownPlugin(name.clone(), available.join(", ")));
+    }
+
+    for (name, register) in plugins {
+        if config.enabled().iter().any(|enabled| enabled == name) {
+            info!("Enabling plugin {}...", name);
+            node_builder = register(node_builder, config.section(name))?;
+        }
+    }
+
+    Ok(node_builder)
+}
+
+/// A builder to create a node.
 pub struct NodeBuilder<B: Backend> {
     config: NodeConfig<B>,
+    config_source: Option<ConfigSource>,
+    plugins: Vec<(&'static str, Box<PluginRegister<B>>)>,
 }
 
 impl<B: Backend> NodeBuilder<B> {
+    /// Registers a plugin, started if its name is listed in `plugins.enabled`.
+    pub fn with_plugin<P>(mut self) -> Self
+    where
+        P: Plugin<BeeNode<B>> + 'static,
+        P::Config: DeserializeOwned + Default,
+    {
+        self.plugins.push((P::name(), plugin_register::<B, P>()));
+        self
+    }
+
+    /// Watches the config file of `source` to apply its changes while running.
+    pub fn with_config_source(mut self, source: ConfigSource) -> Self {
+        self.config_source.replace(source);
+        self
+    }
+
     /// Finishes the build process of a new node.
-    pub async fn finish(self) -> Result<Node<B>, Error> {
+    pub async fn finish(mut self) -> Result<Node<B>, Error> {
+        // The logger is set up before the node, the level it allows bounds the changes made while running.
+        log_level::init();
+
         print_banner_and_version();
 
         info!(
@@ -70,14 +177,24 @@ impl<B: Backend> NodeBuilder<B> {
             self.config.network_id.0, self.config.network_id.1
         );
 
-        let generated_new_local_keypair = self.config.peering.local_keypair.2;
-        if generated_new_local_keypair {
-            info!("Generated new local keypair: {}", self.config.peering.local_keypair.1);
-            info!("Add this to your config, and restart the node.");
+        let (local_keys, generated) = identity::keypair(&self.config).map_err(Error::IdentityError)?;
+        if generated {
+            info!(
+                "Generated new identity, stored in {}.",
+                self.config.identity.path().display()
+            );
         }
-        let local_keys = self.config.peering.local_keypair.0.clone();
+        self.config.peering.local_keypair = (local_keys.clone(), hex::encode(&local_keys.encode()[..]), false);
 
-        let node_builder = BeeNode::<B>::build();
+        if !shutdown::mark_running(self.config.shutdown.marker_path()).map_err(Error::ShutdownMarkerError)? {
+            warn!("The previous run didn't shut down cleanly, the database may be inconsistent.");
+        }
+
+        // The protocol config and the network id are shared with the plugins, which only get their own config section.
+        let node_builder = BeeNode::<B>::build()
+            .with_resource(self.config.shutdown.clone())
+            .with_resource(self.config.protocol.clone())
+            .with_resource(self.config.network_id.clone());
 
         let mut shutdown = Shutdown::new();
 
@@ -103,16 +220,20 @@ impl<B: Backend> NodeBuilder<B> {
         info!("Own Peer Id = {}", network.local_id());
 
         info!("Starting manual peer manager...");
-        spawn(ManualPeerManager::new(self.config.peering.manual.clone(), network.clone()).run());
+        let manual_peers = Arc::new(RwLock::new(self.config.peering.manual.clone()));
+        let (manual_peers_sender, manual_peers_receiver) = flume::unbounded();
+        spawn(
+            ManualPeerManager::new(self.config.peering.manual.clone(), network.clone())
+                .with_updates(manual_peers_receiver)
+                .run(),
+        );
 
-        // info!("Initializing ledger...");
-        // node_builder = bee_ledger::whiteflag::init::<BeeNode<B>>(
-        //     snapshot_metadata.index(),
-        //     snapshot_state.into(),
-        //     self.config.protocol.coordinator().clone(),
-        //     node_builder,
-        //     bus.clone(),
-        // );
+        info!("Initializing ledger...");
+        node_builder = bee_ledger::init::<BeeNode<B>>(
+            snapshot.header().ledger_index(),
+            self.config.protocol.coordinator().clone(),
+            node_builder,
+        );
 
         info!("Initializing protocol...");
         node_builder = Protocol::init::<BeeNode<B>>(
@@ -124,10 +245,47 @@ impl<B: Backend> NodeBuilder<B> {
             node_builder,
         );
 
+        info!("Initializing API...");
+        node_builder = bee_api::init::<BeeNode<B>>(
+            self.config.api.clone(),
+            self.config.network_id.clone(),
+            BEE_VERSION.to_string(),
+            self.config.protocol.clone(),
+            network.clone(),
+            self.config.peering.local_keypair.0.clone(),
+            manual_peers.clone(),
+            node_builder,
+        );
+
         info!("Initializing plugins...");
-        // plugin::init(bus.clone());
+        let plugins = std::mem::take(&mut self.plugins);
+        node_builder = register_plugins(node_builder, plugins, &self.config.plugins)?;
 
-        node_builder = node_builder.with_worker::<VersionCheckerWorker>();
+        node_builder = node_builder.with_worker_cfg::<VersionCheckerWorker>(self.config.version_checker.clone());
+
+        if !self.config.webhook.hooks().is_empty() {
+            info!("Initializing webhooks...");
+            node_builder = node_builder.with_worker_cfg::<WebhookWorker>(self.config.webhook.clone());
+        }
+
+        let (rpc_shutdown_sender, rpc_shutdown_receiver) = flume::unbounded();
+        if self.config.rpc.enabled() {
+            info!("Initializing RPC...");
+            node_builder = node_builder.with_worker_cfg::<RpcWorker>((
+                self.config.rpc.clone(),
+                network.clone(),
+                self.config.network_id.1,
+                manual_peers.clone(),
+                rpc_shutdown_sender,
+            ));
+        }
+
+        // Without a watcher, the sender is dropped and the manual peer manager stops once its peers are added.
+        if let Some(source) = self.config_source.take() {
+            info!("Initializing config watcher...");
+            node_builder =
+                node_builder.with_worker_cfg::<ConfigWatcherWorker>((source, manual_peers, manual_peers_sender));
+        }
 
         let bee_node = node_builder.finish().await;
 
@@ -140,7 +298,7 @@ impl<B: Backend> NodeBuilder<B> {
         Ok(Node {
             config: self.config,
             tmp_node: bee_node,
-            network_events: ShutdownStream::new(ctrl_c_listener(), events.into_stream()),
+            network_events: ShutdownStream::new(shutdown_listener(rpc_shutdown_receiver), events.into_stream()),
             shutdown,
             peers: HashMap::new(),
         })
@@ -175,16 +333,25 @@ impl<B: Backend> Node<B> {
             let _ = shutdown.send(());
         }
 
-        self.tmp_node.stop().await.expect("Failed to properly stop node");
-
-        info!("Stopped.");
+        if self.tmp_node.stop_gracefully().await {
+            shutdown::mark_clean(self.config.shutdown.marker_path()).map_err(Error::ShutdownMarkerError)?;
+            info!("Stopped.");
+        } else {
+            warn!("Stopped, but not cleanly.");
+        }
 
         Ok(())
     }
 
     /// Returns a builder to create a node.
     pub fn builder(config: NodeConfig<B>) -> NodeBuilder<B> {
-        NodeBuilder { config }
+        NodeBuilder {
+            config,
+            config_source: None,
+            plugins: Vec::new(),
+        }
+        .with_plugin::<TpsPlugin>()
+        .with_plugin::<CoordinatorPlugin>()
     }
 
     async fn process_event(&mut self, event: Event) {
@@ -226,12 +393,28 @@ impl<B: Backend> Node<B> {
     }
 }
 
-fn ctrl_c_listener() -> oneshot::Receiver<()> {
+fn shutdown_listener(rpc_shutdown: flume::Receiver<()>) -> oneshot::Receiver<()> {
     let (sender, receiver) = oneshot::channel();
 
     tokio::spawn(async move {
-        if let Err(e) = tokio::signal::ctrl_c().await {
-            panic!("Failed to intercept CTRL-C: {:?}.", e);
+        // The RPC server may be disabled, in which case the channel is disconnected and only the signals remain.
+        let rpc_shutdown = async move {
+            if rpc_shutdown.recv_async().await.is_err() {
+                future::pending::<()>().await;
+            }
+        };
+        let mut terminate = signal(SignalKind::terminate()).expect("Failed to intercept SIGTERM");
+        let mut hangup = signal(SignalKind::hangup()).expect("Failed to intercept SIGHUP");
+
+        select! {
+            res = tokio::signal::ctrl_c().fuse() => {
+                if let Err(e) = res {
+                    panic!("Failed to intercept CTRL-C: {:?}.", e);
+                }
+            },
+            _ = terminate.recv().fuse() => info!("Received SIGTERM."),
+            _ = hangup.recv().fuse() => info!("Received SIGHUP."),
+            _ = rpc_shutdown.fuse() => info!("Shutdown requested over RPC."),
         }
 
         if let Err(e) = sender.send(()) {
@@ -241,3 +424,123 @@ fn ctrl_c_listener() -> oneshot::Receiver<()> {
 
     receiver
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use crate::{config::NodeConfigBuilder, plugin::PluginsConfigBuilder, profile};
+
+    use bee_common_ext::{node::Node as _, worker::Worker};
+    use bee_storage_rocksdb::storage::Storage;
+
+    use async_trait::async_trait;
+    use serde::Deserialize;
+
+    use std::{cell::RefCell, convert::Infallible, rc::Rc};
+
+    #[derive(Default, Deserialize)]
+    struct DummyConfig {
+        #[allow(dead_code)]
+        value: u32,
+    }
+
+    struct Dummy;
+
+    #[async_trait]
+    impl<B: Backend> Worker<BeeNode<B>> for Dummy {
+        type Config = DummyConfig;
+        type Error = Infallible;
+
+        async fn start(_node: &mut BeeNode<B>, _config: Self::Config) -> Result<Self, Self::Error> {
+            Ok(Self)
+        }
+    }
+
+    impl<B: Backend> Plugin<BeeNode<B>> for Dummy {
+        fn name() -> &'static str {
+            "dummy"
+        }
+    }
+
+    type Registered = Rc<RefCell<Vec<(&'static str, Option<toml::Value>)>>>;
+
+    fn recording(name: &'static str, registered: &Registered) -> (&'static str, Box<PluginRegister<Storage>>) {
+        let registered = registered.clone();
+
+        (
+            name,
+            Box::new(
+                move |node_builder: BeeNodeBuilder<Storage>, section: Option<&toml::Value>| {
+                    registered.borrow_mut().push((name, section.cloned()));
+                    Ok(node_builder)
+                },
+            ),
+        )
+    }
+
+    #[test]
+    fn builtin_plugins_are_registered() {
+        let config = toml::from_str::<NodeConfigBuilder<Storage>>(profile::get("alphanet").unwrap())
+            .unwrap()
+            .finish()
+            .unwrap();
+
+        assert_eq!(
+            Node::builder(config)
+                .with_plugin::<Dummy>()
+                .plugins
+                .iter()
+                .map(|(name, _)| *name)
+                .collect::<Vec<_>>(),
+            vec!["tps", "coordinator", "dummy"]
+        );
+    }
+
+    #[test]
+    fn plugin_config_section() {
+        let section = |toml: &str| toml::from_str::<toml::Value>(toml).unwrap();
+
+        assert!(plugin_register::<Storage, Dummy>()(BeeNode::<Storage>::build(), None).is_ok());
+        assert!(plugin_register::<Storage, Dummy>()(BeeNode::<Storage>::build(), Some(&section("value = 1"))).is_ok());
+        assert!(matches!(
+            plugin_register::<Storage, Dummy>()(BeeNode::<Storage>::build(), Some(&section("value = \"one\""))),
+            Err(Error::PluginConfigError(name, _)) if name == "dummy"
+        ));
+    }
+
+    #[test]
+    fn only_enabled_plugins_are_registered() {
+        let registered = Registered::default();
+        let config = toml::from_str::<PluginsConfigBuilder>("enabled = [\"b\"]\n[b]\nvalue = 1")
+            .unwrap()
+            .finish();
+
+        assert!(register_plugins(
+            BeeNode::<Storage>::build(),
+            vec![recording("a", &registered), recording("b", &registered)],
+            &config,
+        )
+        .is_ok());
+        assert_eq!(
+            *registered.borrow(),
+            vec![("b", Some(toml::from_str::<toml::Value>("value = 1").unwrap()))]
+        );
+    }
+
+    #[test]
+    fn unknown_plugin() {
+        let registered = Registered::default();
+        let config = PluginsConfig::build().enable("c".to_string()).finish();
+
+        match register_plugins(BeeNode::<Storage>::build(), vec![recording("a", &registered)], &config) {
+            Err(Error::UnknownPlugin(name, available)) => {
+                assert_eq!(name, "c");
+                assert_eq!(available, "a");
+            }
+            _ => panic!("unknown plugin was accepted"),
+        }
+        assert!(registered.borrow().is_empty());
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use serde::Deserialize;
+
+#[derive(Default, Deserialize)]
+pub struct PluginsConfigBuilder {
+    enabled: Option<Vec<String>>,
+    // The config sections of the plugins, keyed by their names.
+    #[serde(flatten)]
+    sections: toml::value::Table,
+}
+
+impl PluginsConfigBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn enable(mut self, name: String) -> Self {
+        self.enabled.get_or_insert_with(Vec::new).push(name);
+        self
+    }
+
+    pub fn finish(self) -> PluginsConfig {
+        PluginsConfig {
+            enabled: self.enabled.unwrap_or_default(),
+            sections: self.sections,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct PluginsConfig {
+    pub(crate) enabled: Vec<String>,
+    pub(crate) sections: toml::value::Table,
+}
+
+impl PluginsConfig {
+    pub fn build() -> PluginsConfigBuilder {
+        PluginsConfigBuilder::new()
+    }
+
+    /// Names of the plugins to start.
+    pub fn enabled(&self) -> &[String] {
+        &self.enabled
+    }
+
+    /// Config section of the plugin `name`, i.e. `[plugins.<name>]`.
+    pub fn section(&self, name: &str) -> Option<&toml::Value> {
+        self.sections.get(name)
+    }
+}
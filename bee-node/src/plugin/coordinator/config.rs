@@ -0,0 +1,95 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use serde::Deserialize;
+
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+const DEFAULT_INTERVAL_SEC: u64 = 10;
+const DEFAULT_STATE_PATH: &str = "./coordinator.state";
+
+#[derive(Default, Deserialize)]
+pub struct CoordinatorConfigBuilder {
+    keypairs: Option<Vec<String>>,
+    interval_sec: Option<u64>,
+    state_path: Option<PathBuf>,
+    bootstrap: Option<bool>,
+}
+
+impl CoordinatorConfigBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn keypair(mut self, keypair: String) -> Self {
+        self.keypairs.get_or_insert_with(Vec::new).push(keypair);
+        self
+    }
+
+    pub fn interval_sec(mut self, interval_sec: u64) -> Self {
+        self.interval_sec.replace(interval_sec);
+        self
+    }
+
+    pub fn state_path(mut self, state_path: PathBuf) -> Self {
+        self.state_path.replace(state_path);
+        self
+    }
+
+    pub fn bootstrap(mut self, bootstrap: bool) -> Self {
+        self.bootstrap.replace(bootstrap);
+        self
+    }
+
+    pub fn finish(self) -> CoordinatorConfig {
+        CoordinatorConfig {
+            keypairs: self.keypairs.unwrap_or_default(),
+            interval: Duration::from_secs(self.interval_sec.unwrap_or(DEFAULT_INTERVAL_SEC)),
+            state_path: self.state_path.unwrap_or_else(|| PathBuf::from(DEFAULT_STATE_PATH)),
+            bootstrap: self.bootstrap.unwrap_or(false),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct CoordinatorConfig {
+    pub(crate) keypairs: Vec<String>,
+    pub(crate) interval: Duration,
+    pub(crate) state_path: PathBuf,
+    pub(crate) bootstrap: bool,
+}
+
+impl CoordinatorConfig {
+    pub fn build() -> CoordinatorConfigBuilder {
+        CoordinatorConfigBuilder::new()
+    }
+
+    /// Hex encoded Ed25519 keypairs the milestones are signed with.
+    pub fn keypairs(&self) -> &[String] {
+        &self.keypairs
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    pub fn state_path(&self) -> &Path {
+        &self.state_path
+    }
+
+    /// Whether a new chain may be started when there is neither a state file nor a known milestone to continue from.
+    pub fn bootstrap(&self) -> bool {
+        self.bootstrap
+    }
+}
@@ -0,0 +1,412 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+mod config;
+mod state;
+
+pub use config::{CoordinatorConfig, CoordinatorConfigBuilder};
+
+use crate::{identity, plugin::Plugin, storage::Backend};
+
+use state::State;
+
+use bee_common::{packable::Packable, shutdown_stream::ShutdownStream};
+use bee_common_ext::{
+    node::{Node, ResHandle},
+    worker::Worker,
+};
+use bee_ledger::{LedgerIndex, LedgerWorker};
+use bee_message::{
+    payload::milestone::{Milestone, MilestoneEssence},
+    Message, MessageId,
+};
+use bee_network::Keypair;
+use bee_protocol::{
+    config::ProtocolConfig, tangle::MsTangle, MessageSubmitterError, MessageSubmitterWorker,
+    MessageSubmitterWorkerEvent, MilestoneIndex, MilestoneValidatorWorker, StorageWorker, TangleWorker,
+};
+
+use async_trait::async_trait;
+use futures::{channel::oneshot, StreamExt};
+use log::{debug, error, info, warn};
+use thiserror::Error;
+use tokio::time::interval;
+
+use std::{
+    any::TypeId,
+    io,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Accessing the coordinator state failed: {0}.")]
+    Io(#[from] io::Error),
+
+    #[error("The coordinator state is malformed: {0}.")]
+    MalformedState(String),
+
+    #[error("No keypair configured in the coordinator section.")]
+    NoKeypair,
+
+    #[error("Neither a coordinator state nor a known milestone to continue from, `bootstrap` starts a new chain.")]
+    NoChain,
+
+    #[error("{0}")]
+    InvalidKeypair(identity::Error),
+
+    #[error("{0} configured keys are active at milestone {1}, {2} are required.")]
+    InsufficientKeys(usize, u32, usize),
+
+    #[error("Milestone {0} isn't confirmed by the ledger yet.")]
+    Unconfirmed(u32),
+
+    #[error("Computing the merkle proof failed: {0:?}.")]
+    MerkleProof(bee_ledger::Error),
+
+    #[error("Building the milestone message failed: {0:?}.")]
+    InvalidMessage(bee_message::Error),
+
+    #[error("No nonce reaching the minimum PoW score was found.")]
+    Pow,
+
+    #[error("Submitting the milestone failed: {0}")]
+    Submission(MessageSubmitterError),
+}
+
+/// Returns the configured keypairs active at `index`, sorted by public key, as long as there are enough of them.
+fn signers<'a>(
+    keypairs: &'a [Keypair],
+    config: &ProtocolConfig,
+    index: MilestoneIndex,
+) -> Result<Vec<&'a Keypair>, Error> {
+    let public_keys = config.coordinator().public_keys(index);
+    let mut signers = keypairs
+        .iter()
+        .filter(|keypair| public_keys.contains(&hex::encode(keypair.public().encode())))
+        .collect::<Vec<_>>();
+
+    if signers.len() < config.coordinator().public_key_count() {
+        return Err(Error::InsufficientKeys(
+            signers.len(),
+            *index,
+            config.coordinator().public_key_count(),
+        ));
+    }
+
+    signers.sort_by_key(|keypair| keypair.public().encode());
+
+    Ok(signers)
+}
+
+fn build_message(
+    network_id: u64,
+    (parent1, parent2): (MessageId, MessageId),
+    milestone: Milestone,
+    nonce: u64,
+) -> Result<Message, Error> {
+    Message::builder()
+        .with_network_id(network_id)
+        .with_parent1(parent1)
+        .with_parent2(parent2)
+        .with_payload(milestone.into())
+        .with_nonce(nonce)
+        .finish()
+        .map_err(Error::InvalidMessage)
+}
+
+/// Chooses the milestone to continue the chain from: the state file, else the latest known milestone, else a new chain
+/// if bootstrapping is allowed.
+fn initial_state(
+    loaded: Option<State>,
+    latest: (MilestoneIndex, Option<MessageId>),
+    bootstrap: bool,
+) -> Result<State, Error> {
+    match (loaded, latest) {
+        (Some(state), _) => Ok(state),
+        (None, (index, Some(message_id))) if *index != 0 && message_id != MessageId::null() => Ok(State {
+            index,
+            message_id,
+            pending: None,
+        }),
+        _ if bootstrap => Ok(State {
+            index: MilestoneIndex(0),
+            message_id: MessageId::null(),
+            pending: None,
+        }),
+        _ => Err(Error::NoChain),
+    }
+}
+
+/// Builds the milestone following `last` once the ledger confirmed it, to be persisted before its submission.
+async fn prepare<N: Node>(
+    tangle: &MsTangle<N::Backend>,
+    storage: &ResHandle<N::Backend>,
+    ledger_index: &LedgerIndex,
+    keypairs: &[Keypair],
+    config: &ProtocolConfig,
+    network_id: u64,
+    last: &State,
+) -> Result<State, Error>
+where
+    N::Backend: Backend,
+{
+    let index = MilestoneIndex(*last.index + 1);
+    let signers = signers(keypairs, config, index)?;
+    // The proof only skips the messages already confirmed, so the ledger must have confirmed the previous milestone and
+    // is held back until the proof is computed.
+    let ledger_index = ledger_index.read().await;
+    if *ledger_index < last.index {
+        return Err(Error::Unconfirmed(*last.index));
+    }
+    let parents = tangle
+        .get_messages_to_approve()
+        .await
+        .unwrap_or((last.message_id, last.message_id));
+    let merkle_proof = bee_ledger::merkle_proof::<N>(tangle, storage, index, parents.0, parents.1)
+        .await
+        .map_err(Error::MerkleProof)?;
+    drop(ledger_index);
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+
+    let essence = MilestoneEssence::new(
+        *index,
+        timestamp,
+        parents.0,
+        parents.1,
+        merkle_proof.into_boxed_slice(),
+        signers.iter().map(|keypair| keypair.public().encode()).collect(),
+    );
+    let essence_bytes = essence.pack_new();
+    let signatures = signers
+        .iter()
+        .map(|keypair| keypair.sign(&essence_bytes).into_boxed_slice())
+        .collect();
+    let milestone = Milestone::new(essence, signatures);
+
+    let bytes = build_message(network_id, parents, milestone.clone(), 0)?.pack_new();
+    let target_score = config.minimum_pow_score();
+    let nonce = match tokio::task::spawn_blocking(move || bee_pow::mine(&bytes, target_score)).await {
+        Ok(Some(nonce)) => nonce,
+        _ => return Err(Error::Pow),
+    };
+    let message = build_message(network_id, parents, milestone, nonce)?;
+
+    Ok(State {
+        index,
+        message_id: message.id(),
+        pending: Some(message.pack_new()),
+    })
+}
+
+/// Submits a packed milestone message and returns its id once it entered the processing pipeline, to be gossiped.
+async fn submit(submitter: &flume::Sender<MessageSubmitterWorkerEvent>, bytes: &[u8]) -> Result<MessageId, Error> {
+    let message = Message::unpack(&mut &bytes[..]).map_err(Error::InvalidMessage)?;
+    let (notifier, waiter) = oneshot::channel();
+    submitter
+        .send(MessageSubmitterWorkerEvent { message, notifier })
+        .map_err(|_| Error::Submission(MessageSubmitterError::ChannelClosed))?;
+    waiter
+        .await
+        .map_err(|_| Error::Submission(MessageSubmitterError::ChannelClosed))?
+        .map_err(Error::Submission)
+}
+
+/// Issues milestones at a fixed interval, to run a private network.
+///
+/// The chain continues from the state file, or from the latest known milestone when there is none. A milestone is only
+/// issued once the ledger confirmed the previous one, and is persisted before its submission so that a restart
+/// resubmits it instead of forking the chain.
+pub(crate) struct CoordinatorPlugin;
+
+#[async_trait]
+impl<N: Node> Worker<N> for CoordinatorPlugin
+where
+    N::Backend: Backend,
+{
+    type Config = CoordinatorConfigBuilder;
+    type Error = Error;
+
+    fn dependencies() -> &'static [TypeId] {
+        vec![
+            TypeId::of::<StorageWorker>(),
+            TypeId::of::<TangleWorker>(),
+            TypeId::of::<MessageSubmitterWorker>(),
+            TypeId::of::<MilestoneValidatorWorker>(),
+            TypeId::of::<LedgerWorker>(),
+        ]
+        .leak()
+    }
+
+    async fn start(node: &mut N, config: Self::Config) -> Result<Self, Self::Error> {
+        let config = config.finish();
+
+        if config.keypairs().is_empty() {
+            return Err(Error::NoKeypair);
+        }
+        let keypairs = config
+            .keypairs()
+            .iter()
+            .map(|keypair| identity::decode(keypair))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(Error::InvalidKeypair)?;
+
+        let tangle = node.resource::<MsTangle<N::Backend>>();
+        let storage = node.storage();
+        let ledger_index = node.resource::<LedgerIndex>();
+        let protocol_config = (*node.resource::<ProtocolConfig>()).clone();
+        let network_id = node.resource::<(String, u64)>().1;
+        let submitter = node.worker::<MessageSubmitterWorker>().unwrap().tx.clone();
+
+        let latest = tangle.get_latest_milestone_index();
+        let mut state = initial_state(
+            state::load(config.state_path())?,
+            (latest, tangle.get_milestone_message_id(latest)),
+            config.bootstrap(),
+        )?;
+        info!(
+            "Continuing the chain from milestone {} {}.",
+            *state.index, state.message_id
+        );
+
+        node.spawn::<Self, _, _>(|shutdown| async move {
+            info!("Running.");
+
+            let mut ticker = ShutdownStream::new(shutdown, interval(config.interval()));
+
+            while ticker.next().await.is_some() {
+                if state.pending.is_none() {
+                    match prepare::<N>(
+                        &tangle,
+                        &storage,
+                        &ledger_index,
+                        &keypairs,
+                        &protocol_config,
+                        network_id,
+                        &state,
+                    )
+                    .await
+                    {
+                        Ok(next) => {
+                            // Persisted before the submission, so a restart resubmits it rather than forking the chain.
+                            if let Err(e) = state::store(config.state_path(), &next) {
+                                error!(
+                                    "Persisting the coordinator state failed, no more milestones are issued: {}",
+                                    e
+                                );
+                                break;
+                            }
+                            state = next;
+                        }
+                        Err(Error::Unconfirmed(index)) => {
+                            debug!("Milestone {} isn't confirmed yet, waiting.", index);
+                            continue;
+                        }
+                        Err(e) => {
+                            warn!("Issuing milestone {} failed: {}", *state.index + 1, e);
+                            continue;
+                        }
+                    }
+                }
+
+                if let Some(bytes) = state.pending.as_ref() {
+                    match submit(&submitter, bytes).await {
+                        Ok(message_id) => {
+                            info!("Issued milestone {} {}.", *state.index, message_id);
+                            state.pending = None;
+                            if let Err(e) = state::store(config.state_path(), &state) {
+                                error!(
+                                    "Persisting the coordinator state failed, no more milestones are issued: {}",
+                                    e
+                                );
+                                break;
+                            }
+                        }
+                        Err(e) => warn!("Submitting milestone {} failed, retrying: {}", *state.index, e),
+                    }
+                }
+            }
+
+            info!("Stopped.");
+        });
+
+        Ok(Self)
+    }
+}
+
+impl<N: Node> Plugin<N> for CoordinatorPlugin
+where
+    N::Backend: Backend,
+{
+    fn name() -> &'static str {
+        "coordinator"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn initial_state_from_file() {
+        let loaded = State {
+            index: MilestoneIndex(42),
+            message_id: MessageId::new([7; 32]),
+            pending: None,
+        };
+
+        assert_eq!(
+            initial_state(
+                Some(loaded.clone()),
+                (MilestoneIndex(50), Some(MessageId::new([8; 32]))),
+                false
+            )
+            .unwrap(),
+            loaded
+        );
+    }
+
+    #[test]
+    fn initial_state_from_latest_milestone() {
+        assert_eq!(
+            initial_state(None, (MilestoneIndex(50), Some(MessageId::new([8; 32]))), false).unwrap(),
+            State {
+                index: MilestoneIndex(50),
+                message_id: MessageId::new([8; 32]),
+                pending: None,
+            }
+        );
+    }
+
+    #[test]
+    fn initial_state_requires_bootstrap() {
+        assert!(matches!(
+            initial_state(None, (MilestoneIndex(0), None), false),
+            Err(Error::NoChain)
+        ));
+        assert!(matches!(
+            initial_state(None, (MilestoneIndex(50), None), false),
+            Err(Error::NoChain)
+        ));
+        assert_eq!(
+            initial_state(None, (MilestoneIndex(50), None), true).unwrap(),
+            State {
+                index: MilestoneIndex(0),
+                message_id: MessageId::null(),
+                pending: None,
+            }
+        );
+    }
+}
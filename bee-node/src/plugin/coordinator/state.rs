@@ -0,0 +1,109 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::plugin::coordinator::Error;
+
+use bee_message::MessageId;
+use bee_protocol::MilestoneIndex;
+
+use serde::{Deserialize, Serialize};
+
+use std::{fs, io, path::Path};
+
+/// The last milestone issued by the coordinator, the next one is chained to it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct State {
+    pub(crate) index: MilestoneIndex,
+    pub(crate) message_id: MessageId,
+    /// Packed message of the milestone, as long as it isn't known to be submitted.
+    pub(crate) pending: Option<Vec<u8>>,
+}
+
+/// Content of the state file.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StateFile {
+    last_index: u32,
+    last_milestone_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pending_message: Option<String>,
+}
+
+/// Reads the state file, `None` if there is no state file.
+pub(crate) fn load(path: &Path) -> Result<Option<State>, Error> {
+    let content = match fs::read(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(Error::Io(e)),
+    };
+    let file = serde_json::from_slice::<StateFile>(&content).map_err(|e| Error::MalformedState(e.to_string()))?;
+
+    Ok(Some(State {
+        index: MilestoneIndex(file.last_index),
+        message_id: file
+            .last_milestone_id
+            .parse()
+            .map_err(|_| Error::MalformedState(format!("invalid milestone id {}", file.last_milestone_id)))?,
+        pending: file
+            .pending_message
+            .map(|message| hex::decode(message).map_err(|e| Error::MalformedState(e.to_string())))
+            .transpose()?,
+    }))
+}
+
+/// Writes the state file, atomically replacing the previous one.
+pub(crate) fn store(path: &Path, state: &State) -> Result<(), Error> {
+    let file = StateFile {
+        last_index: *state.index,
+        last_milestone_id: state.message_id.to_string(),
+        pending_message: state.pending.as_ref().map(hex::encode),
+    };
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, serde_json::to_vec_pretty(&file).map_err(io::Error::from)?)?;
+
+    Ok(fs::rename(&tmp_path, path)?)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use std::env;
+
+    #[test]
+    fn store_load() {
+        let path = env::temp_dir().join(format!("bee-coordinator-{}.state", std::process::id()));
+        let state = State {
+            index: MilestoneIndex(42),
+            message_id: MessageId::new([7; 32]),
+            pending: None,
+        };
+
+        assert!(load(&path).unwrap().is_none());
+        store(&path, &state).unwrap();
+        assert_eq!(load(&path).unwrap(), Some(state.clone()));
+
+        let pending = State {
+            pending: Some(vec![1, 2, 3]),
+            ..state
+        };
+        store(&path, &pending).unwrap();
+        assert_eq!(load(&path).unwrap(), Some(pending));
+
+        fs::remove_file(path).unwrap();
+    }
+}
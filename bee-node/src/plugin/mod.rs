@@ -9,21 +9,22 @@
 // an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and limitations under the License.
 
-use bee_common_ext::event::Bus;
-
-use std::sync::Arc;
-
+mod config;
+mod coordinator;
 mod tps;
 
-pub trait Plugin {
-    type Error;
+pub use config::{PluginsConfig, PluginsConfigBuilder};
+pub(crate) use coordinator::CoordinatorPlugin;
+pub(crate) use tps::TpsPlugin;
 
-    fn name(&self) -> &str;
-    fn init(&mut self, bus: Arc<Bus>) -> Result<(), Self::Error>;
-    fn start(&mut self) -> Result<(), Self::Error>;
-}
+use bee_common_ext::{node::Node, worker::Worker};
 
-pub(crate) fn init(bus: Arc<Bus>) {
-    let result = tps::TpsPlugin::new().init(bus);
-    debug_assert!(result.is_ok());
+/// A worker shipped as an optional feature of the node.
+///
+/// Plugins are registered with `NodeBuilder::with_plugin` and only started when their name is listed in
+/// `plugins.enabled`. They get their config from the `[plugins.<name>]` section, or the default one if there is none,
+/// and are started after their dependencies and stopped before them, like any other worker.
+pub trait Plugin<N: Node>: Worker<N> {
+    /// Name of the plugin, used in `plugins.enabled` and as the name of its config section.
+    fn name() -> &'static str;
 }
@@ -11,12 +11,13 @@
 
 use crate::plugin::Plugin;
 
-use bee_common_ext::event::Bus;
+use bee_common_ext::{event::Bus, node::Node, worker::Worker};
 use bee_protocol::event::TpsMetricsUpdated;
 
+use async_trait::async_trait;
 use log::info;
 
-use std::{convert::Infallible, sync::Arc};
+use std::convert::Infallible;
 
 fn tps(metrics: &TpsMetricsUpdated) {
     info!(
@@ -25,27 +26,22 @@ fn tps(metrics: &TpsMetricsUpdated) {
     );
 }
 
-pub(crate) struct TpsPlugin {}
+pub(crate) struct TpsPlugin;
 
-impl TpsPlugin {
-    pub(crate) fn new() -> Self {
-        Self {}
-    }
-}
-
-impl Plugin for TpsPlugin {
+#[async_trait]
+impl<N: Node> Worker<N> for TpsPlugin {
+    type Config = ();
     type Error = Infallible;
 
-    fn name(&self) -> &str {
-        "tps"
-    }
+    async fn start(node: &mut N, _config: Self::Config) -> Result<Self, Self::Error> {
+        node.resource::<Bus>().add_listener::<Self, _, _>(tps);
 
-    fn init(&mut self, bus: Arc<Bus>) -> Result<(), Self::Error> {
-        bus.add_listener::<(), _, _>(tps);
-        Ok(())
+        Ok(Self)
     }
+}
 
-    fn start(&mut self) -> Result<(), Self::Error> {
-        Ok(())
+impl<N: Node> Plugin<N> for TpsPlugin {
+    fn name() -> &'static str {
+        "tps"
     }
 }
@@ -0,0 +1,61 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+/// Built-in configurations of the networks the node can join without a config file, as `(name, toml)` pairs.
+// Only the alphanet runs the Chrysalis protocol implemented here, other networks get a profile once they do.
+const PROFILES: [(&str, &str); 1] = [("alphanet", include_str!("../profiles/alphanet.toml"))];
+
+/// Networks that don't run the Chrysalis protocol yet, their coordinators don't issue milestones the node can validate.
+const UNSUPPORTED: [&str; 3] = ["comnet", "mainnet", "testnet"];
+
+/// Returns the names of the built-in network profiles.
+pub(crate) fn names() -> Vec<&'static str> {
+    PROFILES.iter().map(|(name, _)| *name).collect()
+}
+
+/// Returns the TOML configuration of the network profile `name`, if it exists.
+pub(crate) fn get(name: &str) -> Option<&'static str> {
+    PROFILES
+        .iter()
+        .find(|(profile, _)| profile.eq_ignore_ascii_case(name))
+        .map(|(_, toml)| *toml)
+}
+
+/// Returns whether `name` is a network the node can't join yet.
+pub(crate) fn is_unsupported(name: &str) -> bool {
+    UNSUPPORTED.iter().any(|network| network.eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use crate::config::NodeConfigBuilder;
+
+    use bee_storage_rocksdb::storage::Storage;
+
+    #[test]
+    fn profiles_are_complete() {
+        for name in names() {
+            if let Err(e) = toml::from_str::<NodeConfigBuilder<Storage>>(get(name).unwrap()) {
+                panic!("Profile {} is invalid: {}", name, e);
+            }
+        }
+    }
+
+    #[test]
+    fn unsupported_networks() {
+        assert!(is_unsupported("Mainnet"));
+        assert!(!is_unsupported("alphanet"));
+        assert!(names().iter().all(|name| !is_unsupported(name)));
+    }
+}
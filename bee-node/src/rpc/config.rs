@@ -0,0 +1,67 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use serde::Deserialize;
+
+use std::path::{Path, PathBuf};
+
+const DEFAULT_ENABLED: bool = true;
+const DEFAULT_PATH: &str = "./bee.sock";
+
+#[derive(Default, Deserialize)]
+pub struct RpcConfigBuilder {
+    enabled: Option<bool>,
+    path: Option<PathBuf>,
+}
+
+impl RpcConfigBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled.replace(enabled);
+        self
+    }
+
+    pub fn path(mut self, path: PathBuf) -> Self {
+        self.path.replace(path);
+        self
+    }
+
+    pub fn finish(self) -> RpcConfig {
+        RpcConfig {
+            enabled: self.enabled.unwrap_or(DEFAULT_ENABLED),
+            path: self.path.unwrap_or_else(|| PathBuf::from(DEFAULT_PATH)),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RpcConfig {
+    pub(crate) enabled: bool,
+    pub(crate) path: PathBuf,
+}
+
+impl RpcConfig {
+    pub fn build() -> RpcConfigBuilder {
+        RpcConfigBuilder::new()
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Path of the Unix domain socket, only accessible to the user running the node.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
@@ -0,0 +1,276 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::rpc::error::Error;
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::{
+    io::{self, AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::UnixStream,
+};
+
+use std::sync::Arc;
+
+const VERSION: &str = "2.0";
+
+/// Executes the methods requested over a connection.
+#[async_trait]
+pub(crate) trait Handler: Send + Sync {
+    async fn call(&self, method: &str, params: Value) -> Result<Value, Error>;
+}
+
+#[derive(Deserialize)]
+struct Request {
+    jsonrpc: String,
+    method: String,
+    #[serde(default)]
+    params: Value,
+    // Requests without an id are notifications and don't get a response.
+    id: Option<Value>,
+}
+
+#[derive(Serialize)]
+struct ErrorObject {
+    code: i64,
+    message: String,
+}
+
+#[derive(Serialize)]
+struct Response {
+    jsonrpc: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<ErrorObject>,
+    id: Value,
+}
+
+impl Response {
+    fn result(id: Value, result: Value) -> Self {
+        Self {
+            jsonrpc: VERSION,
+            result: Some(result),
+            error: None,
+            id,
+        }
+    }
+
+    fn error(id: Value, error: Error) -> Self {
+        Self {
+            jsonrpc: VERSION,
+            result: None,
+            error: Some(ErrorObject {
+                code: error.code(),
+                message: error.to_string(),
+            }),
+            id,
+        }
+    }
+}
+
+/// Serves newline delimited JSON-RPC requests, single or batched, until the client closes the connection.
+pub(crate) async fn handle<H: Handler>(stream: UnixStream, handler: Arc<H>) {
+    let (reader, mut writer) = io::split(stream);
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Some(line) = lines.next().await {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                debug!("Reading from RPC connection failed: {}.", e);
+                break;
+            }
+        };
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if let Some(mut response) = process(&*handler, &line).await {
+            response.push('\n');
+            if let Err(e) = writer.write_all(response.as_bytes()).await {
+                debug!("Writing to RPC connection failed: {}.", e);
+                break;
+            }
+        }
+    }
+}
+
+async fn process<H: Handler>(handler: &H, line: &str) -> Option<String> {
+    let response = match serde_json::from_str::<Value>(line) {
+        Ok(Value::Array(requests)) if requests.is_empty() => serde_json::to_string(&Response::error(
+            Value::Null,
+            Error::InvalidRequest("empty batch".to_string()),
+        )),
+        Ok(Value::Array(requests)) => {
+            let mut responses = Vec::new();
+
+            for request in requests {
+                if let Some(response) = call(handler, request).await {
+                    responses.push(response);
+                }
+            }
+
+            if responses.is_empty() {
+                return None;
+            }
+
+            serde_json::to_string(&responses)
+        }
+        Ok(request) => serde_json::to_string(&call(handler, request).await?),
+        Err(e) => serde_json::to_string(&Response::error(Value::Null, Error::Parse(e.to_string()))),
+    };
+
+    response.ok()
+}
+
+async fn call<H: Handler>(handler: &H, request: Value) -> Option<Response> {
+    let request = match serde_json::from_value::<Request>(request) {
+        Ok(request) if request.jsonrpc == VERSION => request,
+        Ok(request) => {
+            return Some(Response::error(
+                request.id.unwrap_or_default(),
+                Error::InvalidRequest(format!("unsupported version {}", request.jsonrpc)),
+            ));
+        }
+        Err(e) => return Some(Response::error(Value::Null, Error::InvalidRequest(e.to_string()))),
+    };
+
+    let result = handler.call(&request.method, request.params).await;
+
+    if let Err(e) = &result {
+        warn!("RPC method {} failed: {}", request.method, e);
+    }
+
+    let id = request.id?;
+
+    Some(match result {
+        Ok(result) => Response::result(id, result),
+        Err(e) => Response::error(id, e),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use serde_json::json;
+
+    struct Echo;
+
+    #[async_trait]
+    impl Handler for Echo {
+        async fn call(&self, method: &str, params: Value) -> Result<Value, Error> {
+            match method {
+                "echo" => Ok(params),
+                _ => Err(Error::MethodNotFound(method.to_string())),
+            }
+        }
+    }
+
+    async fn response(line: &str) -> Option<Value> {
+        process(&Echo, line)
+            .await
+            .map(|response| serde_json::from_str(&response).unwrap())
+    }
+
+    #[tokio::test]
+    async fn single() {
+        assert_eq!(
+            response(r#"{"jsonrpc": "2.0", "method": "echo", "params": [1, 2], "id": 1}"#).await,
+            Some(json!({ "jsonrpc": "2.0", "result": [1, 2], "id": 1 }))
+        );
+        assert_eq!(
+            response(r#"{"jsonrpc": "2.0", "method": "echo", "id": "a"}"#).await,
+            Some(json!({ "jsonrpc": "2.0", "result": null, "id": "a" }))
+        );
+    }
+
+    #[tokio::test]
+    async fn notification() {
+        assert_eq!(
+            response(r#"{"jsonrpc": "2.0", "method": "echo", "params": [1]}"#).await,
+            None
+        );
+        assert_eq!(response(r#"{"jsonrpc": "2.0", "method": "unknown"}"#).await, None);
+    }
+
+    #[tokio::test]
+    async fn batch() {
+        assert_eq!(
+            response(
+                r#"[
+                    {"jsonrpc": "2.0", "method": "echo", "params": [1], "id": 1},
+                    {"jsonrpc": "2.0", "method": "echo", "params": [2]},
+                    {"jsonrpc": "2.0", "method": "unknown", "id": 3}
+                ]"#
+            )
+            .await,
+            Some(json!([
+                { "jsonrpc": "2.0", "result": [1], "id": 1 },
+                { "jsonrpc": "2.0", "error": { "code": -32601, "message": "Method unknown not found." }, "id": 3 },
+            ]))
+        );
+        assert_eq!(response(r#"[{"jsonrpc": "2.0", "method": "echo"}]"#).await, None);
+    }
+
+    #[tokio::test]
+    async fn errors() {
+        let code = |response: Option<Value>| response.unwrap()["error"]["code"].as_i64();
+
+        assert_eq!(code(response("{").await), Some(-32700));
+        assert_eq!(code(response("[]").await), Some(-32600));
+        assert_eq!(code(response(r#"{"method": "echo", "id": 1}"#).await), Some(-32600));
+        assert_eq!(
+            code(response(r#"{"jsonrpc": "1.0", "method": "echo", "id": 1}"#).await),
+            Some(-32600)
+        );
+        assert_eq!(
+            code(response(r#"{"jsonrpc": "2.0", "method": "unknown", "id": 1}"#).await),
+            Some(-32601)
+        );
+    }
+
+    #[tokio::test]
+    async fn socket() {
+        let (client, server) = UnixStream::pair().unwrap();
+        let connection = tokio::spawn(handle(server, Arc::new(Echo)));
+        let (reader, mut writer) = io::split(client);
+        let mut lines = BufReader::new(reader).lines();
+
+        writer
+            .write_all(
+                b"{\"jsonrpc\": \"2.0\", \"method\": \"echo\", \"params\": [1], \"id\": 1}\n\n\
+                  {\"jsonrpc\": \"2.0\", \"method\": \"echo\"}\n\
+                  {\"jsonrpc\": \"2.0\", \"method\": \"echo\", \"params\": [2], \"id\": 2}\n",
+            )
+            .await
+            .unwrap();
+
+        for id in 1..=2 {
+            let line = lines.next_line().await.unwrap().unwrap();
+            assert_eq!(
+                serde_json::from_str::<Value>(&line).unwrap(),
+                json!({ "jsonrpc": "2.0", "result": [id], "id": id })
+            );
+        }
+
+        // Closing the connection ends its handling.
+        drop(writer);
+        drop(lines);
+        connection.await.unwrap();
+    }
+}
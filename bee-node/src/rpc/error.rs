@@ -0,0 +1,47 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use thiserror::Error;
+
+/// Errors as defined by the JSON-RPC 2.0 specification, plus a generic server error.
+#[derive(Debug, Error)]
+pub(crate) enum Error {
+    #[error("Parse error: {0}.")]
+    Parse(String),
+
+    #[error("Invalid request: {0}.")]
+    InvalidRequest(String),
+
+    #[error("Method {0} not found.")]
+    MethodNotFound(String),
+
+    #[error("Invalid params: {0}.")]
+    InvalidParams(String),
+
+    #[error("Internal error: {0}.")]
+    Internal(String),
+
+    #[error("{0}")]
+    Server(String),
+}
+
+impl Error {
+    pub(crate) fn code(&self) -> i64 {
+        match self {
+            Error::Parse(_) => -32700,
+            Error::InvalidRequest(_) => -32600,
+            Error::MethodNotFound(_) => -32601,
+            Error::InvalidParams(_) => -32602,
+            Error::Internal(_) => -32603,
+            Error::Server(_) => -32000,
+        }
+    }
+}
@@ -0,0 +1,424 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::{
+    inner::WorkerInfo,
+    log_level,
+    rpc::{connection::Handler, error::Error},
+    storage::Backend,
+};
+
+use bee_common_ext::node::ResHandle;
+use bee_ledger::{output::Output, unspent::Unspent, LedgerIndex};
+use bee_message::payload::transaction::{self, OutputId, UTXOInput};
+use bee_network::{Command, Multiaddr, Network, PeerId, Protocol as MultiaddrProtocol};
+use bee_peering::ManualPeers;
+use bee_protocol::{event::TpsMetricsUpdated, tangle::MsTangle, PeerMetrics, Protocol, WorkerQueue};
+use bee_snapshot::{output::Output as SnapshotOutput, Kind, Snapshot, SnapshotHeader};
+use bee_storage::access::{AsStream, Fetch};
+
+use async_trait::async_trait;
+use futures::{pin_mut, StreamExt};
+use log::{info, LevelFilter};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use std::{
+    collections::HashSet,
+    fs,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Everything the RPC methods act upon, shared by all connections.
+pub(crate) struct Context<B: Backend> {
+    pub(crate) network: Network,
+    pub(crate) network_id: u64,
+    pub(crate) tangle: ResHandle<MsTangle<B>>,
+    pub(crate) storage: ResHandle<B>,
+    pub(crate) ledger_index: ResHandle<LedgerIndex>,
+    pub(crate) workers: Vec<WorkerInfo>,
+    pub(crate) worker_queues: Vec<WorkerQueue>,
+    pub(crate) manual_peers: ManualPeers,
+    pub(crate) tps: Arc<Mutex<Tps>>,
+    pub(crate) shutdown: flume::Sender<()>,
+}
+
+/// The latest figures dispatched as `TpsMetricsUpdated`.
+#[derive(Clone, Copy, Default, Serialize)]
+pub(crate) struct Tps {
+    incoming: u64,
+    new: u64,
+    known: u64,
+    invalid: u64,
+    outgoing: u64,
+}
+
+impl From<&TpsMetricsUpdated> for Tps {
+    fn from(metrics: &TpsMetricsUpdated) -> Self {
+        Self {
+            incoming: metrics.incoming,
+            new: metrics.new,
+            known: metrics.known,
+            invalid: metrics.invalid,
+            outgoing: metrics.outgoing,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AddPeerParams {
+    multi_address: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PeerParams {
+    peer_id: String,
+}
+
+#[derive(Deserialize)]
+struct SetLogLevelParams {
+    level: String,
+}
+
+#[derive(Deserialize)]
+struct CreateSnapshotParams {
+    path: String,
+}
+
+#[async_trait]
+impl<B: Backend> Handler for Context<B> {
+    async fn call(&self, method: &str, params: Value) -> Result<Value, Error> {
+        call(self, method, params).await
+    }
+}
+
+async fn call<B: Backend>(context: &Context<B>, method: &str, params: Value) -> Result<Value, Error> {
+    match method {
+        "peers.add" => add_peer(context, parse(params)?),
+        "peers.remove" => remove_peer(context, parse(params)?),
+        "peers.ban" => ban_peer(context, parse(params)?),
+        "log.set_level" => set_log_level(parse(params)?),
+        "snapshot.create" => create_snapshot(context, parse(params)?).await,
+        "db.compact" => compact_database(context).await,
+        "node.shutdown" => shutdown_node(context),
+        "node.status" => Ok(node_status(context).await),
+        "workers.list" => Ok(list_workers(context)),
+        _ => Err(Error::MethodNotFound(method.to_string())),
+    }
+}
+
+fn parse<T: DeserializeOwned>(params: Value) -> Result<T, Error> {
+    serde_json::from_value(params).map_err(|e| Error::InvalidParams(e.to_string()))
+}
+
+fn parse_peer_id(id: &str) -> Result<PeerId, Error> {
+    PeerId::from_str(id).map_err(|_| Error::InvalidParams(format!("invalid peer id {}", id)))
+}
+
+fn send<B: Backend>(context: &Context<B>, command: Command) -> Result<(), Error> {
+    context
+        .network
+        .unbounded_send(command)
+        .map_err(|e| Error::Server(format!("Sending network command failed: {:?}.", e)))
+}
+
+/// Splits a multiaddress ending with a peer id, e.g. `/ip4/1.2.3.4/tcp/15600/p2p/<id>`, into the address and the id.
+fn parse_multiaddress(multi_address: &str) -> Result<(Multiaddr, PeerId), Error> {
+    let mut address = multi_address
+        .parse::<Multiaddr>()
+        .map_err(|_| Error::InvalidParams(format!("invalid multiaddress {}", multi_address)))?;
+
+    let id = match address.pop() {
+        Some(MultiaddrProtocol::P2p(multihash)) => PeerId::from_multihash(multihash)
+            .map_err(|_| Error::InvalidParams(format!("invalid peer id in {}", multi_address)))?,
+        _ => return Err(Error::InvalidParams(format!("missing peer id in {}", multi_address))),
+    };
+
+    Ok((address, id))
+}
+
+fn add_peer<B: Backend>(context: &Context<B>, params: AddPeerParams) -> Result<Value, Error> {
+    let (address, id) = parse_multiaddress(&params.multi_address)?;
+
+    send(
+        context,
+        Command::ConnectPeer {
+            address,
+            id: id.clone(),
+        },
+    )?;
+
+    Ok(json!({ "peerId": id.to_string() }))
+}
+
+fn remove_peer<B: Backend>(context: &Context<B>, params: PeerParams) -> Result<Value, Error> {
+    let id = parse_peer_id(&params.peer_id)?;
+
+    send(context, Command::DisconnectPeer { id })?;
+
+    Ok(Value::Null)
+}
+
+fn ban_peer<B: Backend>(context: &Context<B>, params: PeerParams) -> Result<Value, Error> {
+    let id = parse_peer_id(&params.peer_id)?;
+
+    send(context, Command::BanPeer { id })?;
+
+    Ok(Value::Null)
+}
+
+fn set_log_level(params: SetLogLevelParams) -> Result<Value, Error> {
+    let level = LevelFilter::from_str(&params.level)
+        .map_err(|_| Error::InvalidParams(format!("invalid log level {}", params.level)))?;
+    let previous = log_level::set(level).map_err(|ceiling| {
+        Error::InvalidParams(format!(
+            "log level {} is above {}, the level the logger was set up with",
+            level, ceiling
+        ))
+    })?;
+
+    info!("Log level changed from {} to {}.", previous, level);
+
+    Ok(json!({ "previous": previous.to_string().to_lowercase() }))
+}
+
+async fn create_snapshot<B: Backend>(context: &Context<B>, params: CreateSnapshotParams) -> Result<Value, Error> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let network_id = context.network_id;
+    let snapshot_index = *context.tangle.get_snapshot_index();
+    let solid_entry_points = context
+        .tangle
+        .get_solid_entry_points()
+        .into_iter()
+        .map(|(message_id, _)| message_id)
+        .collect::<HashSet<_>>();
+    let storage = context.storage.clone();
+    let ledger_index = context.ledger_index.clone();
+    let path = params.path;
+
+    // Storage streams can't be sent across threads, the ledger is therefore read on a dedicated one.
+    tokio::task::spawn_blocking(move || -> Result<Value, Error> {
+        // The ledger is held for the whole export so that the outputs are the ones at its index.
+        let (ledger_index, outputs) = futures::executor::block_on(async {
+            let ledger_index = ledger_index.read().await;
+            Ok::<_, Error>((**ledger_index, unspent_outputs(&*storage).await?))
+        })?;
+        let header = SnapshotHeader::new(Kind::Full, timestamp, network_id, snapshot_index, ledger_index);
+        let output_count = outputs.len();
+        let snapshot = Snapshot::new(header, solid_entry_points, outputs, Vec::new());
+        let tmp_path = format!("{}.tmp", path);
+
+        snapshot
+            .to_file(&tmp_path)
+            .map_err(|e| Error::Server(format!("Writing snapshot failed: {}", e)))?;
+        fs::rename(&tmp_path, &path).map_err(|e| Error::Server(format!("Writing snapshot failed: {}.", e)))?;
+
+        info!("Created snapshot {} at ledger index {}.", path, ledger_index);
+
+        Ok(json!({
+            "path": path,
+            "ledgerIndex": ledger_index,
+            "outputs": output_count,
+        }))
+    })
+    .await
+    .map_err(|e| Error::Internal(e.to_string()))?
+}
+
+async fn unspent_outputs<B: Backend>(storage: &B) -> Result<Vec<SnapshotOutput>, Error> {
+    let storage_error = |e: B::Error| Error::Server(format!("Reading the ledger failed: {}.", e));
+    let unspents = AsStream::<Unspent, ()>::stream(storage).await.map_err(storage_error)?;
+    pin_mut!(unspents);
+    let mut outputs = Vec::new();
+
+    while let Some((unspent, _)) = unspents.next().await {
+        let output_id = *unspent.id();
+        let output = Fetch::<OutputId, Output>::fetch(storage, &output_id)
+            .await
+            .map_err(storage_error)?
+            .ok_or_else(|| Error::Server(format!("Unspent output {} is missing.", output_id)))?;
+
+        // Snapshots can only hold single signature outputs.
+        if let transaction::Output::SignatureLockedSingle(inner) = output.inner() {
+            outputs.push(SnapshotOutput::new(
+                *output.message_id(),
+                UTXOInput::from(output_id),
+                inner.clone(),
+            ));
+        }
+    }
+
+    Ok(outputs)
+}
+
+async fn compact_database<B: Backend>(context: &Context<B>) -> Result<Value, Error> {
+    let storage = context.storage.clone();
+
+    tokio::task::spawn_blocking(move || storage.compact())
+        .await
+        .map_err(|e| Error::Internal(e.to_string()))?
+        .map_err(|e| Error::Server(format!("Compacting the database failed: {}.", e)))?;
+
+    Ok(Value::Null)
+}
+
+fn shutdown_node<B: Backend>(context: &Context<B>) -> Result<Value, Error> {
+    context
+        .shutdown
+        .send(())
+        .map_err(|_| Error::Server("The node is already shutting down.".to_string()))?;
+
+    Ok(Value::Null)
+}
+
+fn peer_metrics(metrics: &PeerMetrics) -> Value {
+    json!({
+        "messagesReceived": metrics.messages_received(),
+        "messagesSent": metrics.messages_sent(),
+        "newMessages": metrics.new_messages(),
+        "knownMessages": metrics.known_messages(),
+        "invalidMessages": metrics.invalid_messages(),
+        "heartbeatsReceived": metrics.heartbeats_received(),
+        "heartbeatsSent": metrics.heartbeats_sent(),
+    })
+}
+
+async fn node_status<B: Backend>(context: &Context<B>) -> Value {
+    let connected = Protocol::peers();
+    let manual_peers = context.manual_peers.read().unwrap().clone();
+    let mut peers = connected
+        .iter()
+        .map(|peer| {
+            json!({
+                "id": peer.id().to_string(),
+                "alias": manual_peers.alias(peer.id()),
+                "address": peer.address().to_string(),
+                "connected": true,
+                "metrics": peer_metrics(peer.metrics()),
+            })
+        })
+        .collect::<Vec<Value>>();
+
+    // Manual peers end with their id, e.g. `/ip4/1.2.3.4/tcp/15600/p2p/<id>`.
+    for address in manual_peers.peers().iter() {
+        if let Some(MultiaddrProtocol::P2p(multihash)) = address.iter().last() {
+            if let Ok(id) = PeerId::from_multihash(multihash) {
+                if !connected.iter().any(|peer| peer.id() == &id) {
+                    peers.push(json!({
+                        "id": id.to_string(),
+                        "alias": manual_peers.alias(&id),
+                        "address": address.to_string(),
+                        "connected": false,
+                        "metrics": Value::Null,
+                    }));
+                }
+            }
+        }
+    }
+
+    let tps = *context.tps.lock().unwrap();
+
+    json!({
+        "synced": context.tangle.is_synced(),
+        "milestones": {
+            "latest": *context.tangle.get_latest_milestone_index(),
+            "solid": *context.tangle.get_latest_solid_milestone_index(),
+            "snapshot": *context.tangle.get_snapshot_index(),
+            "pruning": *context.tangle.get_pruning_index(),
+        },
+        "tps": tps,
+        "tips": {
+            "total": context.tangle.num_tips().await,
+            "nonLazy": context.tangle.num_non_lazy_tips().await,
+        },
+        "queues": context
+            .worker_queues
+            .iter()
+            .map(|queue| json!({ "name": queue.name(), "depth": queue.depth() }))
+            .collect::<Vec<Value>>(),
+        "databaseSize": context.storage.size().ok().flatten(),
+        "peers": peers,
+    })
+}
+
+fn list_workers<B: Backend>(context: &Context<B>) -> Value {
+    Value::Array(
+        context
+            .workers
+            .iter()
+            .map(|worker| json!({ "name": worker.name, "dependencies": worker.dependencies }))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn multiaddress() {
+        let id = PeerId::random();
+        let (address, parsed_id) = parse_multiaddress(&format!("/ip4/1.2.3.4/tcp/15600/p2p/{}", id)).unwrap();
+
+        assert_eq!(address, "/ip4/1.2.3.4/tcp/15600".parse::<Multiaddr>().unwrap());
+        assert_eq!(parsed_id, id);
+        assert!(matches!(
+            parse_multiaddress("/ip4/1.2.3.4/tcp/15600"),
+            Err(Error::InvalidParams(_))
+        ));
+        assert!(matches!(
+            parse_multiaddress("1.2.3.4:15600"),
+            Err(Error::InvalidParams(_))
+        ));
+    }
+
+    #[test]
+    fn peer_id() {
+        let id = PeerId::random();
+
+        assert_eq!(parse_peer_id(&id.to_string()).unwrap(), id);
+        assert!(matches!(parse_peer_id("peer"), Err(Error::InvalidParams(_))));
+    }
+
+    #[test]
+    fn params() {
+        assert_eq!(
+            parse::<PeerParams>(json!({ "peerId": "peer" })).unwrap().peer_id,
+            "peer"
+        );
+        assert!(matches!(
+            parse::<PeerParams>(json!({ "peer_id": "peer" })),
+            Err(Error::InvalidParams(_))
+        ));
+        assert!(matches!(
+            parse::<AddPeerParams>(Value::Null),
+            Err(Error::InvalidParams(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_log_level() {
+        assert!(matches!(
+            set_log_level(SetLogLevelParams {
+                level: "verbose".to_string()
+            }),
+            Err(Error::InvalidParams(_))
+        ));
+    }
+}
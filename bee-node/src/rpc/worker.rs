@@ -0,0 +1,142 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::{
+    inner::BeeNode,
+    rpc::{
+        connection,
+        methods::{Context, Tps},
+        RpcConfig,
+    },
+    storage::Backend,
+};
+
+use bee_common_ext::{event::Bus, node::Node, worker::Worker};
+use bee_ledger::LedgerIndex;
+use bee_network::Network;
+use bee_peering::ManualPeers;
+use bee_protocol::{event::TpsMetricsUpdated, tangle::MsTangle, Protocol, StorageWorker, TangleWorker};
+
+use async_trait::async_trait;
+use futures::{select, stream::FuturesUnordered, FutureExt, StreamExt};
+use log::{error, info, warn};
+use tokio::net::UnixListener;
+
+use std::{
+    any::TypeId,
+    convert::Infallible,
+    fs,
+    io::ErrorKind,
+    os::unix::fs::FileTypeExt,
+    sync::{Arc, Mutex},
+};
+
+pub(crate) struct RpcWorker;
+
+#[async_trait]
+impl<B: Backend> Worker<BeeNode<B>> for RpcWorker {
+    type Config = (RpcConfig, Network, u64, ManualPeers, flume::Sender<()>);
+    type Error = Infallible;
+
+    fn dependencies() -> &'static [TypeId] {
+        let mut dependencies = vec![TypeId::of::<StorageWorker>(), TypeId::of::<TangleWorker>()];
+        // The status reports the depth of their queues.
+        dependencies.extend(Protocol::queue_workers());
+        dependencies.leak()
+    }
+
+    async fn start(node: &mut BeeNode<B>, config: Self::Config) -> Result<Self, Self::Error> {
+        let (config, network, network_id, manual_peers, shutdown_sender) = config;
+
+        let tps = Arc::new(Mutex::new(Tps::default()));
+        let updated_tps = tps.clone();
+        node.resource::<Bus>()
+            .add_listener::<Self, _, _>(move |metrics: &TpsMetricsUpdated| {
+                *updated_tps.lock().unwrap() = Tps::from(metrics)
+            });
+
+        let context = Arc::new(Context {
+            network,
+            network_id,
+            tangle: node.resource::<MsTangle<B>>(),
+            storage: node.storage(),
+            ledger_index: node.resource::<LedgerIndex>(),
+            workers: node.worker_infos(),
+            worker_queues: Protocol::worker_queues(node),
+            manual_peers,
+            tps,
+            shutdown: shutdown_sender,
+        });
+
+        // A socket left behind by a node that didn't stop properly would prevent binding, anything else is kept.
+        match fs::symlink_metadata(config.path()) {
+            Ok(metadata) if metadata.file_type().is_socket() => {
+                if let Err(e) = fs::remove_file(config.path()) {
+                    error!("Removing stale socket {} failed: {}.", config.path().display(), e);
+                    return Ok(Self);
+                }
+            }
+            Ok(_) => {
+                error!(
+                    "{} exists and is not a socket, the RPC server is disabled.",
+                    config.path().display()
+                );
+                return Ok(Self);
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => (),
+            Err(e) => {
+                error!("Accessing {} failed: {}.", config.path().display(), e);
+                return Ok(Self);
+            }
+        }
+
+        // There is no authentication, whoever can open the socket is in full control of the node. It is therefore
+        // created accessible to the owner only, restricting it after binding would leave a window open. The umask is
+        // process wide, files created by other threads meanwhile only end up more restricted.
+        let umask = unsafe { libc::umask(0o177) };
+        let listener = UnixListener::bind(config.path());
+        unsafe { libc::umask(umask) };
+
+        let mut listener = match listener {
+            Ok(listener) => listener,
+            Err(e) => {
+                error!("Binding to {} failed: {}.", config.path().display(), e);
+                return Ok(Self);
+            }
+        };
+
+        node.spawn::<Self, _, _>(|shutdown| async move {
+            info!("Listening on {}.", config.path().display());
+
+            let mut shutdown = shutdown.fuse();
+            let mut connections = FuturesUnordered::new();
+
+            loop {
+                select! {
+                    _ = shutdown => break,
+                    accepted = listener.accept().fuse() => match accepted {
+                        Ok((stream, _)) => connections.push(connection::handle(stream, context.clone())),
+                        Err(e) => warn!("Accepting RPC connection failed: {}.", e),
+                    },
+                    _ = connections.select_next_some() => (),
+                }
+            }
+
+            if let Err(e) = fs::remove_file(config.path()) {
+                warn!("Removing socket {} failed: {}.", config.path().display(), e);
+            }
+
+            info!("Stopped.");
+        });
+
+        Ok(Self)
+    }
+}
@@ -0,0 +1,94 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use serde::Deserialize;
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+const DEFAULT_WORKER_TIMEOUT_SEC: u64 = 10;
+const DEFAULT_MARKER_FILE_NAME: &str = "shutdown.marker";
+
+#[derive(Default, Deserialize)]
+pub struct ShutdownConfigBuilder {
+    worker_timeout_sec: Option<u64>,
+    #[serde(default)]
+    worker_timeouts_sec: HashMap<String, u64>,
+    marker_path: Option<PathBuf>,
+}
+
+impl ShutdownConfigBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn worker_timeout_sec(mut self, worker_timeout_sec: u64) -> Self {
+        self.worker_timeout_sec.replace(worker_timeout_sec);
+        self
+    }
+
+    pub fn worker_timeouts_sec(mut self, worker: String, timeout_sec: u64) -> Self {
+        self.worker_timeouts_sec.insert(worker, timeout_sec);
+        self
+    }
+
+    pub fn marker_path(mut self, marker_path: PathBuf) -> Self {
+        self.marker_path.replace(marker_path);
+        self
+    }
+
+    /// Finishes the config, the marker file being kept in `dir` unless a path is set.
+    pub fn finish(self, dir: &Path) -> ShutdownConfig {
+        ShutdownConfig {
+            worker_timeout: Duration::from_secs(self.worker_timeout_sec.unwrap_or(DEFAULT_WORKER_TIMEOUT_SEC)),
+            worker_timeouts: self
+                .worker_timeouts_sec
+                .into_iter()
+                .map(|(worker, timeout_sec)| (worker, Duration::from_secs(timeout_sec)))
+                .collect(),
+            marker_path: self.marker_path.unwrap_or_else(|| dir.join(DEFAULT_MARKER_FILE_NAME)),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ShutdownConfig {
+    pub(crate) worker_timeout: Duration,
+    pub(crate) worker_timeouts: HashMap<String, Duration>,
+    pub(crate) marker_path: PathBuf,
+}
+
+impl Default for ShutdownConfig {
+    fn default() -> Self {
+        ShutdownConfigBuilder::default().finish(Path::new("."))
+    }
+}
+
+impl ShutdownConfig {
+    pub fn build() -> ShutdownConfigBuilder {
+        ShutdownConfigBuilder::new()
+    }
+
+    /// Time given to a worker to stop, looked up by its type name without path nor generic parameters.
+    pub fn worker_timeout(&self, worker: &str) -> Duration {
+        let name = worker.split('<').next().unwrap_or(worker);
+        let name = name.rsplit("::").next().unwrap_or(name);
+
+        self.worker_timeouts.get(name).copied().unwrap_or(self.worker_timeout)
+    }
+
+    pub fn marker_path(&self) -> &Path {
+        &self.marker_path
+    }
+}
@@ -0,0 +1,95 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+mod config;
+
+pub use config::{ShutdownConfig, ShutdownConfigBuilder};
+
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::Path,
+};
+
+const RUNNING: &str = "running";
+const CLEAN: &str = "clean";
+
+/// Marks the node as running and returns whether the previous run, if any, shut down cleanly.
+pub(crate) fn mark_running(path: &Path) -> Result<bool, io::Error> {
+    let clean = match fs::read_to_string(path) {
+        Ok(state) => state.trim() != RUNNING,
+        Err(e) if e.kind() == ErrorKind::NotFound => true,
+        Err(e) => return Err(e),
+    };
+
+    fs::write(path, RUNNING)?;
+
+    Ok(clean)
+}
+
+/// Marks the node as cleanly shut down, to be checked by `mark_running` at the next start.
+pub(crate) fn mark_clean(path: &Path) -> Result<(), io::Error> {
+    fs::write(path, CLEAN)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use std::{env, path::PathBuf, time::Duration};
+
+    #[test]
+    fn marker() {
+        let path = env::temp_dir().join(format!("bee-shutdown-{}.marker", std::process::id()));
+
+        assert!(mark_running(&path).unwrap());
+        assert!(!mark_running(&path).unwrap());
+        mark_clean(&path).unwrap();
+        assert!(mark_running(&path).unwrap());
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn worker_timeout() {
+        let config = ShutdownConfig::build()
+            .worker_timeout_sec(5)
+            .worker_timeouts_sec("StorageWorker".to_string(), 30)
+            .finish(Path::new("."));
+
+        assert_eq!(
+            config.worker_timeout("bee_protocol::worker::storage::StorageWorker"),
+            Duration::from_secs(30)
+        );
+        assert_eq!(
+            config.worker_timeout("bee_protocol::worker::tps::TpsWorker<bee_node::Foo>"),
+            Duration::from_secs(5)
+        );
+    }
+
+    #[test]
+    fn default_marker_in_dir() {
+        let dir = Path::new("./database/alphanet");
+
+        assert_eq!(
+            ShutdownConfig::build().finish(dir).marker_path(),
+            dir.join("shutdown.marker")
+        );
+        assert_eq!(
+            ShutdownConfig::build()
+                .marker_path(PathBuf::from("./bee.marker"))
+                .finish(dir)
+                .marker_path(),
+            Path::new("./bee.marker")
+        );
+    }
+}
@@ -9,9 +9,17 @@
 // an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and limitations under the License.
 
-use bee_ledger::storage::Backend as LedgerBackend;
-use bee_storage::storage;
+use bee_api::storage::Backend as ApiBackend;
+use bee_ledger::{storage::Backend as LedgerBackend, unspent::Unspent};
+use bee_protocol::storage::Backend as ProtocolBackend;
+use bee_storage::{access::AsStream, storage};
 
-pub trait Backend: storage::Backend + LedgerBackend {}
+pub trait Backend:
+    storage::Backend + ApiBackend + LedgerBackend + ProtocolBackend + for<'a> AsStream<'a, Unspent, ()>
+{
+}
 
-impl<T> Backend for T where T: storage::Backend + LedgerBackend {}
+impl<T> Backend for T where
+    T: storage::Backend + ApiBackend + LedgerBackend + ProtocolBackend + for<'a> AsStream<'a, Unspent, ()>
+{
+}
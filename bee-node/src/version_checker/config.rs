@@ -0,0 +1,79 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use serde::Deserialize;
+
+use std::time::Duration;
+
+const DEFAULT_INTERVAL_SEC: u64 = 3600;
+const DEFAULT_TIMEOUT_SEC: u64 = 10;
+
+#[derive(Default, Deserialize)]
+pub struct VersionCheckerConfigBuilder {
+    manifest: Option<String>,
+    interval_sec: Option<u64>,
+    timeout_sec: Option<u64>,
+}
+
+impl VersionCheckerConfigBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn manifest(mut self, manifest: String) -> Self {
+        self.manifest.replace(manifest);
+        self
+    }
+
+    pub fn interval_sec(mut self, interval_sec: u64) -> Self {
+        self.interval_sec.replace(interval_sec);
+        self
+    }
+
+    pub fn timeout_sec(mut self, timeout_sec: u64) -> Self {
+        self.timeout_sec.replace(timeout_sec);
+        self
+    }
+
+    pub fn finish(self) -> VersionCheckerConfig {
+        VersionCheckerConfig {
+            manifest: self.manifest.filter(|manifest| !manifest.is_empty()),
+            interval: Duration::from_secs(self.interval_sec.unwrap_or(DEFAULT_INTERVAL_SEC)),
+            timeout: Duration::from_secs(self.timeout_sec.unwrap_or(DEFAULT_TIMEOUT_SEC)),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct VersionCheckerConfig {
+    pub(crate) manifest: Option<String>,
+    pub(crate) interval: Duration,
+    pub(crate) timeout: Duration,
+}
+
+impl VersionCheckerConfig {
+    pub fn build() -> VersionCheckerConfigBuilder {
+        VersionCheckerConfigBuilder::new()
+    }
+
+    /// URL or local path of the release manifest; versions aren't checked without one.
+    pub fn manifest(&self) -> Option<&str> {
+        self.manifest.as_deref()
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+}
@@ -0,0 +1,167 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use reqwest::Client;
+use semver::Version;
+use serde::Deserialize;
+use thiserror::Error;
+
+use std::{fs, io};
+
+#[derive(Debug, Error)]
+pub(crate) enum Error {
+    #[error("Fetching the manifest failed: {0}.")]
+    Request(#[from] reqwest::Error),
+
+    #[error("Reading the manifest failed: {0}.")]
+    Io(#[from] io::Error),
+
+    #[error("Parsing the manifest failed: {0}.")]
+    Parse(#[from] serde_json::Error),
+
+    #[error("Invalid version {0} in the manifest: {1}.")]
+    Version(String, semver::SemVerError),
+}
+
+/// A release manifest, e.g.
+/// `{"latest": "0.2.0", "url": "https://...", "minimumSecure": "0.1.5", "insecure": ["0.1.7"]}`.
+/// Versions older than `minimumSecure`, as well as the ones listed in `insecure`, are critically insecure.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Manifest {
+    latest: String,
+    url: Option<String>,
+    minimum_secure: Option<String>,
+    #[serde(default)]
+    insecure: Vec<String>,
+}
+
+/// How a version compares to a manifest.
+pub(crate) struct Assessment {
+    /// The latest version, if newer.
+    pub(crate) newer: Option<Version>,
+    pub(crate) insecure: bool,
+}
+
+fn parse(version: &str) -> Result<Version, Error> {
+    Version::parse(version).map_err(|e| Error::Version(version.to_string(), e))
+}
+
+impl Manifest {
+    /// Where to get the latest version.
+    pub(crate) fn url(&self) -> Option<&str> {
+        self.url.as_deref()
+    }
+
+    pub(crate) fn assess(&self, current: &Version) -> Result<Assessment, Error> {
+        let latest = parse(&self.latest)?;
+        let below_minimum = match self.minimum_secure.as_deref() {
+            Some(minimum) => *current < parse(minimum)?,
+            None => false,
+        };
+        let mut listed = false;
+        for version in self.insecure.iter() {
+            listed |= parse(version)? == *current;
+        }
+
+        Ok(Assessment {
+            newer: if latest > *current { Some(latest) } else { None },
+            insecure: below_minimum || listed,
+        })
+    }
+}
+
+/// Fetches the manifest from an `http(s)://` URL, or reads it from a local path.
+pub(crate) async fn fetch(client: &Client, location: &str) -> Result<Manifest, Error> {
+    let bytes = if location.starts_with("http://") || location.starts_with("https://") {
+        client
+            .get(location)
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?
+            .to_vec()
+    } else {
+        fs::read(location.trim_start_matches("file://"))?
+    };
+
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use hyper::{
+        service::{make_service_fn, service_fn},
+        Body, Request, Response, Server,
+    };
+
+    use std::{convert::Infallible, env};
+
+    const MANIFEST: &str =
+        r#"{"latest": "0.2.0", "url": "https://example.com", "minimumSecure": "0.1.5", "insecure": ["0.1.7"]}"#;
+
+    fn assess(version: &str) -> (Option<String>, bool) {
+        let assessment = serde_json::from_str::<Manifest>(MANIFEST)
+            .unwrap()
+            .assess(&Version::parse(version).unwrap())
+            .unwrap();
+
+        (assessment.newer.map(|newer| newer.to_string()), assessment.insecure)
+    }
+
+    #[test]
+    fn assess_versions() {
+        let latest = Some("0.2.0".to_string());
+
+        assert_eq!(assess("0.1.0-alpha"), (latest.clone(), true));
+        assert_eq!(assess("0.1.6"), (latest.clone(), false));
+        assert_eq!(assess("0.1.7"), (latest.clone(), true));
+        assert_eq!(assess("0.2.0-rc.1"), (latest, false));
+        assert_eq!(assess("0.2.0"), (None, false));
+        assert_eq!(assess("0.3.0"), (None, false));
+    }
+
+    #[test]
+    fn invalid_version() {
+        let manifest = serde_json::from_str::<Manifest>(r#"{"latest": "latest"}"#).unwrap();
+
+        assert!(matches!(
+            manifest.assess(&Version::parse("0.1.0").unwrap()),
+            Err(Error::Version(..))
+        ));
+    }
+
+    #[tokio::test]
+    async fn fetch_from_http_and_file() {
+        let make_service = make_service_fn(|_| async {
+            Ok::<_, Infallible>(service_fn(|_: Request<Body>| async {
+                Ok::<_, Infallible>(Response::new(Body::from(MANIFEST)))
+            }))
+        });
+        let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let url = format!("http://{}/manifest.json", server.local_addr());
+        tokio::spawn(server);
+
+        let manifest = fetch(&Client::new(), &url).await.unwrap();
+        assert_eq!(manifest.latest, "0.2.0");
+        assert_eq!(manifest.url(), Some("https://example.com"));
+
+        let path = env::temp_dir().join(format!("bee-manifest-{}.json", std::process::id()));
+        fs::write(&path, MANIFEST).unwrap();
+        let manifest = fetch(&Client::new(), path.to_str().unwrap()).await.unwrap();
+        assert_eq!(manifest.insecure, vec!["0.1.7".to_string()]);
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,27 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+mod config;
+mod manifest;
+mod worker;
+
+pub use config::{VersionCheckerConfig, VersionCheckerConfigBuilder};
+pub(crate) use worker::VersionCheckerWorker;
+
+/// Dispatched when the release manifest announces a version newer than the running one.
+pub struct NewVersionAvailable {
+    pub current: String,
+    pub latest: String,
+    /// Where to get the latest version.
+    pub url: Option<String>,
+    /// Whether the running version is critically insecure.
+    pub critical: bool,
+}
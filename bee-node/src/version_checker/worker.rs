@@ -0,0 +1,113 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::{
+    constants::BEE_VERSION,
+    version_checker::{manifest, NewVersionAvailable, VersionCheckerConfig},
+};
+
+use bee_common::shutdown_stream::ShutdownStream;
+use bee_common_ext::{event::Bus, node::Node, worker::Worker};
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use log::{error, info, warn};
+use reqwest::Client;
+use semver::Version;
+use tokio::time::interval;
+
+use std::convert::Infallible;
+
+#[derive(Default)]
+pub(crate) struct VersionCheckerWorker {}
+
+#[async_trait]
+impl<N: Node> Worker<N> for VersionCheckerWorker {
+    type Config = VersionCheckerConfig;
+    type Error = Infallible;
+
+    async fn start(node: &mut N, config: Self::Config) -> Result<Self, Self::Error> {
+        let location = match config.manifest() {
+            Some(location) => location.to_string(),
+            None => {
+                info!("No release manifest configured, versions won't be checked.");
+                return Ok(Self::default());
+            }
+        };
+        let bus = node.resource::<Bus>();
+
+        node.spawn::<Self, _, _>(|shutdown| async move {
+            info!("Running.");
+
+            let current = Version::parse(BEE_VERSION).expect("invalid crate version");
+            let client = match Client::builder().timeout(config.timeout()).build() {
+                Ok(client) => client,
+                Err(e) => {
+                    error!("Creating the HTTP client failed: {}.", e);
+                    return;
+                }
+            };
+            // The latest version the node was notified of, so that it is only announced once.
+            let mut notified = None;
+
+            let mut ticker = ShutdownStream::new(shutdown, interval(config.interval()));
+
+            while ticker.next().await.is_some() {
+                let (manifest, assessment) = match manifest::fetch(&client, &location).await {
+                    Ok(manifest) => match manifest.assess(&current) {
+                        Ok(assessment) => (manifest, assessment),
+                        Err(e) => {
+                            warn!("Checking for a new version failed: {}", e);
+                            continue;
+                        }
+                    },
+                    Err(e) => {
+                        warn!("Checking for a new version failed: {}", e);
+                        continue;
+                    }
+                };
+                let url = manifest.url().map(|url| format!(", see {}", url)).unwrap_or_default();
+
+                if assessment.insecure {
+                    error!(
+                        "Version {} is critically insecure, update to {} as soon as possible{}.",
+                        current,
+                        assessment
+                            .newer
+                            .as_ref()
+                            .map_or("a secure version".to_string(), ToString::to_string),
+                        url
+                    );
+                }
+
+                if let Some(latest) = assessment.newer {
+                    if notified.as_ref() == Some(&latest) {
+                        continue;
+                    }
+                    if !assessment.insecure {
+                        warn!("Version {} is available, running {}{}.", latest, current, url);
+                    }
+                    bus.dispatch(NewVersionAvailable {
+                        current: current.to_string(),
+                        latest: latest.to_string(),
+                        url: manifest.url().map(ToString::to_string),
+                        critical: assessment.insecure,
+                    });
+                    notified = Some(latest);
+                }
+            }
+
+            info!("Stopped.");
+        });
+
+        Ok(Self::default())
+    }
+}
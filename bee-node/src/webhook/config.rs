@@ -0,0 +1,237 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use serde::{Deserialize, Serialize};
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+const DEFAULT_QUEUE_PATH: &str = "./webhooks";
+const DEFAULT_MAX_ATTEMPTS: u32 = 10;
+const DEFAULT_INITIAL_BACKOFF_SEC: u64 = 1;
+const DEFAULT_MAX_BACKOFF_SEC: u64 = 3600;
+const DEFAULT_TIMEOUT_SEC: u64 = 10;
+
+/// The node events that can be delivered to a webhook.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Event {
+    MilestoneConfirmed,
+    LatestSolidMilestoneChanged,
+    OutputCreated,
+}
+
+impl Event {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Event::MilestoneConfirmed => "milestoneConfirmed",
+            Event::LatestSolidMilestoneChanged => "latestSolidMilestoneChanged",
+            Event::OutputCreated => "outputCreated",
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct HookConfigBuilder {
+    url: String,
+    secret: Option<String>,
+    events: Option<Vec<Event>>,
+    addresses: Option<Vec<String>>,
+}
+
+impl HookConfigBuilder {
+    pub fn new(url: String) -> Self {
+        Self {
+            url,
+            secret: None,
+            events: None,
+            addresses: None,
+        }
+    }
+
+    pub fn secret(mut self, secret: String) -> Self {
+        self.secret.replace(secret);
+        self
+    }
+
+    pub fn events(mut self, events: Vec<Event>) -> Self {
+        self.events.replace(events);
+        self
+    }
+
+    pub fn addresses(mut self, addresses: Vec<String>) -> Self {
+        self.addresses.replace(addresses);
+        self
+    }
+
+    pub fn finish(self) -> HookConfig {
+        HookConfig {
+            url: self.url,
+            secret: self.secret,
+            events: self
+                .events
+                .unwrap_or_else(|| {
+                    vec![
+                        Event::MilestoneConfirmed,
+                        Event::LatestSolidMilestoneChanged,
+                        Event::OutputCreated,
+                    ]
+                })
+                .into_iter()
+                .collect(),
+            addresses: self
+                .addresses
+                .unwrap_or_default()
+                .iter()
+                .map(|address| address.to_lowercase())
+                .collect(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct HookConfig {
+    url: String,
+    secret: Option<String>,
+    events: HashSet<Event>,
+    addresses: HashSet<String>,
+}
+
+impl HookConfig {
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Key of the HMAC-SHA256 signature sent along with every delivery, deliveries are unsigned without it.
+    pub fn secret(&self) -> Option<&str> {
+        self.secret.as_deref()
+    }
+
+    pub fn wants(&self, event: Event) -> bool {
+        self.events.contains(&event)
+    }
+
+    /// Returns whether outputs created for the bech32 `address` are delivered to this hook.
+    pub fn watches(&self, address: &str) -> bool {
+        self.addresses.contains(address)
+    }
+}
+
+#[derive(Default, Deserialize)]
+pub struct WebhookConfigBuilder {
+    queue_path: Option<PathBuf>,
+    max_attempts: Option<u32>,
+    initial_backoff_sec: Option<u64>,
+    max_backoff_sec: Option<u64>,
+    timeout_sec: Option<u64>,
+    hooks: Option<Vec<HookConfigBuilder>>,
+}
+
+impl WebhookConfigBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn queue_path(mut self, queue_path: PathBuf) -> Self {
+        self.queue_path.replace(queue_path);
+        self
+    }
+
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts.replace(max_attempts);
+        self
+    }
+
+    pub fn initial_backoff_sec(mut self, initial_backoff_sec: u64) -> Self {
+        self.initial_backoff_sec.replace(initial_backoff_sec);
+        self
+    }
+
+    pub fn max_backoff_sec(mut self, max_backoff_sec: u64) -> Self {
+        self.max_backoff_sec.replace(max_backoff_sec);
+        self
+    }
+
+    pub fn timeout_sec(mut self, timeout_sec: u64) -> Self {
+        self.timeout_sec.replace(timeout_sec);
+        self
+    }
+
+    pub fn hook(mut self, hook: HookConfigBuilder) -> Self {
+        self.hooks.get_or_insert_with(Vec::new).push(hook);
+        self
+    }
+
+    pub fn finish(self) -> WebhookConfig {
+        WebhookConfig {
+            queue_path: self.queue_path.unwrap_or_else(|| PathBuf::from(DEFAULT_QUEUE_PATH)),
+            max_attempts: self.max_attempts.unwrap_or(DEFAULT_MAX_ATTEMPTS).max(1),
+            initial_backoff: Duration::from_secs(self.initial_backoff_sec.unwrap_or(DEFAULT_INITIAL_BACKOFF_SEC)),
+            max_backoff: Duration::from_secs(self.max_backoff_sec.unwrap_or(DEFAULT_MAX_BACKOFF_SEC)),
+            timeout: Duration::from_secs(self.timeout_sec.unwrap_or(DEFAULT_TIMEOUT_SEC)),
+            hooks: self
+                .hooks
+                .unwrap_or_default()
+                .into_iter()
+                .map(HookConfigBuilder::finish)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct WebhookConfig {
+    queue_path: PathBuf,
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    timeout: Duration,
+    hooks: Vec<HookConfig>,
+}
+
+impl WebhookConfig {
+    pub fn build() -> WebhookConfigBuilder {
+        WebhookConfigBuilder::new()
+    }
+
+    /// Directory holding the deliveries that haven't succeeded yet, they are resumed on restart.
+    pub fn queue_path(&self) -> &Path {
+        &self.queue_path
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Returns the delay before the next attempt of a delivery that already failed `attempts` times.
+    pub fn backoff(&self, attempts: u32) -> Duration {
+        let factor = 2u32.checked_pow(attempts.saturating_sub(1)).unwrap_or(u32::MAX);
+
+        self.initial_backoff
+            .checked_mul(factor)
+            .map_or(self.max_backoff, |backoff| backoff.min(self.max_backoff))
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    pub fn hooks(&self) -> &[HookConfig] {
+        &self.hooks
+    }
+
+    pub fn hook(&self, url: &str) -> Option<&HookConfig> {
+        self.hooks.iter().find(|hook| hook.url == url)
+    }
+}
@@ -0,0 +1,202 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::webhook::{
+    config::WebhookConfig,
+    queue::{Delivery, Queue},
+};
+
+use hmac::{Hmac, Mac, NewMac};
+use log::{debug, warn};
+use reqwest::{header::CONTENT_TYPE, Client};
+use sha2::Sha256;
+use thiserror::Error;
+use tokio::time::delay_for;
+
+use std::sync::Arc;
+
+pub(crate) const HEADER_EVENT: &str = "X-Bee-Event";
+pub(crate) const HEADER_DELIVERY: &str = "X-Bee-Delivery";
+pub(crate) const HEADER_SIGNATURE: &str = "X-Bee-Signature";
+
+#[derive(Debug, Error)]
+pub(crate) enum Error {
+    #[error("Request failed: {0}.")]
+    Request(#[from] reqwest::Error),
+
+    #[error("Unexpected status code {0}.")]
+    Status(u16),
+}
+
+/// Returns the `sha256=<hex>` HMAC signature of `body`, for receivers to authenticate deliveries.
+pub(crate) fn sign(secret: &str, body: &[u8]) -> String {
+    // HMAC accepts keys of any length.
+    let mut mac = Hmac::<Sha256>::new_varkey(secret.as_bytes()).unwrap();
+    mac.update(body);
+
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+async fn post(client: &Client, delivery: &Delivery, secret: Option<&str>) -> Result<(), Error> {
+    let mut request = client
+        .post(&delivery.url)
+        .header(CONTENT_TYPE, "application/json")
+        .header(HEADER_EVENT, delivery.event.name())
+        .header(HEADER_DELIVERY, &delivery.id);
+
+    if let Some(secret) = secret {
+        request = request.header(HEADER_SIGNATURE, sign(secret, delivery.body.as_bytes()));
+    }
+
+    let status = request.body(delivery.body.clone()).send().await?.status();
+
+    if status.is_success() {
+        Ok(())
+    } else {
+        Err(Error::Status(status.as_u16()))
+    }
+}
+
+/// Posts the delivery until it succeeds or runs out of attempts, then drops it from the queue.
+pub(crate) async fn deliver(client: Client, config: Arc<WebhookConfig>, queue: Arc<Queue>, mut delivery: Delivery) {
+    loop {
+        let secret = match config.hook(&delivery.url) {
+            Some(hook) => hook.secret().map(ToOwned::to_owned),
+            None => {
+                warn!(
+                    "Dropping webhook delivery {}, {} is no longer configured.",
+                    delivery.id, delivery.url
+                );
+                break;
+            }
+        };
+
+        match post(&client, &delivery, secret.as_deref()).await {
+            Ok(()) => {
+                debug!("Delivered {} to {}.", delivery.id, delivery.url);
+                break;
+            }
+            Err(e) => {
+                delivery.attempts += 1;
+
+                if delivery.attempts >= config.max_attempts() {
+                    warn!(
+                        "Giving up on webhook delivery {} to {} after {} attempts: {}",
+                        delivery.id, delivery.url, delivery.attempts, e
+                    );
+                    break;
+                }
+
+                let backoff = config.backoff(delivery.attempts);
+                debug!(
+                    "Webhook delivery {} to {} failed, retrying in {:?}: {}",
+                    delivery.id, delivery.url, backoff, e
+                );
+
+                // Keeps the attempt count across restarts.
+                if let Err(e) = queue.store(&delivery) {
+                    warn!("Persisting webhook delivery {} failed: {}.", delivery.id, e);
+                }
+
+                delay_for(backoff).await;
+            }
+        }
+    }
+
+    if let Err(e) = queue.remove(&delivery) {
+        warn!("Removing webhook delivery {} failed: {}.", delivery.id, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use crate::webhook::config::{Event, HookConfigBuilder};
+
+    use hyper::{
+        service::{make_service_fn, service_fn},
+        Body, Request, Response, Server, StatusCode,
+    };
+
+    use std::{
+        convert::Infallible,
+        env, fs,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Mutex,
+        },
+    };
+
+    #[test]
+    fn sign_known_vector() {
+        // RFC 4231, test case 2.
+        assert_eq!(
+            sign("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[tokio::test]
+    async fn deliver_retries_until_success() {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let received = Arc::new(Mutex::new(Vec::new()));
+
+        let (r, c) = (requests.clone(), received.clone());
+        let make_service = make_service_fn(move |_| {
+            let (requests, received) = (r.clone(), c.clone());
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                    let (requests, received) = (requests.clone(), received.clone());
+                    async move {
+                        let signature = request.headers()[HEADER_SIGNATURE].to_str().unwrap().to_string();
+                        let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+                        received.lock().unwrap().push((signature, body.to_vec()));
+
+                        // The first attempt fails to exercise the retry.
+                        let status = if requests.fetch_add(1, Ordering::SeqCst) == 0 {
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        } else {
+                            StatusCode::OK
+                        };
+
+                        Ok::<_, Infallible>(Response::builder().status(status).body(Body::empty()).unwrap())
+                    }
+                }))
+            }
+        });
+        let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let url = format!("http://{}/hook", server.local_addr());
+        tokio::spawn(server);
+
+        let config = Arc::new(
+            WebhookConfig::build()
+                .initial_backoff_sec(0)
+                .hook(HookConfigBuilder::new(url.clone()).secret("secret".to_string()))
+                .finish(),
+        );
+        let path = env::temp_dir().join(format!("bee-webhook-delivery-{}", std::process::id()));
+        let queue = Arc::new(Queue::open(&path).unwrap());
+        let delivery = Delivery::new(url, Event::MilestoneConfirmed, r#"{"index":1}"#.to_string());
+        queue.store(&delivery).unwrap();
+
+        deliver(Client::new(), config, queue.clone(), delivery).await;
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[1].0, sign("secret", br#"{"index":1}"#));
+        assert_eq!(received[1].1, br#"{"index":1}"#.to_vec());
+        assert!(queue.load().unwrap().is_empty());
+
+        fs::remove_dir_all(&path).unwrap();
+    }
+}
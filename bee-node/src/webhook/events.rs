@@ -0,0 +1,162 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::webhook::{
+    config::{Event, HookConfig, WebhookConfig},
+    queue::Delivery,
+    worker::WebhookWorker,
+};
+
+use bee_common_ext::event::Bus;
+use bee_ledger::event::{MilestoneConfirmed, OutputCreated};
+use bee_message::payload::transaction::Output;
+use bee_protocol::event::LatestSolidMilestoneChanged;
+
+use log::warn;
+use serde::Serialize;
+
+use std::{
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Payload<'a, T: Serialize> {
+    event: Event,
+    timestamp: u64,
+    data: &'a T,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MilestoneConfirmedData {
+    index: u32,
+    timestamp: u64,
+    messages_referenced: usize,
+    messages_excluded_no_transaction: usize,
+    messages_excluded_conflicting: usize,
+    messages_included: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LatestSolidMilestoneChangedData {
+    index: u32,
+    message_id: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OutputCreatedData {
+    message_id: String,
+    transaction_id: String,
+    output_index: u16,
+    address: String,
+    amount: u64,
+}
+
+/// Hands a delivery of `data` to every hook accepted by `filter` over to the worker, which persists them; listeners
+/// run within the emitting workers, e.g. the ledger confirmation, so they must not block on file I/O.
+fn enqueue<T: Serialize>(
+    config: &WebhookConfig,
+    sender: &flume::Sender<Delivery>,
+    event: Event,
+    data: &T,
+    filter: impl Fn(&HookConfig) -> bool,
+) {
+    let hooks = config
+        .hooks()
+        .iter()
+        .filter(|hook| hook.wants(event) && filter(hook))
+        .collect::<Vec<_>>();
+
+    if hooks.is_empty() {
+        return;
+    }
+
+    let payload = Payload {
+        event,
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default(),
+        data,
+    };
+    let body = match serde_json::to_string(&payload) {
+        Ok(body) => body,
+        Err(e) => {
+            warn!("Serializing {} webhook payload failed: {}.", event.name(), e);
+            return;
+        }
+    };
+
+    for hook in hooks {
+        if let Err(e) = sender.send(Delivery::new(hook.url().to_string(), event, body.clone())) {
+            warn!("Sending webhook delivery to the worker failed: {}.", e);
+        }
+    }
+}
+
+pub(crate) fn register(bus: &Bus, config: Arc<WebhookConfig>, sender: flume::Sender<Delivery>) {
+    let (c, s) = (config.clone(), sender.clone());
+    bus.add_listener::<WebhookWorker, _, _>(move |confirmed: &MilestoneConfirmed| {
+        enqueue(
+            &c,
+            &s,
+            Event::MilestoneConfirmed,
+            &MilestoneConfirmedData {
+                index: *confirmed.index,
+                timestamp: confirmed.timestamp,
+                messages_referenced: confirmed.messages_referenced,
+                messages_excluded_no_transaction: confirmed.messages_excluded_no_transaction,
+                messages_excluded_conflicting: confirmed.messages_excluded_conflicting,
+                messages_included: confirmed.messages_included,
+            },
+            |_| true,
+        )
+    });
+
+    let (c, s) = (config.clone(), sender.clone());
+    bus.add_listener::<WebhookWorker, _, _>(move |latest_solid_milestone: &LatestSolidMilestoneChanged| {
+        enqueue(
+            &c,
+            &s,
+            Event::LatestSolidMilestoneChanged,
+            &LatestSolidMilestoneChangedData {
+                index: *latest_solid_milestone.0.index(),
+                message_id: latest_solid_milestone.0.message_id().to_string(),
+            },
+            |_| true,
+        )
+    });
+
+    bus.add_listener::<WebhookWorker, _, _>(move |created: &OutputCreated| {
+        let (address, amount) = match created.output.inner() {
+            Output::SignatureLockedSingle(output) => (output.address().to_bech32(), output.amount().get()),
+            _ => return,
+        };
+
+        enqueue(
+            &config,
+            &sender,
+            Event::OutputCreated,
+            &OutputCreatedData {
+                message_id: created.output.message_id().to_string(),
+                transaction_id: created.output_id.transaction_id().to_string(),
+                output_index: created.output_id.index(),
+                address: address.clone(),
+                amount,
+            },
+            |hook| hook.watches(&address),
+        )
+    });
+}
@@ -0,0 +1,154 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::webhook::config::Event;
+
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const EXTENSION: &str = "json";
+
+static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// A payload waiting to be posted to a hook.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct Delivery {
+    pub(crate) id: String,
+    pub(crate) url: String,
+    pub(crate) event: Event,
+    pub(crate) body: String,
+    pub(crate) attempts: u32,
+}
+
+impl Delivery {
+    pub(crate) fn new(url: String, event: Event, body: String) -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos())
+            .unwrap_or_default();
+
+        Self {
+            // Ids sort in creation order, which is the order deliveries are resumed in after a restart.
+            id: format!(
+                "{:024}-{:06}",
+                nanos,
+                SEQUENCE.fetch_add(1, Ordering::Relaxed) % 1_000_000
+            ),
+            url,
+            event,
+            body,
+            attempts: 0,
+        }
+    }
+}
+
+/// Deliveries persisted as one file each until they succeed or are given up on.
+pub(crate) struct Queue {
+    path: PathBuf,
+}
+
+impl Queue {
+    pub(crate) fn open(path: &Path) -> io::Result<Self> {
+        fs::create_dir_all(path)?;
+
+        Ok(Self { path: path.to_owned() })
+    }
+
+    fn file(&self, delivery: &Delivery) -> PathBuf {
+        self.path.join(&delivery.id).with_extension(EXTENSION)
+    }
+
+    /// Returns the pending deliveries, oldest first.
+    pub(crate) fn load(&self) -> io::Result<Vec<Delivery>> {
+        let mut files = fs::read_dir(&self.path)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().map_or(false, |extension| extension == EXTENSION))
+            .collect::<Vec<_>>();
+        files.sort();
+
+        Ok(files
+            .iter()
+            .filter_map(|file| {
+                match fs::read(file)
+                    .map_err(|e| e.to_string())
+                    .and_then(|bytes| serde_json::from_slice::<Delivery>(&bytes).map_err(|e| e.to_string()))
+                {
+                    Ok(delivery) => Some(delivery),
+                    Err(e) => {
+                        warn!("Skipping unreadable webhook delivery {}: {}.", file.display(), e);
+                        None
+                    }
+                }
+            })
+            .collect())
+    }
+
+    /// Writes the delivery to a temporary file first so that a crash can't leave a truncated one behind.
+    pub(crate) fn store(&self, delivery: &Delivery) -> io::Result<()> {
+        let file = self.file(delivery);
+        let tmp_file = file.with_extension("tmp");
+
+        fs::write(&tmp_file, serde_json::to_vec(delivery)?)?;
+        fs::rename(&tmp_file, &file)
+    }
+
+    pub(crate) fn remove(&self, delivery: &Delivery) -> io::Result<()> {
+        match fs::remove_file(self.file(delivery)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use std::env;
+
+    #[test]
+    fn store_load_remove() {
+        let path = env::temp_dir().join(format!("bee-webhook-queue-{}", std::process::id()));
+        let queue = Queue::open(&path).unwrap();
+        let first = Delivery::new(
+            "http://127.0.0.1/a".to_string(),
+            Event::MilestoneConfirmed,
+            "{}".to_string(),
+        );
+        let mut second = Delivery::new("http://127.0.0.1/b".to_string(), Event::OutputCreated, "[]".to_string());
+
+        queue.store(&first).unwrap();
+        queue.store(&second).unwrap();
+        second.attempts = 3;
+        queue.store(&second).unwrap();
+
+        let loaded = queue.load().unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].id, first.id);
+        assert_eq!(loaded[1].id, second.id);
+        assert_eq!(loaded[1].attempts, 3);
+        assert_eq!(loaded[1].body, "[]");
+
+        queue.remove(&first).unwrap();
+        queue.remove(&first).unwrap();
+        assert_eq!(queue.load().unwrap().len(), 1);
+
+        fs::remove_dir_all(&path).unwrap();
+    }
+}
@@ -0,0 +1,110 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::webhook::{config::WebhookConfig, delivery, events, queue::Queue};
+
+use bee_common_ext::{event::Bus, node::Node, worker::Worker};
+
+use async_trait::async_trait;
+use futures::{select, stream::FuturesUnordered, FutureExt, StreamExt};
+use log::{error, info, warn};
+use reqwest::Client;
+
+use std::{convert::Infallible, sync::Arc};
+
+pub(crate) struct WebhookWorker;
+
+#[async_trait]
+impl<N: Node> Worker<N> for WebhookWorker {
+    type Config = WebhookConfig;
+    type Error = Infallible;
+
+    async fn start(node: &mut N, config: Self::Config) -> Result<Self, Self::Error> {
+        let queue = match Queue::open(config.queue_path()) {
+            Ok(queue) => Arc::new(queue),
+            Err(e) => {
+                error!(
+                    "Opening webhook queue {} failed, webhooks are disabled: {}.",
+                    config.queue_path().display(),
+                    e
+                );
+                return Ok(Self);
+            }
+        };
+        let client = match Client::builder().timeout(config.timeout()).build() {
+            Ok(client) => client,
+            Err(e) => {
+                error!("Creating the webhook HTTP client failed, webhooks are disabled: {}.", e);
+                return Ok(Self);
+            }
+        };
+        let pending = match queue.load() {
+            Ok(pending) => pending,
+            Err(e) => {
+                error!("Loading undelivered webhooks failed: {}.", e);
+                Vec::new()
+            }
+        };
+        let config = Arc::new(config);
+        let (sender, receiver) = flume::unbounded();
+
+        events::register(&node.resource::<Bus>(), config.clone(), sender);
+
+        node.spawn::<Self, _, _>(|shutdown| async move {
+            info!("Running.");
+
+            if !pending.is_empty() {
+                info!("Resuming {} undelivered webhooks.", pending.len());
+            }
+
+            let mut shutdown = shutdown.fuse();
+            let mut deliveries = pending
+                .into_iter()
+                .map(|pending| delivery::deliver(client.clone(), config.clone(), queue.clone(), pending))
+                .collect::<FuturesUnordered<_>>();
+            let mut receiver = receiver.into_stream().fuse();
+
+            // Deliveries still in flight on shutdown stay queued and are resumed on the next start.
+            loop {
+                select! {
+                    _ = shutdown => break,
+                    new = receiver.next() => match new {
+                        Some(new) => {
+                            let persisted = queue.clone();
+                            // Persisted before the first attempt, so that it survives a restart.
+                            let new = match tokio::task::spawn_blocking(move || {
+                                if let Err(e) = persisted.store(&new) {
+                                    warn!("Persisting webhook delivery {} failed: {}.", new.id, e);
+                                }
+                                new
+                            })
+                            .await
+                            {
+                                Ok(new) => new,
+                                Err(e) => {
+                                    error!("Persisting a webhook delivery failed: {}.", e);
+                                    continue;
+                                }
+                            };
+                            deliveries.push(delivery::deliver(client.clone(), config.clone(), queue.clone(), new));
+                        }
+                        None => break,
+                    },
+                    _ = deliveries.select_next_some() => (),
+                }
+            }
+
+            info!("Stopped.");
+        });
+
+        Ok(Self)
+    }
+}
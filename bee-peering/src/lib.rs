@@ -17,4 +17,4 @@ mod manual;
 
 pub use config::{PeeringConfig, PeeringConfigBuilder};
 pub use manager::PeerManager;
-pub use manual::ManualPeerManager;
+pub use manual::{ManualPeerManager, ManualPeeringConfig, ManualPeeringConfigBuilder, ManualPeers};
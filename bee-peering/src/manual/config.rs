@@ -11,18 +11,32 @@
 
 use serde::Deserialize;
 
-use bee_network::Multiaddr;
+use bee_network::{Multiaddr, PeerId, Protocol};
 
-use std::str::FromStr;
-
-// TODO add acceptAnyConnection
+use std::{collections::HashMap, str::FromStr};
 
 const DEFAULT_LIMIT: u8 = 5;
 
+/// A manual peer, given either by its multiaddress or as a table also holding an alias.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum ManualPeerBuilder {
+    Address(String),
+    Detailed { address: String, alias: Option<String> },
+}
+
+impl ManualPeerBuilder {
+    fn address(&self) -> &str {
+        match self {
+            Self::Address(address) | Self::Detailed { address, .. } => address,
+        }
+    }
+}
+
 #[derive(Default, Deserialize)]
 pub struct ManualPeeringConfigBuilder {
     pub(crate) limit: Option<u8>,
-    pub(crate) peers: Vec<String>,
+    pub(crate) peers: Vec<ManualPeerBuilder>,
 }
 
 impl ManualPeeringConfigBuilder {
@@ -36,19 +50,53 @@ impl ManualPeeringConfigBuilder {
     }
 
     pub fn add_peer(mut self, peer_address_id: &str) {
-        self.peers.push(peer_address_id.to_owned());
+        self.peers.push(ManualPeerBuilder::Address(peer_address_id.to_owned()));
+    }
+
+    /// Checks that every peer address ends with a peer id, e.g. `/ip4/1.2.3.4/tcp/15600/p2p/<id>`, as `finish`
+    /// expects.
+    pub fn validate(&self) -> Result<(), String> {
+        for peer in self.peers.iter() {
+            let valid = match Multiaddr::from_str(peer.address()) {
+                Ok(address) => matches!(
+                    address.iter().last(),
+                    Some(Protocol::P2p(multihash)) if PeerId::from_multihash(multihash.clone()).is_ok()
+                ),
+                Err(_) => false,
+            };
+
+            if !valid {
+                return Err(format!("invalid peer address {}", peer.address()));
+            }
+        }
+
+        Ok(())
     }
 
     pub fn finish(self) -> ManualPeeringConfig {
-        let peers = self
-            .peers
-            .iter()
-            .map(|s| Multiaddr::from_str(s).expect("error parsing Multiaddr"))
-            .collect();
+        let mut peers = Vec::new();
+        let mut aliases = HashMap::new();
+
+        for peer in self.peers {
+            let (address, alias) = match peer {
+                ManualPeerBuilder::Address(address) => (address, None),
+                ManualPeerBuilder::Detailed { address, alias } => (address, alias),
+            };
+            let address = Multiaddr::from_str(&address).expect("error parsing Multiaddr");
+
+            if let (Some(alias), Some(Protocol::P2p(multihash))) = (alias, address.iter().last()) {
+                if let Ok(id) = PeerId::from_multihash(multihash) {
+                    aliases.insert(id, alias);
+                }
+            }
+
+            peers.push(address);
+        }
 
         ManualPeeringConfig {
             limit: self.limit.unwrap_or(DEFAULT_LIMIT),
             peers,
+            aliases,
         }
     }
 }
@@ -57,10 +105,19 @@ impl ManualPeeringConfigBuilder {
 pub struct ManualPeeringConfig {
     pub(crate) limit: u8,
     pub(crate) peers: Vec<Multiaddr>,
+    pub(crate) aliases: HashMap<PeerId, String>,
 }
 
 impl ManualPeeringConfig {
     pub fn build() -> ManualPeeringConfigBuilder {
         ManualPeeringConfigBuilder::new()
     }
+
+    pub fn peers(&self) -> &[Multiaddr] {
+        &self.peers
+    }
+
+    pub fn alias(&self, id: &PeerId) -> Option<&str> {
+        self.aliases.get(id).map(String::as_str)
+    }
 }
@@ -11,22 +11,35 @@
 
 use crate::{manual::config::ManualPeeringConfig, PeerManager};
 
-use bee_network::{Command::ConnectPeer, Multiaddr, Network, PeerId, Protocol};
+use bee_network::{
+    Command::{ConnectPeer, DisconnectPeer},
+    Multiaddr, Network, PeerId, Protocol,
+};
 
 use async_trait::async_trait;
-use log::warn;
+use log::{info, warn};
 
-// Manages a peer list and watches a config file for changes
-// Sends changes (peer added/removed) to the network
+use std::collections::HashMap;
 
 pub struct ManualPeerManager {
     config: ManualPeeringConfig,
     network: Network,
+    updates: Option<flume::Receiver<ManualPeeringConfig>>,
 }
 
 impl ManualPeerManager {
     pub fn new(config: ManualPeeringConfig, network: Network) -> Self {
-        Self { config, network }
+        Self {
+            config,
+            network,
+            updates: None,
+        }
+    }
+
+    /// Keeps the manager running to apply the configs received on `updates`, until all senders are dropped.
+    pub fn with_updates(mut self, updates: flume::Receiver<ManualPeeringConfig>) -> Self {
+        self.updates.replace(updates);
+        self
     }
 
     fn connect_peer(&mut self, address: Multiaddr, id: PeerId) {
@@ -34,21 +47,79 @@ impl ManualPeerManager {
             warn!("Failed to add peer: {}", e);
         }
     }
+
+    fn disconnect_peer(&mut self, id: PeerId) {
+        if let Err(e) = self.network.unbounded_send(DisconnectPeer { id }) {
+            warn!("Failed to remove peer: {}", e);
+        }
+    }
+
+    fn update(&mut self, config: ManualPeeringConfig) {
+        let old = peers(&self.config);
+        let new = peers(&config);
+
+        for (id, address) in old.iter() {
+            if new.get(id) != Some(address) {
+                info!("Removing manual peer {}.", name(&self.config, id));
+                self.disconnect_peer(id.clone());
+            }
+        }
+        for (id, address) in new.iter() {
+            if old.get(id) != Some(address) {
+                info!("Adding manual peer {}.", name(&config, id));
+                self.connect_peer(address.clone(), id.clone());
+            }
+        }
+        // Added peers are already logged with their alias.
+        for id in new.keys().filter(|id| old.get(id) == new.get(id)) {
+            match (self.config.alias(id), config.alias(id)) {
+                (old_alias, Some(alias)) if old_alias != Some(alias) => {
+                    info!("Manual peer {} is now known as {}.", id, alias)
+                }
+                (Some(alias), None) => info!("Manual peer {} is no longer known as {}.", id, alias),
+                _ => (),
+            }
+        }
+
+        self.config = config;
+    }
+}
+
+/// Names a manual peer by its alias, if any, and its id.
+fn name(config: &ManualPeeringConfig, id: &PeerId) -> String {
+    match config.alias(id) {
+        Some(alias) => format!("{} ({})", alias, id),
+        None => id.to_string(),
+    }
+}
+
+/// Splits the manual peers of `config` into their ids and addresses.
+// NOTE: addresses without a peer id should have been caught earlier (e.g. when parsing the config, cli, ...)
+fn peers(config: &ManualPeeringConfig) -> HashMap<PeerId, Multiaddr> {
+    config
+        .peers
+        .iter()
+        .filter_map(|address| {
+            let mut address = address.clone();
+            match address.pop() {
+                Some(Protocol::P2p(multihash)) => PeerId::from_multihash(multihash).ok().map(|id| (id, address)),
+                _ => None,
+            }
+        })
+        .collect()
 }
 
 #[async_trait]
 impl PeerManager for ManualPeerManager {
     async fn run(mut self) {
-        // TODO config file watcher
         // TODO use limit
-        for mut address in self.config.peers.clone() {
-            // NOTE: if unwrapping fails here, it should have been caught earlier (e.g. when parsing the config,
-            // cli, ...)
-            if let Some(Protocol::P2p(multihash)) = address.pop() {
-                let id = PeerId::from_multihash(multihash).expect("Invalid Multiaddr.");
-                self.connect_peer(address, id);
-            } else {
-                unreachable!("Invalid Multiaddr.");
+        for (id, address) in peers(&self.config) {
+            self.connect_peer(address, id);
+        }
+
+        if let Some(updates) = self.updates.take() {
+            while let Ok(config) = updates.recv_async().await {
+                self.update(config);
             }
         }
     }
@@ -14,3 +14,8 @@ mod manual;
 
 pub use config::{ManualPeeringConfig, ManualPeeringConfigBuilder};
 pub use manual::ManualPeerManager;
+
+use std::sync::{Arc, RwLock};
+
+/// The manual peering config currently applied, shared by the components listing peers.
+pub type ManualPeers = Arc<RwLock<ManualPeeringConfig>>;
@@ -9,6 +9,8 @@
 // an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and limitations under the License.
 
+mod miner;
 mod score;
 
+pub use miner::{mine, mine_cancellable};
 pub use score::{compute_pow_score, pow_hash, pow_score};
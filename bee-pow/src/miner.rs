@@ -0,0 +1,50 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::score::{nonce_hash, pow_digest, pow_score};
+
+use bee_crypto::ternary::{sponge::CurlP81, HASH_LENGTH};
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Searches a nonce that, once written in the last 8 bytes of `bytes`, makes them reach `target_score`.
+/// Returns `None` if `bytes` are too short to hold a nonce or if the target score can't be reached.
+pub fn mine(bytes: &[u8], target_score: f64) -> Option<u64> {
+    mine_cancellable(bytes, target_score, &AtomicBool::new(false))
+}
+
+/// Same as [`mine`], but gives up and returns `None` as soon as `cancelled` is set.
+pub fn mine_cancellable(bytes: &[u8], target_score: f64, cancelled: &AtomicBool) -> Option<u64> {
+    let length = bytes.len().checked_sub(std::mem::size_of::<u64>())?;
+    // The score is `3^zeros / len` so the target translates into a number of trailing zero trits.
+    let target_zeros = ((target_score * bytes.len() as f64).ln() / 3f64.ln()).ceil().max(0.0) as usize;
+
+    if target_zeros > HASH_LENGTH {
+        return None;
+    }
+
+    let mut curl = CurlP81::new();
+    let pow_digest = pow_digest(&bytes[..length]);
+
+    for nonce in 0..u64::MAX {
+        if cancelled.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        let hash = nonce_hash(&mut curl, &pow_digest, &nonce.to_le_bytes());
+
+        if pow_score(&hash, bytes.len()) >= target_score {
+            return Some(nonce);
+        }
+    }
+
+    None
+}
@@ -26,18 +26,31 @@ pub fn compute_pow_score(bytes: &[u8]) -> f64 {
 }
 
 pub fn pow_hash(bytes: &[u8]) -> TritBuf<T1B1Buf> {
-    let mut blake = VarBlake2b::new(32).unwrap();
-    let mut curl = CurlP81::new();
     let length = bytes.len() - std::mem::size_of::<u64>();
-    let mut pow_input = TritBuf::<T1B1Buf>::with_capacity(HASH_LENGTH);
 
-    blake.update(&bytes[..length]);
-    blake.finalize_variable_reset(|pow_digest| b1t6::encode(&pow_digest).iter().for_each(|t| pow_input.push(t)));
-    b1t6::encode(&bytes[length..]).iter().for_each(|t| pow_input.push(t));
+    nonce_hash(&mut CurlP81::new(), &pow_digest(&bytes[..length]), &bytes[length..])
+}
+
+/// Digest of the bytes preceding the nonce, shared by the PoW input of every nonce.
+pub(crate) fn pow_digest(bytes: &[u8]) -> TritBuf<T1B1Buf> {
+    let mut blake = VarBlake2b::new(32).unwrap();
+    let mut pow_digest = TritBuf::<T1B1Buf>::with_capacity(HASH_LENGTH);
+
+    blake.update(bytes);
+    blake.finalize_variable(|digest| b1t6::encode(&digest).iter().for_each(|t| pow_digest.push(t)));
+
+    pow_digest
+}
+
+pub(crate) fn nonce_hash(curl: &mut CurlP81, pow_digest: &TritBuf<T1B1Buf>, nonce: &[u8]) -> TritBuf<T1B1Buf> {
+    let mut pow_input = pow_digest.clone();
+
+    b1t6::encode(nonce).iter().for_each(|t| pow_input.push(t));
     pow_input.push(Btrit::Zero);
     pow_input.push(Btrit::Zero);
     pow_input.push(Btrit::Zero);
 
+    curl.reset();
     curl.digest(&pow_input).unwrap()
 }
 
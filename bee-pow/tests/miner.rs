@@ -0,0 +1,47 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use bee_pow::{compute_pow_score, mine, mine_cancellable};
+
+use std::sync::atomic::AtomicBool;
+
+#[test]
+fn mine_reaches_target_score() {
+    let mut message = b"Hello, World!".to_vec();
+    message.extend_from_slice(&[0u8; 8]);
+
+    let nonce = mine(&message, 100.0).unwrap();
+    let length = message.len();
+    message[length - 8..].copy_from_slice(&nonce.to_le_bytes());
+
+    assert!(compute_pow_score(&message) >= 100.0);
+}
+
+#[test]
+fn mine_null_target_score() {
+    let message: [u8; 8] = [0, 0, 0, 0, 0, 0, 0, 0];
+
+    assert_eq!(mine(&message, 0.0), Some(0));
+}
+
+#[test]
+fn mine_short_input() {
+    assert_eq!(mine(&[0u8; 7], 0.0), None);
+    assert_eq!(mine(&[], 100.0), None);
+}
+
+#[test]
+fn mine_cancelled() {
+    let mut message = b"Hello, World!".to_vec();
+    message.extend_from_slice(&[0u8; 8]);
+
+    assert_eq!(mine_cancellable(&message, 100.0, &AtomicBool::new(true)), None);
+}
@@ -9,10 +9,12 @@
 // an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and limitations under the License.
 
-use crate::milestone::{key_range::KeyRange, MilestoneIndex};
+use crate::milestone::{key_manager::KeyManager, key_range::KeyRange, MilestoneIndex};
 
 use serde::Deserialize;
 
+use std::collections::HashSet;
+
 const DEFAULT_MINIMUM_POW_SCORE: f64 = 4000.0;
 const DEFAULT_COO_PUBLIC_KEY_COUNT: usize = 2;
 const DEFAULT_COO_PUBLIC_KEY_RANGES: [(&str, MilestoneIndex, MilestoneIndex); 2] = [
@@ -122,6 +124,18 @@ pub struct ProtocolCoordinatorConfig {
     pub(crate) public_key_ranges: Vec<KeyRange>,
 }
 
+impl ProtocolCoordinatorConfig {
+    /// Minimum number of signatures a milestone needs.
+    pub fn public_key_count(&self) -> usize {
+        self.public_key_count
+    }
+
+    /// Public keys, hex encoded, a milestone at `index` may be signed with.
+    pub fn public_keys(&self, index: MilestoneIndex) -> HashSet<String> {
+        KeyManager::new(self.public_key_count, self.public_key_ranges.clone().into_boxed_slice()).get_public_keys(index)
+    }
+}
+
 #[derive(Clone)]
 pub struct ProtocolWorkersConfig {
     pub(crate) message_worker_cache: usize,
@@ -142,6 +156,10 @@ impl ProtocolConfig {
         ProtocolConfigBuilder::new()
     }
 
+    pub fn minimum_pow_score(&self) -> f64 {
+        self.minimum_pow_score
+    }
+
     pub fn coordinator(&self) -> &ProtocolCoordinatorConfig {
         &self.coordinator
     }
@@ -12,11 +12,14 @@
 use crate::Milestone;
 
 use bee_message::MessageId;
+use bee_tangle::MessageRef;
 
 pub struct LatestMilestoneChanged(pub Milestone);
 
 pub struct LatestSolidMilestoneChanged(pub Milestone);
 
+pub struct MessageProcessed(pub MessageId, pub MessageRef);
+
 pub struct MessageSolidified(pub MessageId);
 
 pub struct TpsMetricsUpdated {
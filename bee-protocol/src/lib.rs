@@ -14,6 +14,7 @@
 pub mod config;
 pub mod event;
 pub mod milestone;
+pub mod storage;
 pub mod tangle;
 
 mod packet;
@@ -22,5 +23,9 @@ mod protocol;
 mod worker;
 
 pub use milestone::{Milestone, MilestoneIndex};
+pub use peer::{Peer, PeerMetrics};
 pub use protocol::{Protocol, ProtocolMetrics};
-pub use worker::{StorageWorker, TangleWorker};
+pub use worker::{
+    MessageSubmitterError, MessageSubmitterWorker, MessageSubmitterWorkerEvent, MilestoneValidatorWorker,
+    StorageWorker, TangleWorker, WorkerQueue,
+};
@@ -45,12 +45,19 @@ impl PeerManager {
     }
 
     pub(crate) fn connected_peers(&self) -> u8 {
-        // TODO impl
-        0
+        self.peers.len().min(u8::MAX as usize) as u8
     }
 
     pub(crate) fn synced_peers(&self) -> u8 {
-        // TODO impl
-        0
+        // A peer is considered synced when its last heartbeat reported a solid milestone index matching its latest
+        // milestone index.
+        self.peers
+            .iter()
+            .filter(|peer| {
+                *peer.latest_milestone_index() != 0
+                    && peer.latest_solid_milestone_index() >= peer.latest_milestone_index()
+            })
+            .count()
+            .min(u8::MAX as usize) as u8
     }
 }
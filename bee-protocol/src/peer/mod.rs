@@ -14,5 +14,5 @@ mod metrics;
 mod peer;
 
 pub(crate) use manager::PeerManager;
-pub(crate) use metrics::PeerMetrics;
-pub(crate) use peer::Peer;
+pub use metrics::PeerMetrics;
+pub use peer::Peer;
@@ -40,11 +40,23 @@ impl Peer {
         }
     }
 
+    pub fn id(&self) -> &PeerId {
+        &self.id
+    }
+
+    pub fn address(&self) -> &Multiaddr {
+        &self.address
+    }
+
+    pub fn metrics(&self) -> &PeerMetrics {
+        &self.metrics
+    }
+
     pub(crate) fn set_latest_solid_milestone_index(&self, index: MilestoneIndex) {
         self.latest_solid_milestone_index.store(*index, Ordering::Relaxed);
     }
 
-    pub(crate) fn latest_solid_milestone_index(&self) -> MilestoneIndex {
+    pub fn latest_solid_milestone_index(&self) -> MilestoneIndex {
         self.latest_solid_milestone_index.load(Ordering::Relaxed).into()
     }
 
@@ -52,7 +64,7 @@ impl Peer {
         self.pruned_index.store(*index, Ordering::Relaxed);
     }
 
-    pub(crate) fn pruned_index(&self) -> MilestoneIndex {
+    pub fn pruned_index(&self) -> MilestoneIndex {
         self.pruned_index.load(Ordering::Relaxed).into()
     }
 
@@ -60,7 +72,7 @@ impl Peer {
         self.latest_milestone_index.store(*index, Ordering::Relaxed);
     }
 
-    pub(crate) fn latest_milestone_index(&self) -> MilestoneIndex {
+    pub fn latest_milestone_index(&self) -> MilestoneIndex {
         self.latest_milestone_index.load(Ordering::Relaxed).into()
     }
 
@@ -68,8 +80,7 @@ impl Peer {
         self.connected_peers.store(connected_peers, Ordering::Relaxed);
     }
 
-    #[allow(dead_code)]
-    pub(crate) fn connected_peers(&self) -> u8 {
+    pub fn connected_peers(&self) -> u8 {
         self.connected_peers.load(Ordering::Relaxed)
     }
 
@@ -77,8 +88,7 @@ impl Peer {
         self.synced_peers.store(synced_peers, Ordering::Relaxed);
     }
 
-    #[allow(dead_code)]
-    pub(crate) fn synced_peers(&self) -> u8 {
+    pub fn synced_peers(&self) -> u8 {
         self.synced_peers.load(Ordering::Relaxed)
     }
 
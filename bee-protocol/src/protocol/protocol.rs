@@ -15,29 +15,30 @@ use crate::{
     milestone::MilestoneIndex,
     peer::{Peer, PeerManager},
     protocol::ProtocolMetrics,
+    storage::Backend,
     tangle::MsTangle,
     worker::{
         BroadcasterWorker, HasherWorker, HeartbeaterWorker, KickstartWorker, MessageRequesterWorker,
-        MessageResponderWorker, MessageValidatorWorker, MilestoneConeUpdaterWorker, MilestoneRequesterWorker,
-        MilestoneResponderWorker, MilestoneSolidifierWorker, MilestoneSolidifierWorkerEvent, MilestoneValidatorWorker,
-        MpsWorker, PeerWorker, ProcessorWorker, PropagatorWorker, RequestedMilestones, StatusWorker, StorageWorker,
-        TangleWorker, TipPoolCleanerWorker,
+        MessageResponderWorker, MessageSubmitterWorker, MessageValidatorWorker, MilestoneConeUpdaterWorker,
+        MilestoneRequesterWorker, MilestoneResponderWorker, MilestoneSolidifierWorker, MilestoneSolidifierWorkerEvent,
+        MilestoneValidatorWorker, MpsWorker, PeerWorker, ProcessorWorker, PropagatorWorker, RequestedMilestones,
+        StatusWorker, StorageWorker, TangleWorker, TipPoolCleanerWorker, WorkerQueue,
     },
 };
 
 use bee_common_ext::{
     event::Bus,
     node::{Node, NodeBuilder},
+    worker::Worker,
 };
 use bee_network::{Multiaddr, Network, PeerId};
 use bee_snapshot::Snapshot;
-use bee_storage::storage::Backend;
 
 use futures::channel::oneshot;
 use log::{debug, error, info};
 use tokio::spawn;
 
-use std::sync::Arc;
+use std::{any::TypeId, sync::Arc};
 
 static PROTOCOL: spin::RwLock<Option<&'static Protocol>> = spin::RwLock::new(None);
 
@@ -55,7 +56,10 @@ impl Protocol {
         snapshot: Snapshot,
         network_id: u64,
         node_builder: N::Builder,
-    ) -> N::Builder {
+    ) -> N::Builder
+    where
+        N::Backend: Backend,
+    {
         let protocol = Protocol {
             network: network.clone(),
             metrics: ProtocolMetrics::new(),
@@ -71,6 +75,7 @@ impl Protocol {
             .with_worker_cfg::<TangleWorker>(snapshot)
             .with_worker_cfg::<HasherWorker>(config.workers.message_worker_cache)
             .with_worker_cfg::<ProcessorWorker>((config.clone(), network_id))
+            .with_worker_cfg::<MessageSubmitterWorker>((config.clone(), network_id))
             .with_worker::<MessageResponderWorker>()
             .with_worker::<MilestoneResponderWorker>()
             .with_worker::<MessageRequesterWorker>()
@@ -159,6 +164,90 @@ impl Protocol {
         *PROTOCOL.read().as_ref().expect("Uninitialized protocol.")
     }
 
+    pub fn metrics() -> &'static ProtocolMetrics {
+        &Protocol::get().metrics
+    }
+
+    pub fn connected_peers() -> u8 {
+        Protocol::get().peer_manager.connected_peers()
+    }
+
+    pub fn synced_peers() -> u8 {
+        Protocol::get().peer_manager.synced_peers()
+    }
+
+    /// Returns the currently connected peers.
+    pub fn peers() -> Vec<Arc<Peer>> {
+        Protocol::get()
+            .peer_manager
+            .peers
+            .iter()
+            .map(|peer| peer.value().clone())
+            .collect()
+    }
+
+    /// Returns a connected peer by its id.
+    pub fn peer(id: &PeerId) -> Option<Arc<Peer>> {
+        Protocol::get()
+            .peer_manager
+            .peers
+            .get(id)
+            .map(|peer| peer.value().clone())
+    }
+
+    /// Returns the ids of the workers probed by `worker_queues`, for dependents to be started after them.
+    pub fn queue_workers() -> Vec<TypeId> {
+        vec![
+            TypeId::of::<HasherWorker>(),
+            TypeId::of::<ProcessorWorker>(),
+            TypeId::of::<MessageSubmitterWorker>(),
+            TypeId::of::<MessageValidatorWorker>(),
+            TypeId::of::<MilestoneValidatorWorker>(),
+            TypeId::of::<MilestoneSolidifierWorker>(),
+            TypeId::of::<MilestoneConeUpdaterWorker>(),
+            TypeId::of::<PropagatorWorker>(),
+            TypeId::of::<BroadcasterWorker>(),
+            TypeId::of::<MessageRequesterWorker>(),
+            TypeId::of::<MilestoneRequesterWorker>(),
+            TypeId::of::<MessageResponderWorker>(),
+            TypeId::of::<MilestoneResponderWorker>(),
+        ]
+    }
+
+    /// Returns probes of the queues of the protocol workers that have been started on `node`.
+    pub fn worker_queues<N: Node>(node: &N) -> Vec<WorkerQueue>
+    where
+        N::Backend: Backend,
+    {
+        fn queue<N: Node, W: Worker<N> + Send + Sync, E: Send + 'static>(
+            node: &N,
+            name: &'static str,
+            tx: impl Fn(&W) -> &flume::Sender<E>,
+        ) -> Option<WorkerQueue> {
+            node.worker::<W>()
+                .map(|worker| WorkerQueue::new(name, tx(worker).clone()))
+        }
+
+        vec![
+            queue(node, "hasher", |w: &HasherWorker| &w.tx),
+            queue(node, "processor", |w: &ProcessorWorker| &w.tx),
+            queue(node, "message_submitter", |w: &MessageSubmitterWorker| &w.tx),
+            queue(node, "message_validator", |w: &MessageValidatorWorker| &w.tx),
+            queue(node, "milestone_validator", |w: &MilestoneValidatorWorker| &w.tx),
+            queue(node, "milestone_solidifier", |w: &MilestoneSolidifierWorker| &w.tx),
+            queue(node, "milestone_cone_updater", |w: &MilestoneConeUpdaterWorker| &w.tx),
+            queue(node, "propagator", |w: &PropagatorWorker| &w.tx),
+            queue(node, "broadcaster", |w: &BroadcasterWorker| &w.tx),
+            queue(node, "message_requester", |w: &MessageRequesterWorker| &w.tx),
+            queue(node, "milestone_requester", |w: &MilestoneRequesterWorker| &w.tx),
+            queue(node, "message_responder", |w: &MessageResponderWorker| &w.tx),
+            queue(node, "milestone_responder", |w: &MilestoneResponderWorker| &w.tx),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
     pub async fn register<N: Node>(
         node: &N,
         _config: &ProtocolConfig,
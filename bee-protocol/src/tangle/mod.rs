@@ -192,7 +192,7 @@ impl<B: Backend> MsTangle<B> {
 
     // TODO reduce to one atomic value ?
     pub fn is_synced_threshold(&self, threshold: u32) -> bool {
-        *self.get_latest_solid_milestone_index() >= (*self.get_latest_milestone_index() - threshold)
+        *self.get_latest_solid_milestone_index() >= self.get_latest_milestone_index().saturating_sub(threshold)
     }
 
     pub fn get_solid_entry_point_index(&self, hash: &MessageId) -> Option<MilestoneIndex> {
@@ -212,6 +212,14 @@ impl<B: Backend> MsTangle<B> {
         self.solid_entry_points.clear();
     }
 
+    /// Returns the solid entry points along with the milestone index they were added at.
+    pub fn get_solid_entry_points(&self) -> Vec<(MessageId, MilestoneIndex)> {
+        self.solid_entry_points
+            .iter()
+            .map(|entry| (*entry.key(), *entry.value()))
+            .collect()
+    }
+
     /// Returns whether the message associated with `hash` is a solid entry point.
     pub fn is_solid_entry_point(&self, hash: &MessageId) -> bool {
         self.solid_entry_points.contains_key(hash)
@@ -262,12 +270,20 @@ impl<B: Backend> MsTangle<B> {
     }
 
     pub async fn get_messages_to_approve(&self) -> Option<(MessageId, MessageId)> {
-        self.tip_pool.lock().await.two_non_lazy_tips()
+        self.tip_pool.lock().await.choose_non_lazy_tips()
     }
 
     pub async fn reduce_tips(&self) {
         self.tip_pool.lock().await.reduce_tips();
     }
+
+    pub async fn num_tips(&self) -> usize {
+        self.tip_pool.lock().await.num_tips()
+    }
+
+    pub async fn num_non_lazy_tips(&self) -> usize {
+        self.tip_pool.lock().await.num_non_lazy_tips()
+    }
 }
 
 // #[cfg(test)]
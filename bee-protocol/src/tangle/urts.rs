@@ -180,7 +180,7 @@ impl UrtsTipPool {
         Score::NonLazy
     }
 
-    pub fn two_non_lazy_tips(&self) -> Option<(MessageId, MessageId)> {
+    pub fn choose_non_lazy_tips(&self) -> Option<(MessageId, MessageId)> {
         let non_lazy_tips = &self.non_lazy_tips;
         if non_lazy_tips.is_empty() {
             None
@@ -197,6 +197,14 @@ impl UrtsTipPool {
         }
     }
 
+    pub(crate) fn num_tips(&self) -> usize {
+        self.tips.len()
+    }
+
+    pub(crate) fn num_non_lazy_tips(&self) -> usize {
+        self.non_lazy_tips.len()
+    }
+
     pub(crate) fn reduce_tips(&mut self) {
         let mut to_remove = Vec::new();
         for (tip, metadata) in &self.tips {
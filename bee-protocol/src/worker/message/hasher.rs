@@ -45,7 +45,7 @@ use std::{any::TypeId, convert::Infallible, pin::Pin};
 const BATCH_SIZE_THRESHOLD: usize = 3;
 
 pub(crate) struct HasherWorkerEvent {
-    pub(crate) from: PeerId,
+    pub(crate) from: Option<PeerId>,
     pub(crate) message_packet: MessagePacket,
 }
 
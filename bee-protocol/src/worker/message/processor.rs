@@ -11,6 +11,7 @@
 
 use crate::{
     config::ProtocolConfig,
+    event::MessageProcessed,
     packet::Message as MessagePacket,
     protocol::Protocol,
     tangle::{MessageMetadata, MsTangle},
@@ -21,7 +22,7 @@ use crate::{
 };
 
 use bee_common::{packable::Packable, shutdown_stream::ShutdownStream};
-use bee_common_ext::{node::Node, worker::Worker};
+use bee_common_ext::{event::Bus, node::Node, worker::Worker};
 use bee_message::{payload::Payload, Message, MessageId, MESSAGE_ID_LENGTH};
 use bee_network::PeerId;
 
@@ -37,7 +38,7 @@ use std::{any::TypeId, convert::Infallible};
 
 pub(crate) struct ProcessorWorkerEvent {
     pub(crate) pow_score: f64,
-    pub(crate) from: PeerId,
+    pub(crate) from: Option<PeerId>,
     pub(crate) message_packet: MessagePacket,
 }
 
@@ -70,6 +71,7 @@ impl<N: Node> Worker<N> for ProcessorWorker {
 
         let tangle = node.resource::<MsTangle<N::Backend>>();
         let requested_messages = node.resource::<RequestedMessages>();
+        let bus = node.resource::<Bus>();
 
         node.spawn::<Self, _, _>(|shutdown| async move {
             info!("Running.");
@@ -137,6 +139,8 @@ impl<N: Node> Worker<N> for ProcessorWorker {
 
                     Protocol::get().metrics.new_messages_inc();
 
+                    bus.dispatch(MessageProcessed(message_id, message.clone()));
+
                     match requested_messages.remove(&message_id) {
                         Some((_, (index, _))) => {
                             // Message was requested.
@@ -165,7 +169,7 @@ impl<N: Node> Worker<N> for ProcessorWorker {
                         None => {
                             // Message was not requested.
                             if let Err(e) = broadcaster.send(BroadcasterWorkerEvent {
-                                source: Some(from),
+                                source: from,
                                 message: message_packet,
                             }) {
                                 warn!("Broadcasting message failed: {}.", e);
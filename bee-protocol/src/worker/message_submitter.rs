@@ -0,0 +1,135 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::{
+    config::ProtocolConfig,
+    packet::{Message as MessagePacket, Packet},
+    worker::{HasherWorker, HasherWorkerEvent},
+};
+
+use bee_common::{packable::Packable, shutdown_stream::ShutdownStream};
+use bee_common_ext::{node::Node, worker::Worker};
+use bee_message::{Message, MessageId};
+use bee_pow::compute_pow_score;
+
+use async_trait::async_trait;
+use futures::{channel::oneshot, stream::StreamExt};
+use log::{info, warn};
+
+use std::{any::TypeId, convert::Infallible, error, fmt};
+
+#[derive(Debug)]
+pub enum MessageSubmitterError {
+    InvalidNetworkId(u64, u64),
+    InvalidLength(usize),
+    InsufficientPowScore(f64, f64),
+    ChannelClosed,
+}
+
+impl fmt::Display for MessageSubmitterError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MessageSubmitterError::InvalidNetworkId(received, expected) => {
+                write!(f, "Invalid network id {}, expected {}.", received, expected)
+            }
+            MessageSubmitterError::InvalidLength(length) => write!(f, "Invalid message length {}.", length),
+            MessageSubmitterError::InsufficientPowScore(received, expected) => {
+                write!(
+                    f,
+                    "Insufficient pow score {}, expected at least {}.",
+                    received, expected
+                )
+            }
+            MessageSubmitterError::ChannelClosed => write!(f, "The message processing pipeline is closed."),
+        }
+    }
+}
+
+impl error::Error for MessageSubmitterError {}
+
+/// Submits a locally issued message to the processing pipeline, as if it had been received from a peer.
+pub struct MessageSubmitterWorkerEvent {
+    pub message: Message,
+    pub notifier: oneshot::Sender<Result<MessageId, MessageSubmitterError>>,
+}
+
+pub struct MessageSubmitterWorker {
+    pub tx: flume::Sender<MessageSubmitterWorkerEvent>,
+}
+
+fn submit(
+    message: &Message,
+    config: &ProtocolConfig,
+    network_id: u64,
+    hasher: &flume::Sender<HasherWorkerEvent>,
+) -> Result<MessageId, MessageSubmitterError> {
+    if message.network_id() != network_id {
+        return Err(MessageSubmitterError::InvalidNetworkId(
+            message.network_id(),
+            network_id,
+        ));
+    }
+
+    let bytes = message.pack_new();
+
+    if !MessagePacket::size_range().contains(&bytes.len()) {
+        return Err(MessageSubmitterError::InvalidLength(bytes.len()));
+    }
+
+    let pow_score = compute_pow_score(&bytes);
+
+    if pow_score < config.minimum_pow_score {
+        return Err(MessageSubmitterError::InsufficientPowScore(
+            pow_score,
+            config.minimum_pow_score,
+        ));
+    }
+
+    hasher
+        .send(HasherWorkerEvent {
+            from: None,
+            message_packet: MessagePacket::new(&bytes),
+        })
+        .map_err(|_| MessageSubmitterError::ChannelClosed)?;
+
+    Ok(message.id())
+}
+
+#[async_trait]
+impl<N: Node> Worker<N> for MessageSubmitterWorker {
+    type Config = (ProtocolConfig, u64);
+    type Error = Infallible;
+
+    fn dependencies() -> &'static [TypeId] {
+        vec![TypeId::of::<HasherWorker>()].leak()
+    }
+
+    async fn start(node: &mut N, config: Self::Config) -> Result<Self, Self::Error> {
+        let (tx, rx) = flume::unbounded();
+        let hasher = node.worker::<HasherWorker>().unwrap().tx.clone();
+
+        node.spawn::<Self, _, _>(|shutdown| async move {
+            info!("Running.");
+
+            let mut receiver = ShutdownStream::new(shutdown, rx.into_stream());
+
+            while let Some(MessageSubmitterWorkerEvent { message, notifier }) = receiver.next().await {
+                if notifier.send(submit(&message, &config.0, config.1, &hasher)).is_err() {
+                    warn!("Notifying the submission result of a message failed.");
+                }
+            }
+
+            info!("Stopped.");
+        });
+
+        Ok(Self { tx })
+    }
+}
@@ -39,20 +39,20 @@ impl<N: Node> Worker<N> for MessageValidatorWorker {
     async fn start(node: &mut N, _config: Self::Config) -> Result<Self, Self::Error> {
         let (tx, rx) = flume::unbounded();
 
-        let _tangle = node.resource::<MsTangle<N::Backend>>();
+        let tangle = node.resource::<MsTangle<N::Backend>>();
 
         node.spawn::<Self, _, _>(|shutdown| async move {
             info!("Running.");
 
             let mut receiver = ShutdownStream::new(shutdown, rx.into_stream());
 
-            while let Some(MessageValidatorWorkerEvent(_hash)) = receiver.next().await {
-                // if let Ok(bundle) = builder.validate() {
-                //     tangle.update_metadata(&hash, |metadata| {
-                //         metadata.flags_mut().set_valid(true);
-                //     });
-                //     tangle.insert_tip(hash, *bundle.parent1(), *bundle.parent2()).await;
-                // }
+            while let Some(MessageValidatorWorkerEvent(message_id)) = receiver.next().await {
+                // Messages are syntactically validated when unpacked, a solid message is then a valid tip candidate.
+                if let Some(message) = tangle.get(&message_id).await {
+                    tangle
+                        .insert_tip(message_id, *message.parent1(), *message.parent2())
+                        .await;
+                }
             }
 
             info!("Stopped.");
@@ -13,6 +13,7 @@ use crate::{
     config::ProtocolConfig,
     event::{LatestMilestoneChanged, LatestSolidMilestoneChanged},
     milestone::{key_manager::KeyManager, Milestone, MilestoneIndex},
+    storage::Backend,
     tangle::MsTangle,
     worker::{
         MilestoneConeUpdaterWorker, MilestoneConeUpdaterWorkerEvent, MilestoneRequesterWorker,
@@ -23,6 +24,7 @@ use crate::{
 use bee_common::{packable::Packable, shutdown_stream::ShutdownStream};
 use bee_common_ext::{event::Bus, node::Node, worker::Worker};
 use bee_message::{payload::Payload, MessageId};
+use bee_storage::access::Insert;
 
 use async_trait::async_trait;
 use futures::stream::StreamExt;
@@ -46,7 +48,7 @@ pub(crate) enum Error {
 
 pub(crate) struct MilestoneValidatorWorkerEvent(pub(crate) MessageId);
 
-pub(crate) struct MilestoneValidatorWorker {
+pub struct MilestoneValidatorWorker {
     pub(crate) tx: flume::Sender<MilestoneValidatorWorkerEvent>,
 }
 
@@ -122,6 +124,7 @@ where
 impl<N> Worker<N> for MilestoneValidatorWorker
 where
     N: Node,
+    N::Backend: Backend,
 {
     type Config = ProtocolConfig;
     type Error = Infallible;
@@ -142,6 +145,7 @@ where
         let milestone_cone_updater = node.worker::<MilestoneConeUpdaterWorker>().unwrap().tx.clone();
 
         let tangle = node.resource::<MsTangle<N::Backend>>();
+        let storage = node.storage();
         let requested_milestones = node.resource::<RequestedMilestones>();
         let key_manager = KeyManager::new(
             config.coordinator.public_key_count,
@@ -164,6 +168,17 @@ where
                         Ok(milestone) => {
                             tangle.add_milestone(milestone.index, milestone.message_id);
 
+                            // The tangle only keeps the milestones in memory, persist them to survive restarts.
+                            if let Err(e) = Insert::<MilestoneIndex, MessageId>::insert(
+                                &*storage,
+                                &milestone.index,
+                                &milestone.message_id,
+                            )
+                            .await
+                            {
+                                error!("Persisting milestone {} failed: {:?}.", *milestone.index, e);
+                            }
+
                             // This is possibly not sufficient as there is no guarantee a milestone has been
                             // solidified before being validated, we then also need
                             // to check when a milestone gets solidified if it's
@@ -14,12 +14,14 @@
 mod broadcaster;
 mod heartbeater;
 mod message;
+mod message_submitter;
 mod message_validator;
 mod milestone_cone_updater;
 mod milestone_validator;
 mod mps;
 mod peer;
 mod propagator;
+mod queue;
 mod requester;
 mod responder;
 mod solidifier;
@@ -31,12 +33,15 @@ mod tip_pool_cleaner;
 pub(crate) use broadcaster::{BroadcasterWorker, BroadcasterWorkerEvent};
 pub(crate) use heartbeater::HeartbeaterWorker;
 pub(crate) use message::{HasherWorker, HasherWorkerEvent, ProcessorWorker};
+pub use message_submitter::{MessageSubmitterError, MessageSubmitterWorker, MessageSubmitterWorkerEvent};
 pub(crate) use message_validator::{MessageValidatorWorker, MessageValidatorWorkerEvent};
 pub(crate) use milestone_cone_updater::{MilestoneConeUpdaterWorker, MilestoneConeUpdaterWorkerEvent};
-pub(crate) use milestone_validator::{MilestoneValidatorWorker, MilestoneValidatorWorkerEvent};
+pub use milestone_validator::MilestoneValidatorWorker;
+pub(crate) use milestone_validator::MilestoneValidatorWorkerEvent;
 pub(crate) use mps::MpsWorker;
 pub(crate) use peer::PeerWorker;
 pub(crate) use propagator::{PropagatorWorker, PropagatorWorkerEvent};
+pub use queue::WorkerQueue;
 pub(crate) use requester::{
     MessageRequesterWorker, MessageRequesterWorkerEvent, MilestoneRequesterWorker, MilestoneRequesterWorkerEvent,
     RequestedMessages, RequestedMilestones,
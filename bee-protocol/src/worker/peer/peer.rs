@@ -135,7 +135,7 @@ impl PeerWorker {
                     Ok(message) => {
                         self.hasher
                             .send(HasherWorkerEvent {
-                                from: self.peer.id.clone(),
+                                from: Some(self.peer.id.clone()),
                                 message_packet: message,
                             })
                             .map_err(|_| PeerWorkerError::FailedSend)?;
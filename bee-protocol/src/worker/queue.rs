@@ -0,0 +1,33 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+/// Probe of the number of events waiting in the queue of a worker.
+pub struct WorkerQueue {
+    name: &'static str,
+    depth: Box<dyn Fn() -> usize + Send + Sync>,
+}
+
+impl WorkerQueue {
+    pub(crate) fn new<E: Send + 'static>(name: &'static str, tx: flume::Sender<E>) -> Self {
+        Self {
+            name,
+            depth: Box::new(move || tx.len()),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn depth(&self) -> usize {
+        (self.depth)()
+    }
+}
@@ -25,6 +25,16 @@ pub struct SnapshotHeader {
 }
 
 impl SnapshotHeader {
+    pub fn new(kind: Kind, timestamp: u64, network_id: u64, sep_index: u32, ledger_index: u32) -> Self {
+        Self {
+            kind,
+            timestamp,
+            network_id,
+            sep_index,
+            ledger_index,
+        }
+    }
+
     pub fn kind(&self) -> Kind {
         self.kind
     }
@@ -12,7 +12,6 @@
 mod download;
 
 pub(crate) mod constants;
-pub(crate) mod kind;
 pub(crate) mod pruning;
 // pub(crate) mod worker;
 
@@ -20,6 +19,7 @@ pub mod config;
 pub mod error;
 pub mod event;
 pub mod header;
+pub mod kind;
 pub mod milestone_diff;
 pub mod output;
 pub mod snapshot;
@@ -29,6 +29,7 @@ pub(crate) use download::download_snapshot;
 
 pub use error::Error;
 pub use header::SnapshotHeader;
+pub use kind::Kind;
 pub use snapshot::Snapshot;
 
 use bee_common_ext::node::Node;
@@ -17,12 +17,22 @@ use bee_message::{
     MessageId,
 };
 
-pub(crate) struct Output {
+pub struct Output {
     message_id: MessageId,
     output_id: UTXOInput,
     output: SignatureLockedSingleOutput,
 }
 
+impl Output {
+    pub fn new(message_id: MessageId, output_id: UTXOInput, output: SignatureLockedSingleOutput) -> Self {
+        Self {
+            message_id,
+            output_id,
+            output,
+        }
+    }
+}
+
 impl Packable for Output {
     type Error = Error;
 
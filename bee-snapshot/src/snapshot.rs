@@ -30,6 +30,20 @@ pub struct Snapshot {
 }
 
 impl Snapshot {
+    pub fn new(
+        header: SnapshotHeader,
+        solid_entry_points: HashSet<MessageId>,
+        outputs: Vec<Output>,
+        milestone_diffs: Vec<MilestoneDiff>,
+    ) -> Self {
+        Self {
+            header,
+            solid_entry_points,
+            outputs,
+            milestone_diffs,
+        }
+    }
+
     pub fn header(&self) -> &SnapshotHeader {
         &self.header
     }
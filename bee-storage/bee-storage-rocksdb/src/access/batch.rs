@@ -12,7 +12,7 @@
 use crate::{error::Error, storage::*};
 
 use bee_common::packable::Packable;
-use bee_ledger::{output::Output, spent::Spent, unspent::Unspent};
+use bee_ledger::{diff::Diff, output::Output, spent::Spent, unspent::Unspent};
 use bee_message::{
     payload::{
         indexation::HashedIndex,
@@ -20,7 +20,7 @@ use bee_message::{
     },
     Message, MessageId,
 };
-use bee_protocol::tangle::MessageMetadata;
+use bee_protocol::{tangle::MessageMetadata, MilestoneIndex};
 use bee_storage::access::{Batch, BatchBuilder};
 
 use rocksdb::{WriteBatch, WriteOptions};
@@ -346,3 +346,38 @@ impl Batch<(Ed25519Address, OutputId), ()> for Storage {
         Ok(())
     }
 }
+
+impl Batch<MilestoneIndex, Diff> for Storage {
+    fn batch_insert(&self, batch: &mut Self::Batch, index: &MilestoneIndex, diff: &Diff) -> Result<(), Self::Error> {
+        let cf = self
+            .inner
+            .cf_handle(CF_MILESTONE_INDEX_TO_DIFF)
+            .ok_or(Error::UnknownCf(CF_MILESTONE_INDEX_TO_DIFF))?;
+
+        batch.key_buf.clear();
+        // Packing to bytes can't fail.
+        index.pack(&mut batch.key_buf).unwrap();
+        batch.value_buf.clear();
+        // Packing to bytes can't fail.
+        diff.pack(&mut batch.value_buf).unwrap();
+
+        batch.inner.put_cf(&cf, &batch.key_buf, &batch.value_buf);
+
+        Ok(())
+    }
+
+    fn batch_delete(&self, batch: &mut Self::Batch, index: &MilestoneIndex) -> Result<(), Self::Error> {
+        let cf = self
+            .inner
+            .cf_handle(CF_MILESTONE_INDEX_TO_DIFF)
+            .ok_or(Error::UnknownCf(CF_MILESTONE_INDEX_TO_DIFF))?;
+
+        batch.key_buf.clear();
+        // Packing to bytes can't fail.
+        index.pack(&mut batch.key_buf).unwrap();
+
+        batch.inner.delete_cf(&cf, &batch.key_buf);
+
+        Ok(())
+    }
+}
@@ -12,7 +12,7 @@
 use crate::{error::Error, storage::*};
 
 use bee_common::packable::Packable;
-use bee_ledger::{output::Output, spent::Spent};
+use bee_ledger::{diff::Diff, output::Output, spent::Spent};
 use bee_message::{
     payload::{
         indexation::{HashedIndex, HASHED_INDEX_LENGTH},
@@ -20,9 +20,11 @@ use bee_message::{
     },
     Message, MessageId, MESSAGE_ID_LENGTH,
 };
-use bee_protocol::tangle::MessageMetadata;
+use bee_protocol::{tangle::MessageMetadata, MilestoneIndex};
 use bee_storage::access::Fetch;
 
+use rocksdb::{Direction, IteratorMode};
+
 use std::convert::TryInto;
 
 #[async_trait::async_trait]
@@ -113,6 +115,47 @@ impl Fetch<HashedIndex, Vec<MessageId>> for Storage {
     }
 }
 
+/// Fetches a page of the message ids of an index, starting strictly after a given message id or from the first one,
+/// along with whether there are more message ids after the page.
+#[async_trait::async_trait]
+impl Fetch<(HashedIndex, Option<MessageId>), (Vec<MessageId>, bool)> for Storage {
+    async fn fetch(
+        &self,
+        (index, message_id): &(HashedIndex, Option<MessageId>),
+    ) -> Result<Option<(Vec<MessageId>, bool)>, <Self as Backend>::Error>
+    where
+        Self: Sized,
+    {
+        let cf = self
+            .inner
+            .cf_handle(CF_INDEX_TO_MESSAGE_ID)
+            .ok_or(Error::UnknownCf(CF_INDEX_TO_MESSAGE_ID))?;
+
+        let mut start = index.as_ref().to_vec();
+        if let Some(message_id) = message_id {
+            start.extend_from_slice(message_id.as_ref());
+        }
+
+        // Reading one message id beyond the limit tells whether there is a next page without fetching it.
+        let mut message_ids = self
+            .inner
+            .iterator_cf(&cf, IteratorMode::From(&start, Direction::Forward))
+            .take_while(|(key, _)| key.starts_with(index.as_ref()))
+            .filter(|(key, _)| key.as_ref() != start.as_slice())
+            .map(|(key, _)| {
+                let (_, message_id) = key.split_at(HASHED_INDEX_LENGTH);
+                let message_id: [u8; MESSAGE_ID_LENGTH] = message_id.try_into().unwrap();
+                MessageId::from(message_id)
+            })
+            .take(self.config.fetch_index_limit + 1)
+            .collect::<Vec<MessageId>>();
+        let more = message_ids.len() > self.config.fetch_index_limit;
+        message_ids.truncate(self.config.fetch_index_limit);
+
+        Ok(Some((message_ids, more)))
+    }
+}
+
 #[async_trait::async_trait]
 impl Fetch<OutputId, Output> for Storage {
     async fn fetch(&self, output_id: &OutputId) -> Result<Option<Output>, <Self as Backend>::Error>
@@ -174,3 +217,81 @@ impl Fetch<Ed25519Address, Vec<OutputId>> for Storage {
         ))
     }
 }
+
+/// Fetches a page of the output ids of an address, starting strictly after a given output id or from the first one,
+/// along with whether there are more output ids after the page.
+#[async_trait::async_trait]
+impl Fetch<(Ed25519Address, Option<OutputId>), (Vec<OutputId>, bool)> for Storage {
+    async fn fetch(
+        &self,
+        (address, output_id): &(Ed25519Address, Option<OutputId>),
+    ) -> Result<Option<(Vec<OutputId>, bool)>, <Self as Backend>::Error>
+    where
+        Self: Sized,
+    {
+        let cf = self
+            .inner
+            .cf_handle(CF_ED25519_ADDRESS_TO_OUTPUT_ID)
+            .ok_or(Error::UnknownCf(CF_ED25519_ADDRESS_TO_OUTPUT_ID))?;
+
+        let mut start = address.as_ref().to_vec();
+        if let Some(output_id) = output_id {
+            start.extend_from_slice(&output_id.pack_new());
+        }
+
+        // Reading one output id beyond the limit tells whether there is a next page without fetching it.
+        let mut output_ids = self
+            .inner
+            .iterator_cf(&cf, IteratorMode::From(&start, Direction::Forward))
+            .take_while(|(key, _)| key.starts_with(address.as_ref()))
+            .filter(|(key, _)| key.as_ref() != start.as_slice())
+            .map(|(key, _)| {
+                let (_, output_id) = key.split_at(ED25519_ADDRESS_LENGTH);
+                From::<[u8; OUTPUT_ID_LENGTH]>::from(output_id.try_into().unwrap())
+            })
+            .take(self.config.fetch_output_id_limit + 1)
+            .collect::<Vec<OutputId>>();
+        let more = output_ids.len() > self.config.fetch_output_id_limit;
+        output_ids.truncate(self.config.fetch_output_id_limit);
+
+        Ok(Some((output_ids, more)))
+    }
+}
+
+#[async_trait::async_trait]
+impl Fetch<MilestoneIndex, MessageId> for Storage {
+    async fn fetch(&self, index: &MilestoneIndex) -> Result<Option<MessageId>, <Self as Backend>::Error>
+    where
+        Self: Sized,
+    {
+        let cf = self
+            .inner
+            .cf_handle(CF_MILESTONE_INDEX_TO_MESSAGE_ID)
+            .ok_or(Error::UnknownCf(CF_MILESTONE_INDEX_TO_MESSAGE_ID))?;
+
+        if let Some(res) = self.inner.get_cf(&cf, index.pack_new())? {
+            Ok(Some(MessageId::unpack(&mut res.as_slice()).unwrap()))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Fetch<MilestoneIndex, Diff> for Storage {
+    async fn fetch(&self, index: &MilestoneIndex) -> Result<Option<Diff>, <Self as Backend>::Error>
+    where
+        Self: Sized,
+    {
+        let cf = self
+            .inner
+            .cf_handle(CF_MILESTONE_INDEX_TO_DIFF)
+            .ok_or(Error::UnknownCf(CF_MILESTONE_INDEX_TO_DIFF))?;
+
+        if let Some(res) = self.inner.get_cf(&cf, index.pack_new())? {
+            Ok(Some(Diff::unpack(&mut res.as_slice()).unwrap()))
+        } else {
+            Ok(None)
+        }
+    }
+}
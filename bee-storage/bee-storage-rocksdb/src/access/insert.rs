@@ -20,7 +20,7 @@ use bee_message::{
     },
     Message, MessageId,
 };
-use bee_protocol::tangle::MessageMetadata;
+use bee_protocol::{tangle::MessageMetadata, MilestoneIndex};
 use bee_storage::access::Insert;
 
 #[async_trait::async_trait]
@@ -151,3 +151,17 @@ impl Insert<(Ed25519Address, OutputId), ()> for Storage {
         Ok(())
     }
 }
+
+#[async_trait::async_trait]
+impl Insert<MilestoneIndex, MessageId> for Storage {
+    async fn insert(&self, index: &MilestoneIndex, message_id: &MessageId) -> Result<(), <Self as Backend>::Error> {
+        let cf = self
+            .inner
+            .cf_handle(CF_MILESTONE_INDEX_TO_MESSAGE_ID)
+            .ok_or(Error::UnknownCf(CF_MILESTONE_INDEX_TO_MESSAGE_ID))?;
+
+        self.inner.put_cf(&cf, index.pack_new(), message_id)?;
+
+        Ok(())
+    }
+}
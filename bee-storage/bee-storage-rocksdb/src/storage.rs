@@ -24,6 +24,8 @@ use bee_message::{
 use async_trait::async_trait;
 use rocksdb::{ColumnFamilyDescriptor, DBCompactionStyle, DBCompressionType, Options, SliceTransform, DB};
 
+use std::path::Path;
+
 pub(crate) const CF_MESSAGE_ID_TO_MESSAGE: &str = "message_id_to_message";
 pub(crate) const CF_MESSAGE_ID_TO_METADATA: &str = "message_id_to_metadata";
 pub(crate) const CF_MESSAGE_ID_TO_MESSAGE_ID: &str = "message_id_to_message_id";
@@ -32,6 +34,21 @@ pub(crate) const CF_OUTPUT_ID_TO_OUTPUT: &str = "output_id_to_output";
 pub(crate) const CF_OUTPUT_ID_TO_SPENT: &str = "output_id_to_spent";
 pub(crate) const CF_OUTPUT_ID_UNSPENT: &str = "output_id_unspent";
 pub(crate) const CF_ED25519_ADDRESS_TO_OUTPUT_ID: &str = "ed25519_address_to_output_id";
+pub(crate) const CF_MILESTONE_INDEX_TO_MESSAGE_ID: &str = "milestone_index_to_message_id";
+pub(crate) const CF_MILESTONE_INDEX_TO_DIFF: &str = "milestone_index_to_diff";
+
+const COLUMN_FAMILIES: [&str; 10] = [
+    CF_MESSAGE_ID_TO_MESSAGE,
+    CF_MESSAGE_ID_TO_METADATA,
+    CF_MESSAGE_ID_TO_MESSAGE_ID,
+    CF_INDEX_TO_MESSAGE_ID,
+    CF_OUTPUT_ID_TO_OUTPUT,
+    CF_OUTPUT_ID_TO_SPENT,
+    CF_OUTPUT_ID_UNSPENT,
+    CF_ED25519_ADDRESS_TO_OUTPUT_ID,
+    CF_MILESTONE_INDEX_TO_MESSAGE_ID,
+    CF_MILESTONE_INDEX_TO_DIFF,
+];
 
 pub struct Storage {
     pub(crate) config: StorageConfig,
@@ -65,6 +82,11 @@ impl Storage {
         options.set_prefix_extractor(prefix_extractor);
         let cf_ed25519_address_to_output_id = ColumnFamilyDescriptor::new(CF_ED25519_ADDRESS_TO_OUTPUT_ID, options);
 
+        let cf_milestone_index_to_message_id =
+            ColumnFamilyDescriptor::new(CF_MILESTONE_INDEX_TO_MESSAGE_ID, Options::default());
+
+        let cf_milestone_index_to_diff = ColumnFamilyDescriptor::new(CF_MILESTONE_INDEX_TO_DIFF, Options::default());
+
         let mut opts = Options::default();
 
         opts.create_if_missing(config.create_if_missing);
@@ -97,12 +119,46 @@ impl Storage {
             cf_output_id_to_spent,
             cf_output_id_unspent,
             cf_ed25519_address_to_output_id,
+            cf_milestone_index_to_message_id,
+            cf_milestone_index_to_diff,
         ];
 
         Ok(DB::open_cf_descriptors(&opts, config.path, column_familes)?)
     }
 }
 
+/// Parses a RocksDB statistics dump; tickers are reported by name, histograms as `<name>.count` and `<name>.sum`.
+fn parse_statistics(dump: &str) -> Vec<(String, u64)> {
+    let mut statistics = Vec::new();
+
+    for line in dump.lines() {
+        let mut tokens = line.split_whitespace();
+        let name = match tokens.next() {
+            Some(name) => name,
+            None => continue,
+        };
+        let values = tokens.collect::<Vec<&str>>();
+        // Values come as `KEY : VALUE` triples, e.g. `COUNT : 42` or `P50 : 1.000000 ... COUNT : 42 SUM : 84`.
+        let value = |key: &str| {
+            values
+                .windows(3)
+                .find(|window| window[0] == key && window[1] == ":")
+                .and_then(|window| window[2].parse::<u64>().ok())
+        };
+
+        match (value("COUNT"), value("SUM")) {
+            (Some(count), Some(sum)) => {
+                statistics.push((format!("{}.count", name), count));
+                statistics.push((format!("{}.sum", name), sum));
+            }
+            (Some(count), None) => statistics.push((name.to_string(), count)),
+            _ => {}
+        }
+    }
+
+    statistics
+}
+
 #[async_trait]
 impl Backend for Storage {
     type ConfigBuilder = RocksDBConfigBuilder;
@@ -125,4 +181,83 @@ impl Backend for Storage {
         }
         Ok(())
     }
+
+    /// It flushes the memtables to disk, the instance stays open.
+    fn flush(&self) -> Result<(), Self::Error> {
+        Ok(self.inner.flush()?)
+    }
+
+    /// It parses the RocksDB statistics dump, only available if `enable_statistics` is set.
+    fn statistics(&self) -> Vec<(String, u64)> {
+        match self.inner.property_value("rocksdb.options-statistics") {
+            Ok(Some(dump)) => parse_statistics(&dump),
+            _ => Vec::new(),
+        }
+    }
+
+    /// It compacts the whole key range of every column family, blocking until done.
+    fn compact(&self) -> Result<(), Self::Error> {
+        for &cf in COLUMN_FAMILIES.iter() {
+            let cf_handle = self.inner.cf_handle(cf).ok_or(Error::UnknownCf(cf))?;
+            self.inner.compact_range_cf(cf_handle, None::<&[u8]>, None::<&[u8]>);
+        }
+
+        Ok(())
+    }
+
+    /// It sums the size of the SST files of every column family, data still in memtables isn't accounted for.
+    fn size(&self) -> Result<Option<u64>, Self::Error> {
+        let mut size = 0;
+
+        for &cf in COLUMN_FAMILIES.iter() {
+            let cf_handle = self.inner.cf_handle(cf).ok_or(Error::UnknownCf(cf))?;
+            size += self
+                .inner
+                .property_int_value_cf(cf_handle, "rocksdb.total-sst-files-size")?
+                .unwrap_or_default();
+        }
+
+        Ok(Some(size))
+    }
+
+    /// It returns the directory of the RocksDB instance.
+    fn path(config: &Self::Config) -> Option<&Path> {
+        Some(Path::new(&config.path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    // In the format of the `rocksdb.options-statistics` property.
+    const DUMP: &str = "rocksdb.block.cache.miss COUNT : 5
+rocksdb.block.cache.hit COUNT : 0
+rocksdb.bytes.written COUNT : 1823
+rocksdb.db.get.micros P50 : 2.500000 P95 : 7.000000 P99 : 11.960000 P100 : 12.000000 COUNT : 14 SUM : 61
+rocksdb.db.write.micros P50 : 0.000000 P95 : 0.000000 P99 : 0.000000 P100 : 0.000000 COUNT : 0 SUM : 0
+";
+
+    #[test]
+    fn statistics_dump() {
+        assert_eq!(
+            parse_statistics(DUMP),
+            vec![
+                ("rocksdb.block.cache.miss".to_string(), 5),
+                ("rocksdb.block.cache.hit".to_string(), 0),
+                ("rocksdb.bytes.written".to_string(), 1823),
+                ("rocksdb.db.get.micros.count".to_string(), 14),
+                ("rocksdb.db.get.micros.sum".to_string(), 61),
+                ("rocksdb.db.write.micros.count".to_string(), 0),
+                ("rocksdb.db.write.micros.sum".to_string(), 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn statistics_unknown_format() {
+        assert!(parse_statistics("").is_empty());
+        assert!(parse_statistics("rocksdb.block.cache.miss = 5").is_empty());
+    }
 }
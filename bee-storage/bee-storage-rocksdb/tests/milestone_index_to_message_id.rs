@@ -0,0 +1,42 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use bee_message::MessageId;
+use bee_protocol::MilestoneIndex;
+use bee_storage::{
+    access::{Fetch, Insert},
+    storage::Backend,
+};
+use bee_storage_rocksdb::{config::RocksDBConfigBuilder, storage::Storage};
+use bee_test::rand::{integer::random_integer, message::random_message_id};
+
+#[tokio::test]
+async fn access() {
+    let config = RocksDBConfigBuilder::default().finish();
+    let storage = Storage::start(config).await.unwrap();
+
+    let index = MilestoneIndex(random_integer());
+    let message_id = random_message_id();
+
+    assert!(Fetch::<MilestoneIndex, MessageId>::fetch(&storage, &index)
+        .await
+        .unwrap()
+        .is_none());
+
+    storage.insert(&index, &message_id).await.unwrap();
+
+    assert_eq!(
+        Fetch::<MilestoneIndex, MessageId>::fetch(&storage, &index)
+            .await
+            .unwrap(),
+        Some(message_id)
+    );
+}
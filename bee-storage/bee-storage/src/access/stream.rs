@@ -17,7 +17,7 @@ use futures::stream::Stream;
 /// therefore it should be explicitly implemented for the corresponding Backend.
 #[async_trait::async_trait]
 pub trait AsStream<'a, K, V>: Backend {
-    type Stream: Stream;
+    type Stream: Stream<Item = (K, V)>;
     /// This method returns the Stream object for the provided <K, V> collection in order to later execute async next()
     /// calls
     async fn stream(&'a self) -> Result<Self::Stream, Self::Error>
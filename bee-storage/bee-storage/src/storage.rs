@@ -12,7 +12,7 @@
 use async_trait::async_trait;
 use serde::de::DeserializeOwned;
 
-use std::error::Error;
+use std::{error::Error, path::Path};
 
 #[async_trait]
 /// Trait to be implemented on storage backend, which determine how to start and shutdown the storage.
@@ -28,4 +28,34 @@ pub trait Backend: Sized + Send + Sync + 'static {
     /// Shutdown method should impl how to shutdown the corrsponding database.
     /// It takes the ownership of self, and returns () or error.
     async fn shutdown(self) -> Result<(), Box<dyn Error>>;
+
+    /// Flush method persists the pending writes without shutting the storage down.
+    /// Backends that write synchronously do nothing.
+    fn flush(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Statistics method returns the backend specific counters as `(name, value)` pairs.
+    /// Backends that don't collect any statistics, or have them disabled, return nothing.
+    fn statistics(&self) -> Vec<(String, u64)> {
+        Vec::new()
+    }
+
+    /// Compact method reclaims the space held by deleted or overwritten entries.
+    /// Backends that don't need compaction do nothing.
+    fn compact(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Size method returns the space used on disk in bytes.
+    /// Backends that can't tell return nothing.
+    fn size(&self) -> Result<Option<u64>, Self::Error> {
+        Ok(None)
+    }
+
+    /// Path method returns the directory the storage configured by `config` keeps its files in.
+    /// Backends that don't store files return nothing.
+    fn path(_config: &Self::Config) -> Option<&Path> {
+        None
+    }
 }